@@ -2,3 +2,8 @@ pub mod enums;
 pub mod link;
 pub mod object;
 pub mod properties;
+pub mod security;
+
+/// The special collection that addresses an object to everyone.
+/// https://www.w3.org/TR/activitypub/#public-addressing
+pub const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
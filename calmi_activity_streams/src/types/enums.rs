@@ -1,18 +1,29 @@
-use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::types::link::Link;
+use crate::types::link::hashtag::Hashtag;
 use crate::types::object::Object;
 use crate::types::object::accept::Accept;
 use crate::types::object::activity::Activity;
 use crate::types::object::announce::Announce;
 use crate::types::object::collection::Collection;
+use crate::types::object::collection_page::CollectionPage;
 use crate::types::object::create::Create;
+use crate::types::object::delete::Delete;
+use crate::types::object::document::Document;
 use crate::types::object::follow::Follow;
+use crate::types::object::image::Image;
 use crate::types::object::like::Like;
 use crate::types::object::note::Note;
 use crate::types::object::ordered_collection::OrderedCollection;
+use crate::types::object::ordered_collection_page::OrderedCollectionPage;
 use crate::types::object::person::Person;
+use crate::types::object::reject::Reject;
+use crate::types::object::tombstone::Tombstone;
 use crate::types::object::undo::Undo;
+use crate::types::object::update::Update;
+use crate::types::object::video::Video;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
@@ -21,6 +32,40 @@ pub enum SingleOrMultiple<T> {
     Multiple(Vec<T>),
 }
 
+/// https://www.w3.org/TR/json-ld11/#the-context
+/// An entry of `@context`: either a context URI or inline term definitions,
+/// such as the `{"manuallyApprovesFollowers": "as:manuallyApprovesFollowers"}` Mastodon sends.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ContextEntry {
+    Uri(String),
+    Definitions(serde_json::Map<String, serde_json::Value>),
+}
+
+impl From<&str> for ContextEntry {
+    fn from(uri: &str) -> Self {
+        ContextEntry::Uri(uri.to_string())
+    }
+}
+
+impl From<String> for ContextEntry {
+    fn from(uri: String) -> Self {
+        ContextEntry::Uri(uri)
+    }
+}
+
+impl PartialEq<str> for ContextEntry {
+    fn eq(&self, other: &str) -> bool {
+        matches!(self, ContextEntry::Uri(uri) if uri == other)
+    }
+}
+
+impl PartialEq<&str> for ContextEntry {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum ObjectOrLinkOrStringUrl {
@@ -32,30 +77,225 @@ pub enum ObjectOrLinkOrStringUrl {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum ObjectOrStringUrl {
+    Object(Box<ObjectBased>),
+    Str(String),
+}
+
+/// Tried in order, so Hashtags are told apart from other Links by their `type`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum TagEntry {
+    Hashtag(Hashtag),
     Object(ObjectBased),
+    Link(Link),
     Str(String),
 }
 
+/// Range of `icon` and `image`. Tried in order, so a Link parses as an Image without `url`.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
-pub enum LinkOrStringUrl {
+pub enum ImageOrLinkOrStringUrl {
+    Image(Image),
     Link(Link),
     Str(String),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
+pub enum LinkOrStringUrl {
+    Link(Link),
+    Str(String),
+}
+
+/// Deserialized by looking at `type`, since every field of every object is optional
+/// and trying the variants in order would always stop at `Object`.
+/// Unknown types fall back to `Object`; Link types are refused so that
+/// `ObjectOrLinkOrStringUrl` can pick them up as `Link`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(untagged)]
 pub enum ObjectBased {
     Object(Object),
     Person(Person),
     Note(Note),
     Activity(Activity),
     Create(Create),
+    Delete(Delete),
     Follow(Follow),
     Accept(Accept),
+    Reject(Reject),
     Undo(Undo),
+    Update(Update),
     Like(Like),
     Announce(Announce),
     Collection(Collection),
+    CollectionPage(CollectionPage),
     OrderedCollection(OrderedCollection),
+    OrderedCollectionPage(OrderedCollectionPage),
+    Tombstone(Tombstone),
+    Document(Document),
+    Image(Image),
+    Video(Video),
+}
+
+impl<'de> Deserialize<'de> for ObjectBased {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let kind = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default();
+
+        let typed = match kind {
+            "Link" | "Mention" | "Hashtag" => {
+                return Err(D::Error::custom(format!(
+                    "{} is a Link, not an Object",
+                    kind
+                )));
+            }
+            // Actor types share the shape of Person.
+            "Person" | "Service" | "Application" | "Group" | "Organization" => {
+                serde_json::from_value(value.clone()).map(ObjectBased::Person)
+            }
+            "Note" => serde_json::from_value(value.clone()).map(ObjectBased::Note),
+            "Activity" => serde_json::from_value(value.clone()).map(ObjectBased::Activity),
+            "Create" => serde_json::from_value(value.clone()).map(ObjectBased::Create),
+            "Delete" => serde_json::from_value(value.clone()).map(ObjectBased::Delete),
+            "Follow" => serde_json::from_value(value.clone()).map(ObjectBased::Follow),
+            "Accept" => serde_json::from_value(value.clone()).map(ObjectBased::Accept),
+            "Reject" => serde_json::from_value(value.clone()).map(ObjectBased::Reject),
+            "Undo" => serde_json::from_value(value.clone()).map(ObjectBased::Undo),
+            "Update" => serde_json::from_value(value.clone()).map(ObjectBased::Update),
+            "Like" => serde_json::from_value(value.clone()).map(ObjectBased::Like),
+            "Announce" => serde_json::from_value(value.clone()).map(ObjectBased::Announce),
+            "Collection" => serde_json::from_value(value.clone()).map(ObjectBased::Collection),
+            "CollectionPage" => {
+                serde_json::from_value(value.clone()).map(ObjectBased::CollectionPage)
+            }
+            "OrderedCollection" => {
+                serde_json::from_value(value.clone()).map(ObjectBased::OrderedCollection)
+            }
+            "OrderedCollectionPage" => {
+                serde_json::from_value(value.clone()).map(ObjectBased::OrderedCollectionPage)
+            }
+            "Tombstone" => serde_json::from_value(value.clone()).map(ObjectBased::Tombstone),
+            // Audio shares the shape of Document.
+            "Document" | "Audio" => {
+                serde_json::from_value(value.clone()).map(ObjectBased::Document)
+            }
+            "Image" => serde_json::from_value(value.clone()).map(ObjectBased::Image),
+            "Video" => serde_json::from_value(value.clone()).map(ObjectBased::Video),
+            _ => serde_json::from_value(value.clone()).map(ObjectBased::Object),
+        };
+
+        // A malformed typed object is still usable by its id and type.
+        typed
+            .or_else(|_| serde_json::from_value(value).map(ObjectBased::Object))
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_context_with_term_definitions() {
+        let json = r#"[
+            "https://www.w3.org/ns/activitystreams",
+            {"manuallyApprovesFollowers": "as:manuallyApprovesFollowers"}
+        ]"#;
+        let context: SingleOrMultiple<ContextEntry> = serde_json::from_str(json).unwrap();
+        match context {
+            SingleOrMultiple::Multiple(entries) => {
+                assert_eq!(entries.len(), 2);
+                assert_eq!(entries[0], "https://www.w3.org/ns/activitystreams");
+                match &entries[1] {
+                    ContextEntry::Definitions(terms) => {
+                        assert_eq!(
+                            terms["manuallyApprovesFollowers"],
+                            "as:manuallyApprovesFollowers"
+                        );
+                    }
+                    _ => panic!("Expected term definitions"),
+                }
+            }
+            _ => panic!("Expected multiple contexts"),
+        }
+    }
+
+    #[test]
+    fn deserialize_embedded_object_by_type() {
+        let json = r#"{
+            "id": "http://example.org/note/1",
+            "type": "Note",
+            "content": "Hello"
+        }"#;
+        let object: ObjectBased = serde_json::from_str(json).unwrap();
+        match object {
+            ObjectBased::Note(note) => assert_eq!(note.content, Some("Hello".to_string())),
+            other => panic!("Expected Note, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn deserialize_actor_types_as_person() {
+        let json = r#"{
+            "id": "http://example.org/bot",
+            "type": "Service",
+            "preferredUsername": "bot"
+        }"#;
+        let object: ObjectBased = serde_json::from_str(json).unwrap();
+        assert!(matches!(object, ObjectBased::Person(_)));
+    }
+
+    #[test]
+    fn deserialize_unknown_type_as_object() {
+        let json = r#"{
+            "id": "http://example.org/question/1",
+            "type": "Question"
+        }"#;
+        let object: ObjectBased = serde_json::from_str(json).unwrap();
+        match object {
+            ObjectBased::Object(object) => {
+                assert_eq!(object.r#type, Some("Question".to_string()))
+            }
+            other => panic!("Expected Object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn deserialize_link_types_as_link() {
+        let json = r#"{
+            "type": "Mention",
+            "href": "http://example.org/users/alice"
+        }"#;
+        let value: ObjectOrLinkOrStringUrl = serde_json::from_str(json).unwrap();
+        match value {
+            ObjectOrLinkOrStringUrl::Link(link) => {
+                assert_eq!(
+                    link.href,
+                    Some("http://example.org/users/alice".to_string())
+                )
+            }
+            other => panic!("Expected Link, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn serialize_context_entries() {
+        let context: SingleOrMultiple<ContextEntry> = SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+            ContextEntry::Definitions(
+                serde_json::json!({"sensitive": "as:sensitive"})
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            ),
+        ]);
+        let json = serde_json::to_string(&context).unwrap();
+        assert_eq!(
+            json,
+            r#"["https://www.w3.org/ns/activitystreams",{"sensitive":"as:sensitive"}]"#
+        );
+    }
 }
@@ -1,3 +1,5 @@
+pub mod hashtag;
+
 use serde::{Deserialize, Serialize};
 
 use crate::types::properties::{Context, Href, MediaType, Name, Rel, Type};
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::link::Link;
+use crate::types::properties::{Href, Name};
+
+/// Not part of the Activity Vocabulary: servers define it as `as:Hashtag` in `@context`.
+/// It has the shape of a Link, so it is read and written as one with `type` set to `Hashtag`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(try_from = "Link", into = "Link")]
+pub struct Hashtag {
+    pub href: Option<Href>,
+
+    /// The topic, with its leading `#`.
+    pub name: Name,
+}
+
+impl TryFrom<Link> for Hashtag {
+    type Error = String;
+
+    fn try_from(link: Link) -> Result<Self, Self::Error> {
+        if link.r#type.as_deref() != Some("Hashtag") {
+            return Err(format!("{:?} is not a Hashtag", link.r#type));
+        }
+        let name = link.name.ok_or_else(|| "Hashtag has no name".to_string())?;
+        Ok(Hashtag {
+            href: link.href,
+            name,
+        })
+    }
+}
+
+impl From<Hashtag> for Link {
+    fn from(hashtag: Hashtag) -> Self {
+        Link {
+            context: None,
+            r#type: Some("Hashtag".to_string()),
+            href: hashtag.href,
+            rel: None,
+            media_type: None,
+            name: Some(hashtag.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_hashtag() {
+        let json = r##"{
+            "type": "Hashtag",
+            "href": "http://example.org/tags/rust",
+            "name": "#Rust"
+        }"##;
+        let hashtag: Hashtag = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            hashtag.href.as_deref(),
+            Some("http://example.org/tags/rust")
+        );
+        assert_eq!(hashtag.name, "#Rust");
+    }
+
+    #[test]
+    fn refuse_other_link_types() {
+        let json = r#"{
+            "type": "Mention",
+            "href": "http://example.org/person/bob",
+            "name": "@bob"
+        }"#;
+        assert!(serde_json::from_str::<Hashtag>(json).is_err());
+    }
+
+    #[test]
+    fn refuse_hashtag_without_name() {
+        let json = r#"{
+            "type": "Hashtag",
+            "href": "http://example.org/tags/rust"
+        }"#;
+        assert!(serde_json::from_str::<Hashtag>(json).is_err());
+    }
+
+    #[test]
+    fn serialize_hashtag() {
+        let hashtag = Hashtag {
+            href: Some("http://example.org/tags/rust".to_string()),
+            name: "#rust".to_string(),
+        };
+        let json = serde_json::to_value(&hashtag).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "Hashtag",
+                "href": "http://example.org/tags/rust",
+                "name": "#rust"
+            })
+        );
+    }
+}
@@ -2,13 +2,22 @@ pub mod accept;
 pub mod activity;
 pub mod announce;
 pub mod collection;
+pub mod collection_page;
 pub mod create;
+pub mod delete;
+pub mod document;
 pub mod follow;
+pub mod image;
 pub mod like;
 pub mod note;
 pub mod ordered_collection;
+pub mod ordered_collection_page;
 pub mod person;
+pub mod reject;
+pub mod tombstone;
 pub mod undo;
+pub mod update;
+pub mod video;
 
 use calmi_macros::object_based;
 use serde::{Deserialize, Serialize};
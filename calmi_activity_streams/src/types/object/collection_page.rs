@@ -0,0 +1,96 @@
+use calmi_macros::object_based;
+use serde::{Deserialize, Serialize};
+
+use crate::types::properties::{Items, Next, PartOf, Prev, TotalItems};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-collectionpage
+/// CollectionPage extends Collection
+/// One page of the items of a Collection.
+#[object_based]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionPage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<TotalItems>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<Items>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<Box<PartOf>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<Box<Next>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<Box<Prev>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{LinkOrStringUrl, ObjectOrLinkOrStringUrl, SingleOrMultiple};
+
+    #[test]
+    fn deserialize_minimal_collection_page() {
+        let json = r#"{
+            "id": "http://example.org/collection?page=1",
+            "type": "CollectionPage"
+        }"#;
+        let page: Result<CollectionPage, _> = serde_json::from_str(json);
+        assert!(page.is_ok());
+        let p = page.unwrap();
+        assert_eq!(p.r#type, Some("CollectionPage".to_string()));
+        assert!(p.items.is_none());
+        assert!(p.part_of.is_none());
+        assert!(p.next.is_none());
+        assert!(p.prev.is_none());
+    }
+
+    #[test]
+    fn deserialize_collection_page_with_all_fields() {
+        let json = r#"{
+            "id": "http://example.org/collection?page=1",
+            "type": "CollectionPage",
+            "partOf": "http://example.org/collection",
+            "next": "http://example.org/collection?page=2",
+            "items": ["http://example.org/note/1", "http://example.org/note/2"]
+        }"#;
+        let p: CollectionPage = serde_json::from_str(json).unwrap();
+        if let Some(LinkOrStringUrl::Str(part_of)) = p.part_of.as_deref() {
+            assert_eq!(part_of, "http://example.org/collection");
+        } else {
+            panic!("Expected string partOf");
+        }
+        assert!(p.next.is_some());
+        assert!(p.prev.is_none());
+        match p.items.as_deref() {
+            Some(SingleOrMultiple::Multiple(items)) => {
+                assert_eq!(items.len(), 2);
+                assert!(
+                    matches!(&items[0], ObjectOrLinkOrStringUrl::Str(item) if item == "http://example.org/note/1")
+                );
+            }
+            _ => panic!("Expected multiple items"),
+        }
+    }
+
+    #[test]
+    fn serialize_collection_page() {
+        let page = CollectionPage {
+            context: None,
+            id: Some("http://example.org/collection?page=1".to_string()),
+            r#type: Some("CollectionPage".to_string()),
+            total_items: None,
+            items: None,
+            part_of: Some(Box::new(LinkOrStringUrl::Str(
+                "http://example.org/collection".to_string(),
+            ))),
+            next: None,
+            prev: None,
+        };
+        let json = serde_json::to_string(&page).unwrap();
+        let expected = r#"{"id":"http://example.org/collection?page=1","type":"CollectionPage","partOf":"http://example.org/collection"}"#;
+        assert_eq!(json, expected);
+    }
+}
@@ -1,7 +1,7 @@
 use calmi_macros::object_based;
 use serde::{Deserialize, Serialize};
 
-use crate::types::properties::{Actor, ObjectProperty};
+use crate::types::properties::{Actor, Cc, ObjectProperty, To};
 
 /// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-create
 /// Create extends Activity
@@ -15,6 +15,12 @@ pub struct Create {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub object: Option<Box<ObjectProperty>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Box<To>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cc: Option<Box<Cc>>,
 }
 
 #[cfg(test)]
@@ -44,7 +50,9 @@ mod tests {
             "id": "http://example.org/create/2",
             "type": "Create",
             "actor": "http://example.org/person/1",
-            "object": "http://example.org/note/1"
+            "object": "http://example.org/note/1",
+            "to": "https://www.w3.org/ns/activitystreams#Public",
+            "cc": ["http://example.org/person/1/followers"]
         }"#;
         let create: Result<Create, _> = serde_json::from_str(json);
         assert!(create.is_ok());
@@ -53,6 +61,8 @@ mod tests {
         assert_eq!(c.r#type, Some("Create".to_string()));
         assert!(c.actor.is_some());
         assert!(c.object.is_some());
+        assert!(c.to.is_some());
+        assert!(c.cc.is_some());
         if let Some(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Str(actor))) =
             c.actor.as_deref()
         {
@@ -77,6 +87,8 @@ mod tests {
             r#type: Some("Create".to_string()),
             actor: None,
             object: None,
+            to: None,
+            cc: None,
         };
         let json = serde_json::to_string(&create).unwrap();
         let expected = r#"{"id":"http://example.org/create/1","type":"Create"}"#;
@@ -91,10 +103,14 @@ mod tests {
             r#type: Some("Create".to_string()),
             actor: None,
             object: None,
+            to: None,
+            cc: None,
         };
         let json = serde_json::to_string(&create).unwrap();
         assert!(!json.contains("actor"));
         assert!(!json.contains("object"));
+        assert!(!json.contains(r#""to""#));
+        assert!(!json.contains(r#""cc""#));
     }
 
     #[test]
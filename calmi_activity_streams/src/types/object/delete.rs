@@ -0,0 +1,87 @@
+use calmi_macros::object_based;
+use serde::{Deserialize, Serialize};
+
+use crate::types::properties::{Actor, ObjectProperty};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-delete
+/// Delete extends Activity
+/// Indicates that the actor has deleted the object.
+#[object_based]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Delete {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<Box<Actor>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object: Option<Box<ObjectProperty>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple};
+
+    #[test]
+    fn deserialize_minimal_delete() {
+        let json = r#"{
+            "id": "http://example.org/delete/1",
+            "type": "Delete"
+        }"#;
+        let delete: Result<Delete, _> = serde_json::from_str(json);
+        assert!(delete.is_ok());
+        let d = delete.unwrap();
+        assert_eq!(d.id, Some("http://example.org/delete/1".to_string()));
+        assert_eq!(d.r#type, Some("Delete".to_string()));
+        assert!(d.actor.is_none());
+        assert!(d.object.is_none());
+    }
+
+    #[test]
+    fn deserialize_delete_with_tombstone() {
+        let json = r#"{
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "id": "http://example.org/delete/2",
+            "type": "Delete",
+            "actor": "http://example.org/person/1",
+            "object": {
+                "id": "http://example.org/note/1",
+                "type": "Tombstone"
+            }
+        }"#;
+        let delete: Result<Delete, _> = serde_json::from_str(json);
+        assert!(delete.is_ok());
+        let d = delete.unwrap();
+        if let Some(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Str(actor))) =
+            d.actor.as_deref()
+        {
+            assert_eq!(actor, "http://example.org/person/1");
+        } else {
+            panic!("Expected single string actor");
+        }
+        if let Some(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Object(
+            ObjectBased::Tombstone(tombstone),
+        ))) = d.object.as_deref()
+        {
+            assert_eq!(tombstone.id, Some("http://example.org/note/1".to_string()));
+        } else {
+            panic!("Expected embedded Tombstone");
+        }
+    }
+
+    #[test]
+    fn serialize_delete() {
+        let delete = Delete {
+            context: None,
+            id: Some("http://example.org/delete/1".to_string()),
+            r#type: Some("Delete".to_string()),
+            actor: None,
+            object: Some(Box::new(SingleOrMultiple::Single(
+                ObjectOrLinkOrStringUrl::Str("http://example.org/note/1".to_string()),
+            ))),
+        };
+        let json = serde_json::to_string(&delete).unwrap();
+        let expected = r#"{"id":"http://example.org/delete/1","type":"Delete","object":"http://example.org/note/1"}"#;
+        assert_eq!(json, expected);
+    }
+}
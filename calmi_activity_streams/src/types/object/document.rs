@@ -0,0 +1,103 @@
+use calmi_macros::object_based;
+use serde::{Deserialize, Serialize};
+
+use crate::types::properties::{Height, MediaType, Name, Url, Width};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-document
+/// Document extends Object
+/// A document of any kind.
+/// `name` carries the alt text of attachments.
+#[object_based]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Name>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<MediaType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Box<Url>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<Width>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<Height>,
+
+    /// https://docs.joinmastodon.org/spec/activitypub/#blurhash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blurhash: Option<String>,
+
+    /// https://docs.joinmastodon.org/spec/activitypub/#focalPoint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focal_point: Option<[f64; 2]>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{LinkOrStringUrl, SingleOrMultiple};
+
+    #[test]
+    fn deserialize_attached_document() {
+        let json = r#"{
+            "type": "Document",
+            "mediaType": "application/pdf",
+            "url": "http://example.org/files/paper.pdf",
+            "name": "The paper"
+        }"#;
+        let d: Document = serde_json::from_str(json).unwrap();
+        assert_eq!(d.r#type, Some("Document".to_string()));
+        assert_eq!(d.media_type, Some("application/pdf".to_string()));
+        assert_eq!(d.name, Some("The paper".to_string()));
+        if let Some(SingleOrMultiple::Single(LinkOrStringUrl::Str(url))) = d.url.as_deref() {
+            assert_eq!(url, "http://example.org/files/paper.pdf");
+        } else {
+            panic!("Expected single string url");
+        }
+    }
+
+    #[test]
+    fn deserialize_document_with_mastodon_extensions() {
+        let json = r#"{
+            "type": "Document",
+            "mediaType": "image/jpeg",
+            "url": "http://example.org/files/beach.jpg",
+            "width": 1200,
+            "height": 800,
+            "blurhash": "UBL_:rOpGG-oBUNG,qRj2so|=eE1w^n4S5NH",
+            "focalPoint": [-0.5, 0.25]
+        }"#;
+        let d: Document = serde_json::from_str(json).unwrap();
+        assert_eq!(d.width, Some(1200));
+        assert_eq!(d.height, Some(800));
+        assert_eq!(
+            d.blurhash.as_deref(),
+            Some("UBL_:rOpGG-oBUNG,qRj2so|=eE1w^n4S5NH")
+        );
+        assert_eq!(d.focal_point, Some([-0.5, 0.25]));
+    }
+
+    #[test]
+    fn serialize_document() {
+        let document = Document {
+            context: None,
+            id: None,
+            r#type: Some("Document".to_string()),
+            name: None,
+            media_type: Some("audio/mpeg".to_string()),
+            url: Some(Box::new(SingleOrMultiple::Single(LinkOrStringUrl::Str(
+                "http://example.org/files/song.mp3".to_string(),
+            )))),
+            width: None,
+            height: None,
+            blurhash: None,
+            focal_point: None,
+        };
+        let json = serde_json::to_string(&document).unwrap();
+        let expected = r#"{"type":"Document","mediaType":"audio/mpeg","url":"http://example.org/files/song.mp3"}"#;
+        assert_eq!(json, expected);
+    }
+}
@@ -0,0 +1,93 @@
+use calmi_macros::object_based;
+use serde::{Deserialize, Serialize};
+
+use crate::types::properties::{Height, MediaType, Name, Url, Width};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-image
+/// Image extends Document
+/// An image document of any kind.
+#[object_based]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Image {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Name>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<MediaType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Box<Url>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<Width>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<Height>,
+
+    /// https://docs.joinmastodon.org/spec/activitypub/#blurhash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blurhash: Option<String>,
+
+    /// https://docs.joinmastodon.org/spec/activitypub/#focalPoint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focal_point: Option<[f64; 2]>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{LinkOrStringUrl, SingleOrMultiple};
+
+    #[test]
+    fn deserialize_minimal_image() {
+        let json = r#"{
+            "type": "Image"
+        }"#;
+        let image: Result<Image, _> = serde_json::from_str(json);
+        assert!(image.is_ok());
+        let i = image.unwrap();
+        assert_eq!(i.r#type, Some("Image".to_string()));
+        assert!(i.url.is_none());
+    }
+
+    #[test]
+    fn deserialize_image_with_url() {
+        let json = r#"{
+            "type": "Image",
+            "mediaType": "image/png",
+            "url": "http://example.org/avatar.png"
+        }"#;
+        let image: Result<Image, _> = serde_json::from_str(json);
+        assert!(image.is_ok());
+        let i = image.unwrap();
+        assert_eq!(i.media_type, Some("image/png".to_string()));
+        if let Some(SingleOrMultiple::Single(LinkOrStringUrl::Str(url))) = i.url.as_deref() {
+            assert_eq!(url, "http://example.org/avatar.png");
+        } else {
+            panic!("Expected single string url");
+        }
+    }
+
+    #[test]
+    fn serialize_image() {
+        let image = Image {
+            context: None,
+            id: None,
+            r#type: Some("Image".to_string()),
+            name: None,
+            media_type: Some("image/png".to_string()),
+            url: Some(Box::new(SingleOrMultiple::Single(LinkOrStringUrl::Str(
+                "http://example.org/avatar.png".to_string(),
+            )))),
+            width: None,
+            height: None,
+            blurhash: None,
+            focal_point: None,
+        };
+        let json = serde_json::to_string(&image).unwrap();
+        let expected =
+            r#"{"type":"Image","mediaType":"image/png","url":"http://example.org/avatar.png"}"#;
+        assert_eq!(json, expected);
+    }
+}
@@ -1,7 +1,10 @@
 use calmi_macros::object_based;
 use serde::{Deserialize, Serialize};
 
-use crate::types::properties::{AttributedTo, Content, Published, To};
+use crate::types::properties::{
+    Attachment, AttributedTo, Cc, Content, Context, InReplyTo, Likes, Published, Replies, Shares,
+    Summary, Tag, To, Updated, Url,
+};
 
 /// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-note
 /// Note extends Object
@@ -12,20 +15,64 @@ pub struct Note {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub to: Option<Box<To>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cc: Option<Box<Cc>>,
+
+    /// The content warning, shown in place of the content until the reader asks for it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<Summary>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<Content>,
 
+    /// as:sensitive, an extension widely used to hide content and media by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub attributed_to: Option<Box<AttributedTo>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub published: Option<Published>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<Updated>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_reply_to: Option<Box<InReplyTo>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replies: Option<Box<Replies>>,
+
+    /// `context` is taken by `@context`.
+    #[serde(rename = "context", skip_serializing_if = "Option::is_none")]
+    pub thread_context: Option<Box<Context>>,
+
+    /// Where the note can be seen in a browser.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Box<Url>>,
+
+    /// Hashtags, and mentions and other tags arriving as Links.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<Box<Tag>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<Box<Attachment>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub likes: Option<Box<Likes>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shares: Option<Box<Shares>>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::enums::{ObjectOrLinkOrStringUrl, SingleOrMultiple};
+    use crate::types::enums::{
+        LinkOrStringUrl, ObjectBased, ObjectOrLinkOrStringUrl, ObjectOrStringUrl, SingleOrMultiple,
+        TagEntry,
+    };
 
     #[test]
     fn deserialize_minimal_note() {
@@ -85,9 +132,21 @@ mod tests {
             id: Some("http://example.org/note/1".to_string()),
             r#type: Some("Note".to_string()),
             to: None,
+            cc: None,
+            summary: None,
             content: Some("Test content".to_string()),
+            sensitive: None,
             attributed_to: None,
             published: Some("2023-01-01T00:00:00Z".to_string()),
+            updated: None,
+            in_reply_to: None,
+            replies: None,
+            thread_context: None,
+            url: None,
+            tag: None,
+            attachment: None,
+            likes: None,
+            shares: None,
         };
         let json = serde_json::to_string(&note).unwrap();
         assert!(json.contains(r#""id":"http://example.org/note/1""#));
@@ -105,15 +164,259 @@ mod tests {
             id: Some("http://example.org/note/1".to_string()),
             r#type: Some("Note".to_string()),
             to: None,
+            cc: None,
+            summary: None,
             content: None,
+            sensitive: None,
             attributed_to: None,
             published: None,
+            updated: None,
+            in_reply_to: None,
+            replies: None,
+            thread_context: None,
+            url: None,
+            tag: None,
+            attachment: None,
+            likes: None,
+            shares: None,
         };
         let json = serde_json::to_string(&note).unwrap();
         assert!(!json.contains("to"));
         assert!(!json.contains("content"));
         assert!(!json.contains("attributedTo"));
         assert!(!json.contains("published"));
+        assert!(!json.contains("inReplyTo"));
+        assert!(!json.contains("updated"));
+    }
+
+    #[test]
+    fn deserialize_edited_note() {
+        let json = r#"{
+            "id": "http://example.org/note/4",
+            "type": "Note",
+            "content": "Hello, edited",
+            "published": "2014-08-21T12:34:56Z",
+            "updated": "2014-08-22T08:00:00Z"
+        }"#;
+        let n: Note = serde_json::from_str(json).unwrap();
+        assert_eq!(n.content, Some("Hello, edited".to_string()));
+        assert!(n.url.is_none());
+        assert_eq!(n.updated, Some("2014-08-22T08:00:00Z".to_string()));
+    }
+
+    #[test]
+    fn deserialize_note_with_interaction_collections() {
+        let json = r#"{
+            "id": "http://example.org/note/5",
+            "type": "Note",
+            "likes": {
+                "id": "http://example.org/note/5/likes",
+                "type": "OrderedCollection",
+                "totalItems": 3
+            },
+            "shares": "http://example.org/note/5/shares"
+        }"#;
+        let n: Note = serde_json::from_str(json).unwrap();
+        match n.likes.as_deref() {
+            Some(ObjectOrStringUrl::Object(object)) => match object.as_ref() {
+                ObjectBased::OrderedCollection(likes) => assert_eq!(likes.total_items, Some(3)),
+                _ => panic!("Expected an OrderedCollection"),
+            },
+            _ => panic!("Expected embedded likes collection"),
+        }
+        if let Some(ObjectOrStringUrl::Str(shares)) = n.shares.as_deref() {
+            assert_eq!(shares, "http://example.org/note/5/shares");
+        } else {
+            panic!("Expected string shares");
+        }
+    }
+
+    #[test]
+    fn deserialize_reply_with_cc() {
+        let json = r#"{
+            "id": "http://example.org/note/3",
+            "type": "Note",
+            "to": ["https://www.w3.org/ns/activitystreams#Public"],
+            "cc": ["http://example.org/person/2/followers"],
+            "inReplyTo": "http://example.org/note/2",
+            "url": "http://example.org/@john/3"
+        }"#;
+        let note: Result<Note, _> = serde_json::from_str(json);
+        assert!(note.is_ok());
+        let n = note.unwrap();
+        if let Some(SingleOrMultiple::Multiple(cc)) = n.cc.as_deref() {
+            assert_eq!(cc.len(), 1);
+        } else {
+            panic!("Expected multiple cc");
+        }
+        if let Some(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Str(parent))) =
+            n.in_reply_to.as_deref()
+        {
+            assert_eq!(parent, "http://example.org/note/2");
+        } else {
+            panic!("Expected single string inReplyTo");
+        }
+        if let Some(SingleOrMultiple::Single(LinkOrStringUrl::Str(url))) = n.url.as_deref() {
+            assert_eq!(url, "http://example.org/@john/3");
+        } else {
+            panic!("Expected single string url");
+        }
+    }
+
+    #[test]
+    fn deserialize_reply_with_replies_and_conversation_context() {
+        let json = r#"{
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "id": "http://example.org/note/6",
+            "type": "Note",
+            "inReplyTo": "http://example.org/note/2",
+            "context": "http://example.org/contexts/1",
+            "replies": {
+                "id": "http://example.org/note/6/replies",
+                "type": "Collection",
+                "totalItems": 0
+            }
+        }"#;
+        let n: Note = serde_json::from_str(json).unwrap();
+        assert!(n.context.is_some());
+        if let Some(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Str(context))) =
+            n.thread_context.as_deref()
+        {
+            assert_eq!(context, "http://example.org/contexts/1");
+        } else {
+            panic!("Expected single string context");
+        }
+        match n.replies.as_deref() {
+            Some(ObjectOrStringUrl::Object(object)) => match object.as_ref() {
+                ObjectBased::Collection(replies) => assert_eq!(
+                    replies.id.as_deref(),
+                    Some("http://example.org/note/6/replies")
+                ),
+                _ => panic!("Expected a Collection"),
+            },
+            _ => panic!("Expected embedded replies collection"),
+        }
+    }
+
+    #[test]
+    fn deserialize_note_with_content_warning() {
+        let json = r#"{
+            "id": "http://example.org/note/9",
+            "type": "Note",
+            "summary": "Spoilers",
+            "content": "The butler did it",
+            "sensitive": true
+        }"#;
+        let n: Note = serde_json::from_str(json).unwrap();
+        assert_eq!(n.summary.as_deref(), Some("Spoilers"));
+        assert_eq!(n.sensitive, Some(true));
+    }
+
+    #[test]
+    fn serialize_conversation_context_next_to_json_ld_context() {
+        let note = Note {
+            context: Some(SingleOrMultiple::Single(
+                "https://www.w3.org/ns/activitystreams".into(),
+            )),
+            id: Some("http://example.org/note/7".to_string()),
+            r#type: Some("Note".to_string()),
+            to: None,
+            cc: None,
+            summary: None,
+            content: None,
+            sensitive: None,
+            attributed_to: None,
+            published: None,
+            updated: None,
+            in_reply_to: None,
+            replies: Some(Box::new(ObjectOrStringUrl::Str(
+                "http://example.org/note/7/replies".to_string(),
+            ))),
+            thread_context: Some(Box::new(SingleOrMultiple::Single(
+                ObjectOrLinkOrStringUrl::Str("http://example.org/note/7".to_string()),
+            ))),
+            url: None,
+            tag: None,
+            attachment: None,
+            likes: None,
+            shares: None,
+        };
+        let json = serde_json::to_value(&note).unwrap();
+        assert_eq!(json["@context"], "https://www.w3.org/ns/activitystreams");
+        assert_eq!(json["context"], "http://example.org/note/7");
+        assert_eq!(json["replies"], "http://example.org/note/7/replies");
+    }
+
+    #[test]
+    fn deserialize_note_with_mention_and_hashtag_tags() {
+        let json = r##"{
+            "id": "http://example.org/note/8",
+            "type": "Note",
+            "content": "<p>@bob@example.org hi #Rust</p>",
+            "tag": [
+                {
+                    "type": "Mention",
+                    "href": "http://example.org/person/bob",
+                    "name": "@bob@example.org"
+                },
+                {
+                    "type": "Hashtag",
+                    "href": "http://example.org/tags/rust",
+                    "name": "#Rust"
+                }
+            ]
+        }"##;
+        let n: Note = serde_json::from_str(json).unwrap();
+        match n.tag.as_deref() {
+            Some(SingleOrMultiple::Multiple(tags)) => match &tags[..] {
+                [TagEntry::Link(mention), TagEntry::Hashtag(hashtag)] => {
+                    assert_eq!(mention.r#type.as_deref(), Some("Mention"));
+                    assert_eq!(
+                        mention.href.as_deref(),
+                        Some("http://example.org/person/bob")
+                    );
+                    assert_eq!(mention.name.as_deref(), Some("@bob@example.org"));
+                    assert_eq!(hashtag.name, "#Rust");
+                }
+                _ => panic!("Expected a Mention link and a Hashtag"),
+            },
+            _ => panic!("Expected multiple tags"),
+        }
+    }
+
+    #[test]
+    fn deserialize_note_with_attachments() {
+        let json = r#"{
+            "id": "http://example.org/note/9",
+            "type": "Note",
+            "attachment": [
+                {
+                    "type": "Image",
+                    "mediaType": "image/png",
+                    "url": "http://example.org/media/cat.png",
+                    "name": "A cat"
+                },
+                {
+                    "type": "Document",
+                    "mediaType": "video/mp4",
+                    "url": "http://example.org/media/clip.mp4"
+                }
+            ]
+        }"#;
+        let n: Note = serde_json::from_str(json).unwrap();
+        match n.attachment.as_deref() {
+            Some(SingleOrMultiple::Multiple(attachments)) => match &attachments[..] {
+                [
+                    ObjectOrLinkOrStringUrl::Object(ObjectBased::Image(image)),
+                    ObjectOrLinkOrStringUrl::Object(ObjectBased::Document(document)),
+                ] => {
+                    assert_eq!(image.name.as_deref(), Some("A cat"));
+                    assert_eq!(document.media_type.as_deref(), Some("video/mp4"));
+                }
+                _ => panic!("Expected an Image and a Document"),
+            },
+            _ => panic!("Expected multiple attachments"),
+        }
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use calmi_macros::object_based;
 use serde::{Deserialize, Serialize};
 
-use crate::types::properties::{OrderedItems, TotalItems};
+use crate::types::properties::{First, Last, OrderedItems, TotalItems};
 
 /// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-orderedcollection
 /// OrderedCollection extends Collection
@@ -15,12 +15,18 @@ pub struct OrderedCollection {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ordered_items: Option<OrderedItems>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first: Option<Box<First>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last: Option<Box<Last>>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::enums::{ObjectOrLinkOrStringUrl, SingleOrMultiple};
+    use crate::types::enums::{LinkOrStringUrl, ObjectOrLinkOrStringUrl, SingleOrMultiple};
 
     #[test]
     fn deserialize_minimal_ordered_collection() {
@@ -87,6 +93,8 @@ mod tests {
             ordered_items: Some(vec![ObjectOrLinkOrStringUrl::Str(
                 "http://example.org/note/1".to_string(),
             )]),
+            first: None,
+            last: None,
         };
         let json = serde_json::to_string(&ordered_collection).unwrap();
         assert!(json.contains(r#""id":"http://example.org/ordered_collection/1""#));
@@ -103,10 +111,31 @@ mod tests {
             r#type: Some("OrderedCollection".to_string()),
             total_items: None,
             ordered_items: None,
+            first: None,
+            last: None,
         };
         let json = serde_json::to_string(&ordered_collection).unwrap();
         assert!(!json.contains("totalItems"));
         assert!(!json.contains("orderedItems"));
+        assert!(!json.contains("first"));
+    }
+
+    #[test]
+    fn deserialize_paged_ordered_collection() {
+        let json = r#"{
+            "id": "http://example.org/followers",
+            "type": "OrderedCollection",
+            "totalItems": 42,
+            "first": "http://example.org/followers?page=1"
+        }"#;
+        let oc: OrderedCollection = serde_json::from_str(json).unwrap();
+        assert_eq!(oc.total_items, Some(42));
+        assert!(oc.ordered_items.is_none());
+        if let Some(LinkOrStringUrl::Str(first)) = oc.first.as_deref() {
+            assert_eq!(first, "http://example.org/followers?page=1");
+        } else {
+            panic!("Expected string first");
+        }
     }
 
     #[test]
@@ -0,0 +1,96 @@
+use calmi_macros::object_based;
+use serde::{Deserialize, Serialize};
+
+use crate::types::properties::{Next, OrderedItems, PartOf, Prev, TotalItems};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-orderedcollectionpage
+/// OrderedCollectionPage extends OrderedCollection and CollectionPage
+/// One page of the items of an OrderedCollection.
+#[object_based]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderedCollectionPage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<TotalItems>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ordered_items: Option<OrderedItems>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_of: Option<Box<PartOf>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<Box<Next>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<Box<Prev>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{LinkOrStringUrl, ObjectOrLinkOrStringUrl};
+
+    #[test]
+    fn deserialize_minimal_ordered_collection_page() {
+        let json = r#"{
+            "id": "http://example.org/followers?page=1",
+            "type": "OrderedCollectionPage"
+        }"#;
+        let page: Result<OrderedCollectionPage, _> = serde_json::from_str(json);
+        assert!(page.is_ok());
+        let p = page.unwrap();
+        assert_eq!(p.r#type, Some("OrderedCollectionPage".to_string()));
+        assert!(p.ordered_items.is_none());
+        assert!(p.part_of.is_none());
+        assert!(p.next.is_none());
+        assert!(p.prev.is_none());
+    }
+
+    #[test]
+    fn deserialize_ordered_collection_page_with_all_fields() {
+        let json = r#"{
+            "id": "http://example.org/followers?page=2",
+            "type": "OrderedCollectionPage",
+            "totalItems": 3,
+            "partOf": "http://example.org/followers",
+            "next": "http://example.org/followers?page=3",
+            "prev": "http://example.org/followers?page=1",
+            "orderedItems": ["http://example.org/person/1"]
+        }"#;
+        let p: OrderedCollectionPage = serde_json::from_str(json).unwrap();
+        assert_eq!(p.total_items, Some(3));
+        if let Some(LinkOrStringUrl::Str(part_of)) = p.part_of.as_deref() {
+            assert_eq!(part_of, "http://example.org/followers");
+        } else {
+            panic!("Expected string partOf");
+        }
+        assert!(p.next.is_some());
+        assert!(p.prev.is_some());
+        match p.ordered_items.as_deref() {
+            Some([ObjectOrLinkOrStringUrl::Str(item)]) => {
+                assert_eq!(item, "http://example.org/person/1")
+            }
+            _ => panic!("Expected a single string item"),
+        }
+    }
+
+    #[test]
+    fn serialize_ordered_collection_page() {
+        let page = OrderedCollectionPage {
+            context: None,
+            id: Some("http://example.org/followers?page=1".to_string()),
+            r#type: Some("OrderedCollectionPage".to_string()),
+            total_items: None,
+            ordered_items: Some(vec![]),
+            part_of: Some(Box::new(LinkOrStringUrl::Str(
+                "http://example.org/followers".to_string(),
+            ))),
+            next: None,
+            prev: None,
+        };
+        let json = serde_json::to_string(&page).unwrap();
+        let expected = r#"{"id":"http://example.org/followers?page=1","type":"OrderedCollectionPage","orderedItems":[],"partOf":"http://example.org/followers"}"#;
+        assert_eq!(json, expected);
+    }
+}
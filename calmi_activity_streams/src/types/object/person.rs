@@ -1,9 +1,10 @@
 use calmi_macros::object_based;
 use serde::{Deserialize, Serialize};
 
-use crate::types::properties::Name;
+use crate::types::properties::{Name, Url};
+use crate::types::security::PublicKey;
 
-use super::super::enums::ObjectOrLinkOrStringUrl;
+use super::super::enums::{ImageOrLinkOrStringUrl, ObjectOrLinkOrStringUrl, SingleOrMultiple};
 
 /// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-person
 /// Person extends Object
@@ -14,17 +15,56 @@ pub struct Person {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<Name>,
 
+    /// https://www.w3.org/TR/activitypub/#preferredUsername
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+
+    /// Where the profile can be seen in a browser.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Box<Url>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Box<SingleOrMultiple<ImageOrLinkOrStringUrl>>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inbox: Option<Box<ObjectOrLinkOrStringUrl>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub outbox: Option<Box<ObjectOrLinkOrStringUrl>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub followers: Option<Box<ObjectOrLinkOrStringUrl>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub following: Option<Box<ObjectOrLinkOrStringUrl>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liked: Option<Box<ObjectOrLinkOrStringUrl>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoints: Option<Box<Endpoints>>,
+
+    /// https://w3id.org/security/v1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<Box<PublicKey>>,
+
+    /// as:manuallyApprovesFollowers, an extension widely used for locked accounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manually_approves_followers: Option<bool>,
+}
+
+/// https://www.w3.org/TR/activitypub/#endpoints
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Endpoints {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_inbox: Option<String>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::enums::{ObjectOrLinkOrStringUrl, SingleOrMultiple};
+    use crate::types::enums::{ContextEntry, LinkOrStringUrl};
 
     #[test]
     fn deserialize_minimal_person() {
@@ -49,6 +89,7 @@ mod tests {
             "id": "http://example.org/person/2",
             "type": "Person",
             "name": "John Doe",
+            "url": "http://example.org/@john",
             "inbox": "http://example.org/person/2/inbox",
             "outbox": "http://example.org/person/2/outbox"
         }"#;
@@ -70,6 +111,11 @@ mod tests {
         } else {
             panic!("Expected string outbox");
         }
+        if let Some(SingleOrMultiple::Single(LinkOrStringUrl::Str(url))) = p.url.as_deref() {
+            assert_eq!(url, "http://example.org/@john");
+        } else {
+            panic!("Expected single string url");
+        }
     }
 
     #[test]
@@ -79,12 +125,27 @@ mod tests {
             id: Some("http://example.org/person/1".to_string()),
             r#type: Some("Person".to_string()),
             name: Some("Jane Doe".to_string()),
+            preferred_username: None,
+            url: None,
+            icon: None,
             inbox: Some(Box::new(ObjectOrLinkOrStringUrl::Str(
                 "http://example.org/inbox".to_string(),
             ))),
             outbox: Some(Box::new(ObjectOrLinkOrStringUrl::Str(
                 "http://example.org/outbox".to_string(),
             ))),
+            followers: Some(Box::new(ObjectOrLinkOrStringUrl::Str(
+                "http://example.org/followers".to_string(),
+            ))),
+            following: Some(Box::new(ObjectOrLinkOrStringUrl::Str(
+                "http://example.org/following".to_string(),
+            ))),
+            liked: Some(Box::new(ObjectOrLinkOrStringUrl::Str(
+                "http://example.org/liked".to_string(),
+            ))),
+            endpoints: None,
+            public_key: None,
+            manually_approves_followers: None,
         };
         let json = serde_json::to_string(&person).unwrap();
         assert!(json.contains(r#""id":"http://example.org/person/1""#));
@@ -92,6 +153,9 @@ mod tests {
         assert!(json.contains(r#""name":"Jane Doe""#));
         assert!(json.contains(r#""inbox":"http://example.org/inbox""#));
         assert!(json.contains(r#""outbox":"http://example.org/outbox""#));
+        assert!(json.contains(r#""followers":"http://example.org/followers""#));
+        assert!(json.contains(r#""following":"http://example.org/following""#));
+        assert!(json.contains(r#""liked":"http://example.org/liked""#));
     }
 
     #[test]
@@ -101,13 +165,112 @@ mod tests {
             id: Some("http://example.org/person/1".to_string()),
             r#type: Some("Person".to_string()),
             name: None,
+            preferred_username: None,
+            url: None,
+            icon: None,
             inbox: None,
             outbox: None,
+            followers: None,
+            following: None,
+            liked: None,
+            endpoints: None,
+            public_key: None,
+            manually_approves_followers: None,
         };
         let json = serde_json::to_string(&person).unwrap();
         assert!(!json.contains("name"));
         assert!(!json.contains("inbox"));
         assert!(!json.contains("outbox"));
+        assert!(!json.contains("publicKey"));
+        assert!(!json.contains("manuallyApprovesFollowers"));
+    }
+
+    #[test]
+    fn deserialize_person_with_public_key() {
+        let json = r#"{
+            "@context": [
+                "https://www.w3.org/ns/activitystreams",
+                "https://w3id.org/security/v1"
+            ],
+            "id": "http://example.org/person/1",
+            "type": "Person",
+            "publicKey": {
+                "id": "http://example.org/person/1#main-key",
+                "owner": "http://example.org/person/1",
+                "publicKeyPem": "-----BEGIN PUBLIC KEY-----\n...\n-----END PUBLIC KEY-----\n"
+            }
+        }"#;
+        let person: Result<Person, _> = serde_json::from_str(json);
+        assert!(person.is_ok());
+        let p = person.unwrap();
+        let key = p.public_key.expect("Expected publicKey");
+        assert_eq!(key.id, "http://example.org/person/1#main-key");
+        assert_eq!(key.owner, "http://example.org/person/1");
+    }
+
+    #[test]
+    fn deserialize_person_with_manually_approves_followers() {
+        let json = r#"{
+            "id": "http://example.org/person/1",
+            "type": "Person",
+            "manuallyApprovesFollowers": true
+        }"#;
+        let person: Result<Person, _> = serde_json::from_str(json);
+        assert!(person.is_ok());
+        assert_eq!(person.unwrap().manually_approves_followers, Some(true));
+    }
+
+    #[test]
+    fn deserialize_mastodon_style_person() {
+        let json = r#"{
+            "@context": [
+                "https://www.w3.org/ns/activitystreams",
+                "https://w3id.org/security/v1",
+                {
+                    "manuallyApprovesFollowers": "as:manuallyApprovesFollowers",
+                    "toot": "http://joinmastodon.org/ns#"
+                }
+            ],
+            "id": "http://example.org/users/alice",
+            "type": "Person",
+            "preferredUsername": "alice",
+            "name": "Alice",
+            "inbox": "http://example.org/users/alice/inbox",
+            "endpoints": {
+                "sharedInbox": "http://example.org/inbox"
+            },
+            "icon": {
+                "type": "Image",
+                "mediaType": "image/png",
+                "url": "http://example.org/avatars/alice.png"
+            }
+        }"#;
+        let person: Result<Person, _> = serde_json::from_str(json);
+        assert!(person.is_ok(), "{:?}", person.err());
+        let p = person.unwrap();
+        assert_eq!(p.preferred_username, Some("alice".to_string()));
+        match &p.context {
+            Some(SingleOrMultiple::Multiple(ctxs)) => {
+                assert_eq!(ctxs.len(), 3);
+                assert!(matches!(ctxs[2], ContextEntry::Definitions(_)));
+            }
+            _ => panic!("Expected multiple contexts"),
+        }
+        assert_eq!(
+            p.endpoints.and_then(|e| e.shared_inbox),
+            Some("http://example.org/inbox".to_string())
+        );
+        if let Some(SingleOrMultiple::Single(ImageOrLinkOrStringUrl::Image(icon))) =
+            p.icon.as_deref()
+        {
+            if let Some(SingleOrMultiple::Single(LinkOrStringUrl::Str(url))) = icon.url.as_deref() {
+                assert_eq!(url, "http://example.org/avatars/alice.png");
+            } else {
+                panic!("Expected string icon url");
+            }
+        } else {
+            panic!("Expected single icon image");
+        }
     }
 
     #[test]
@@ -0,0 +1,69 @@
+use calmi_macros::object_based;
+use serde::{Deserialize, Serialize};
+
+use crate::types::properties::{Deleted, FormerType};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-tombstone
+/// Tombstone extends Object
+/// A placeholder for an object that has been deleted.
+#[object_based]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Tombstone {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub former_type: Option<FormerType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<Deleted>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::SingleOrMultiple;
+
+    #[test]
+    fn deserialize_minimal_tombstone() {
+        let json = r#"{
+            "id": "http://example.org/note/1",
+            "type": "Tombstone"
+        }"#;
+        let tombstone: Result<Tombstone, _> = serde_json::from_str(json);
+        assert!(tombstone.is_ok());
+        let t = tombstone.unwrap();
+        assert_eq!(t.id, Some("http://example.org/note/1".to_string()));
+        assert_eq!(t.r#type, Some("Tombstone".to_string()));
+        assert!(t.former_type.is_none());
+        assert!(t.deleted.is_none());
+    }
+
+    #[test]
+    fn deserialize_tombstone_with_all_fields() {
+        let json = r#"{
+            "id": "http://example.org/note/1",
+            "type": "Tombstone",
+            "formerType": "Note",
+            "deleted": "2016-05-10T00:00:00Z"
+        }"#;
+        let t: Tombstone = serde_json::from_str(json).unwrap();
+        match t.former_type {
+            Some(SingleOrMultiple::Single(former_type)) => assert_eq!(former_type, "Note"),
+            _ => panic!("Expected single formerType"),
+        }
+        assert_eq!(t.deleted, Some("2016-05-10T00:00:00Z".to_string()));
+    }
+
+    #[test]
+    fn serialize_tombstone() {
+        let tombstone = Tombstone {
+            context: None,
+            id: Some("http://example.org/note/1".to_string()),
+            r#type: Some("Tombstone".to_string()),
+            former_type: Some(SingleOrMultiple::Single("Note".to_string())),
+            deleted: Some("2016-05-10T00:00:00Z".to_string()),
+        };
+        let json = serde_json::to_string(&tombstone).unwrap();
+        let expected = r#"{"id":"http://example.org/note/1","type":"Tombstone","formerType":"Note","deleted":"2016-05-10T00:00:00Z"}"#;
+        assert_eq!(json, expected);
+    }
+}
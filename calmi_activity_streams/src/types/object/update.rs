@@ -0,0 +1,86 @@
+use calmi_macros::object_based;
+use serde::{Deserialize, Serialize};
+
+use crate::types::properties::{Actor, ObjectProperty};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-update
+/// Update extends Activity
+/// Indicates that the actor has updated the object.
+#[object_based]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Update {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<Box<Actor>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object: Option<Box<ObjectProperty>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple};
+
+    #[test]
+    fn deserialize_minimal_update() {
+        let json = r#"{
+            "id": "http://example.org/update/1",
+            "type": "Update"
+        }"#;
+        let update: Result<Update, _> = serde_json::from_str(json);
+        assert!(update.is_ok());
+        let u = update.unwrap();
+        assert_eq!(u.id, Some("http://example.org/update/1".to_string()));
+        assert_eq!(u.r#type, Some("Update".to_string()));
+        assert!(u.actor.is_none());
+        assert!(u.object.is_none());
+    }
+
+    #[test]
+    fn deserialize_update_with_note() {
+        let json = r#"{
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "id": "http://example.org/update/2",
+            "type": "Update",
+            "actor": "http://example.org/person/1",
+            "object": {
+                "id": "http://example.org/note/1",
+                "type": "Note",
+                "content": "Edited"
+            }
+        }"#;
+        let u: Update = serde_json::from_str(json).unwrap();
+        if let Some(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Object(ObjectBased::Note(
+            note,
+        )))) = u.object.as_deref()
+        {
+            assert_eq!(note.content, Some("Edited".to_string()));
+        } else {
+            panic!("Expected embedded Note");
+        }
+    }
+
+    #[test]
+    fn deserialize_update_with_person() {
+        let json = r#"{
+            "id": "http://example.org/update/3",
+            "type": "Update",
+            "actor": "http://example.org/person/1",
+            "object": {
+                "id": "http://example.org/person/1",
+                "type": "Person",
+                "name": "New name"
+            }
+        }"#;
+        let u: Update = serde_json::from_str(json).unwrap();
+        if let Some(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Object(
+            ObjectBased::Person(person),
+        ))) = u.object.as_deref()
+        {
+            assert_eq!(person.name, Some("New name".to_string()));
+        } else {
+            panic!("Expected embedded Person");
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use calmi_macros::object_based;
+use serde::{Deserialize, Serialize};
+
+use crate::types::properties::{Height, MediaType, Name, Url, Width};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-video
+/// Video extends Document
+/// A video document of any kind.
+#[object_based]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Video {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Name>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<MediaType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Box<Url>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<Width>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<Height>,
+
+    /// https://docs.joinmastodon.org/spec/activitypub/#blurhash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blurhash: Option<String>,
+
+    /// https://docs.joinmastodon.org/spec/activitypub/#focalPoint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focal_point: Option<[f64; 2]>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{LinkOrStringUrl, SingleOrMultiple};
+
+    #[test]
+    fn deserialize_video_with_link_url() {
+        let json = r#"{
+            "type": "Video",
+            "url": {
+                "type": "Link",
+                "href": "http://example.org/files/clip.mp4",
+                "mediaType": "video/mp4"
+            }
+        }"#;
+        let v: Video = serde_json::from_str(json).unwrap();
+        assert_eq!(v.r#type, Some("Video".to_string()));
+        assert!(v.media_type.is_none());
+        if let Some(SingleOrMultiple::Single(LinkOrStringUrl::Link(link))) = v.url.as_deref() {
+            assert_eq!(
+                link.href.as_deref(),
+                Some("http://example.org/files/clip.mp4")
+            );
+        } else {
+            panic!("Expected single link url");
+        }
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 use crate::types::enums::{
-    LinkOrStringUrl, ObjectOrLinkOrStringUrl, ObjectOrStringUrl, SingleOrMultiple,
+    LinkOrStringUrl, ObjectOrLinkOrStringUrl, ObjectOrStringUrl, SingleOrMultiple, TagEntry,
 };
 /// https://www.w3.org/TR/activitystreams-vocabulary/#properties
 /// ActivityStreams 2.0 Properties
@@ -153,7 +153,7 @@ pub type AnyOf = SingleOrMultiple<ObjectOrLinkOrStringUrl>;
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum Closed {
-    Object(ObjectOrLinkOrStringUrl),
+    Object(Box<ObjectOrLinkOrStringUrl>),
     DateTime(String),
     Boolean(bool),
 }
@@ -207,7 +207,7 @@ pub type Replies = ObjectOrStringUrl;
 /// URI: https://www.w3.org/ns/activitystreams#tag
 /// Domain: Object
 /// Range: Object | Link
-pub type Tag = SingleOrMultiple<ObjectOrLinkOrStringUrl>;
+pub type Tag = SingleOrMultiple<TagEntry>;
 
 /// Describes the indirect object, or target, of the activity.
 /// URI: https://www.w3.org/ns/activitystreams#target
@@ -417,3 +417,15 @@ pub type FormerType = SingleOrMultiple<String>;
 /// Range: xsd:dateTime
 /// Functional: True
 pub type Deleted = String;
+
+/// A Collection of Like activities of this object.
+/// URI: https://www.w3.org/ns/activitystreams#likes
+/// Defined by ActivityPub: https://www.w3.org/TR/activitypub/#likes
+/// Range: Collection
+pub type Likes = ObjectOrStringUrl;
+
+/// A Collection of Announce activities of this object.
+/// URI: https://www.w3.org/ns/activitystreams#shares
+/// Defined by ActivityPub: https://www.w3.org/TR/activitypub/#shares
+/// Range: Collection
+pub type Shares = ObjectOrStringUrl;
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// https://w3id.org/security/v1
+/// The key an actor signs its requests with, embedded in the actor as `publicKey`.
+/// https://docs.joinmastodon.org/spec/activitypub/#publicKey
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKey {
+    pub id: String,
+
+    /// The actor this key belongs to.
+    pub owner: String,
+
+    pub public_key_pem: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_public_key() {
+        let json = r#"{
+            "id": "http://example.org/person/1#main-key",
+            "owner": "http://example.org/person/1",
+            "publicKeyPem": "-----BEGIN PUBLIC KEY-----\nMIIBIjANBgkqh...\n-----END PUBLIC KEY-----\n"
+        }"#;
+        let key: Result<PublicKey, _> = serde_json::from_str(json);
+        assert!(key.is_ok());
+        let k = key.unwrap();
+        assert_eq!(k.id, "http://example.org/person/1#main-key");
+        assert_eq!(k.owner, "http://example.org/person/1");
+        assert!(k.public_key_pem.starts_with("-----BEGIN PUBLIC KEY-----"));
+    }
+
+    #[test]
+    fn deserialize_public_key_without_pem_fails() {
+        let json = r#"{
+            "id": "http://example.org/person/1#main-key",
+            "owner": "http://example.org/person/1"
+        }"#;
+        let key: Result<PublicKey, _> = serde_json::from_str(json);
+        assert!(key.is_err());
+    }
+
+    #[test]
+    fn serialize_public_key() {
+        let key = PublicKey {
+            id: "http://example.org/person/1#main-key".to_string(),
+            owner: "http://example.org/person/1".to_string(),
+            public_key_pem: "PEM".to_string(),
+        };
+        let json = serde_json::to_string(&key).unwrap();
+        let expected = r#"{"id":"http://example.org/person/1#main-key","owner":"http://example.org/person/1","publicKeyPem":"PEM"}"#;
+        assert_eq!(json, expected);
+    }
+}
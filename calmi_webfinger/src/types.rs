@@ -5,14 +5,14 @@ pub struct WebFingerQuery {
     pub resource: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WebFingerResponse {
     pub subject: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub links: Option<Vec<WebFingerLink>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WebFingerLink {
     pub rel: String,
     #[serde(skip_serializing_if = "Option::is_none")]
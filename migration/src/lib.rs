@@ -3,6 +3,28 @@ pub use sea_orm_migration::prelude::*;
 mod m20220101_000001_create_user_table;
 mod m20251102_073848_create_note_table;
 mod m20251106_000001_create_interactions_tables;
+mod m20251112_000001_add_keys_to_users;
+mod m20251115_000001_create_jobs_table;
+mod m20251118_000001_add_follow_approval;
+mod m20251120_000001_create_following_table;
+mod m20251122_000001_create_remote_actors_table;
+mod m20251124_000001_create_remote_notes_table;
+mod m20251126_000001_add_updated_to_remote_notes;
+mod m20251128_000001_add_deleted_at_to_notes;
+mod m20251130_000001_create_note_revisions_table;
+mod m20251202_000001_add_hide_followers_to_users;
+mod m20251204_000001_create_liked_table;
+mod m20251206_000001_add_notes_author_keyset_index;
+mod m20251208_000001_add_reply_columns;
+mod m20251210_000001_create_mentions_tables;
+mod m20251212_000001_create_note_tags_table;
+mod m20251214_000001_add_visibility_to_notes;
+mod m20251216_000001_add_followers_to_remote_actors;
+mod m20251216_000002_create_conversations_tables;
+mod m20251218_000001_create_media_table;
+mod m20251220_000001_add_processing_to_media;
+mod m20251222_000001_add_content_warnings;
+mod m20251224_000001_add_summary_to_note_revisions;
 
 pub struct Migrator;
 
@@ -13,6 +35,28 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000001_create_user_table::Migration),
             Box::new(m20251102_073848_create_note_table::Migration),
             Box::new(m20251106_000001_create_interactions_tables::Migration),
+            Box::new(m20251112_000001_add_keys_to_users::Migration),
+            Box::new(m20251115_000001_create_jobs_table::Migration),
+            Box::new(m20251118_000001_add_follow_approval::Migration),
+            Box::new(m20251120_000001_create_following_table::Migration),
+            Box::new(m20251122_000001_create_remote_actors_table::Migration),
+            Box::new(m20251124_000001_create_remote_notes_table::Migration),
+            Box::new(m20251126_000001_add_updated_to_remote_notes::Migration),
+            Box::new(m20251128_000001_add_deleted_at_to_notes::Migration),
+            Box::new(m20251130_000001_create_note_revisions_table::Migration),
+            Box::new(m20251202_000001_add_hide_followers_to_users::Migration),
+            Box::new(m20251204_000001_create_liked_table::Migration),
+            Box::new(m20251206_000001_add_notes_author_keyset_index::Migration),
+            Box::new(m20251208_000001_add_reply_columns::Migration),
+            Box::new(m20251210_000001_create_mentions_tables::Migration),
+            Box::new(m20251212_000001_create_note_tags_table::Migration),
+            Box::new(m20251214_000001_add_visibility_to_notes::Migration),
+            Box::new(m20251216_000001_add_followers_to_remote_actors::Migration),
+            Box::new(m20251216_000002_create_conversations_tables::Migration),
+            Box::new(m20251218_000001_create_media_table::Migration),
+            Box::new(m20251220_000001_add_processing_to_media::Migration),
+            Box::new(m20251222_000001_add_content_warnings::Migration),
+            Box::new(m20251224_000001_add_summary_to_note_revisions::Migration),
         ]
     }
 }
@@ -0,0 +1,61 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Jobs::Table)
+                    .if_not_exists()
+                    .col(big_integer(Jobs::Id).auto_increment().primary_key())
+                    .col(string_len(Jobs::Kind, 64))
+                    .col(json_binary(Jobs::Payload))
+                    .col(integer(Jobs::Attempts).default(0))
+                    .col(date_time(Jobs::RunAt))
+                    .col(date_time_null(Jobs::LockedUntil))
+                    .col(date_time_null(Jobs::FailedAt))
+                    .col(text_null(Jobs::LastError))
+                    .col(
+                        date_time(Jobs::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_jobs_run_at")
+                    .table(Jobs::Table)
+                    .col(Jobs::RunAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Jobs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Jobs {
+    Table,
+    Id,
+    Kind,
+    Payload,
+    Attempts,
+    RunAt,
+    LockedUntil,
+    FailedAt,
+    LastError,
+    CreatedAt,
+}
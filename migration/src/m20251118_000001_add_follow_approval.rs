@@ -0,0 +1,60 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(boolean(Users::ManuallyApprovesFollowers).default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing follows were all accepted on arrival.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Follows::Table)
+                    .add_column(boolean(Follows::Pending).default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Follows::Table)
+                    .drop_column(Follows::Pending)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::ManuallyApprovesFollowers)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    ManuallyApprovesFollowers,
+}
+
+#[derive(DeriveIden)]
+enum Follows {
+    Table,
+    Pending,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Remote actors followed by local users; `follows` holds the other direction.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Following::Table)
+                    .if_not_exists()
+                    .col(big_integer(Following::Id).auto_increment().primary_key())
+                    .col(big_integer(Following::UserId).not_null())
+                    .col(text(Following::Actor).not_null())
+                    .col(string_len(Following::ActivityId, 2048))
+                    .col(boolean(Following::Pending).default(true))
+                    .col(
+                        date_time(Following::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_following_user_id")
+                            .from(Following::Table, Following::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_following_user_actor")
+                    .table(Following::Table)
+                    .col(Following::UserId)
+                    .col(Following::Actor)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_following_activity_id")
+                    .table(Following::Table)
+                    .col(Following::ActivityId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Following::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Following {
+    Table,
+    Id,
+    UserId,
+    Actor,
+    ActivityId,
+    Pending,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cached copies of remote actor documents, refreshed once `fetched_at` is too old.
+        manager
+            .create_table(
+                Table::create()
+                    .table(RemoteActors::Table)
+                    .if_not_exists()
+                    .col(big_integer(RemoteActors::Id).auto_increment().primary_key())
+                    .col(text(RemoteActors::Uri))
+                    .col(text(RemoteActors::Inbox))
+                    .col(text_null(RemoteActors::SharedInbox))
+                    .col(text_null(RemoteActors::PublicKeyId))
+                    .col(text_null(RemoteActors::PublicKeyPem))
+                    .col(text_null(RemoteActors::PreferredUsername))
+                    .col(text_null(RemoteActors::Name))
+                    .col(text_null(RemoteActors::IconUrl))
+                    .col(date_time(RemoteActors::FetchedAt))
+                    .col(
+                        date_time(RemoteActors::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_remote_actors_uri")
+                    .table(RemoteActors::Table)
+                    .col(RemoteActors::Uri)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RemoteActors::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RemoteActors {
+    Table,
+    Id,
+    Uri,
+    Inbox,
+    SharedInbox,
+    PublicKeyId,
+    PublicKeyPem,
+    PreferredUsername,
+    Name,
+    IconUrl,
+    FetchedAt,
+    CreatedAt,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RemoteNotes::Table)
+                    .if_not_exists()
+                    .col(big_integer(RemoteNotes::Id).auto_increment().primary_key())
+                    .col(text(RemoteNotes::ApId))
+                    .col(text(RemoteNotes::Actor))
+                    .col(text(RemoteNotes::Content))
+                    .col(
+                        ColumnDef::new(RemoteNotes::To)
+                            .array(ColumnType::Text)
+                            .not_null()
+                            .default("{}"),
+                    )
+                    .col(
+                        ColumnDef::new(RemoteNotes::Cc)
+                            .array(ColumnType::Text)
+                            .not_null()
+                            .default("{}"),
+                    )
+                    .col(text_null(RemoteNotes::InReplyTo))
+                    .col(date_time_null(RemoteNotes::Published))
+                    .col(
+                        date_time(RemoteNotes::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_remote_notes_ap_id")
+                    .table(RemoteNotes::Table)
+                    .col(RemoteNotes::ApId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_remote_notes_actor")
+                    .table(RemoteNotes::Table)
+                    .col(RemoteNotes::Actor)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RemoteNotes::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RemoteNotes {
+    Table,
+    Id,
+    ApId,
+    Actor,
+    Content,
+    To,
+    Cc,
+    InReplyTo,
+    Published,
+    CreatedAt,
+}
@@ -0,0 +1,84 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notes::Table)
+                    .add_column(date_time_null(Notes::UpdatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Superseded versions of edited notes; the current one stays in `notes`.
+        manager
+            .create_table(
+                Table::create()
+                    .table(NoteRevisions::Table)
+                    .if_not_exists()
+                    .col(
+                        big_integer(NoteRevisions::Id)
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(big_integer(NoteRevisions::NoteId).not_null())
+                    .col(text(NoteRevisions::Content))
+                    .col(date_time(NoteRevisions::CreatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_note_revisions_note_id")
+                            .from(NoteRevisions::Table, NoteRevisions::NoteId)
+                            .to(Notes::Table, Notes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_note_revisions_note_id")
+                    .table(NoteRevisions::Table)
+                    .col(NoteRevisions::NoteId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NoteRevisions::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notes::Table)
+                    .drop_column(Notes::UpdatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NoteRevisions {
+    Table,
+    Id,
+    NoteId,
+    Content,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    Id,
+    UpdatedAt,
+}
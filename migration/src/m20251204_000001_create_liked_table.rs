@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Objects liked by local users; `note_likes` holds likes of our notes.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Liked::Table)
+                    .if_not_exists()
+                    .col(big_integer(Liked::Id).auto_increment().primary_key())
+                    .col(big_integer(Liked::UserId).not_null())
+                    .col(text(Liked::Object).not_null())
+                    .col(text(Liked::Actor).not_null())
+                    .col(string_len(Liked::ActivityId, 2048))
+                    .col(
+                        date_time(Liked::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_liked_user_id")
+                            .from(Liked::Table, Liked::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_liked_user_object")
+                    .table(Liked::Table)
+                    .col(Liked::UserId)
+                    .col(Liked::Object)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_liked_activity_id")
+                    .table(Liked::Table)
+                    .col(Liked::ActivityId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Liked::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Liked {
+    Table,
+    Id,
+    UserId,
+    Object,
+    Actor,
+    ActivityId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Outbox pages walk an author's notes by (created_at, id).
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notes_author_id_created_at_id")
+                    .table(Notes::Table)
+                    .col(Notes::AuthorId)
+                    .col(Notes::CreatedAt)
+                    .col(Notes::Id)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_notes_author_id_created_at_id")
+                    .table(Notes::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    Id,
+    AuthorId,
+    CreatedAt,
+}
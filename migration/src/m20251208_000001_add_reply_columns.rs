@@ -0,0 +1,101 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notes::Table)
+                    .add_column(text_null(Notes::InReplyTo))
+                    .add_column(text_null(Notes::Context))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RemoteNotes::Table)
+                    .add_column(text_null(RemoteNotes::Context))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notes_in_reply_to")
+                    .table(Notes::Table)
+                    .col(Notes::InReplyTo)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_remote_notes_in_reply_to")
+                    .table(RemoteNotes::Table)
+                    .col(RemoteNotes::InReplyTo)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_remote_notes_in_reply_to")
+                    .table(RemoteNotes::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_notes_in_reply_to")
+                    .table(Notes::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RemoteNotes::Table)
+                    .drop_column(RemoteNotes::Context)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notes::Table)
+                    .drop_column(Notes::Context)
+                    .drop_column(Notes::InReplyTo)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    InReplyTo,
+    Context,
+}
+
+#[derive(DeriveIden)]
+enum RemoteNotes {
+    Table,
+    InReplyTo,
+    Context,
+}
@@ -0,0 +1,118 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(NoteMentions::Table)
+                    .if_not_exists()
+                    .col(big_integer(NoteMentions::Id).auto_increment().primary_key())
+                    .col(big_integer(NoteMentions::NoteId).not_null())
+                    .col(text(NoteMentions::Href).not_null())
+                    .col(text(NoteMentions::Name).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_note_mentions_note_id")
+                            .from(NoteMentions::Table, NoteMentions::NoteId)
+                            .to(Notes::Table, Notes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_note_mentions_note_href")
+                    .table(NoteMentions::Table)
+                    .col(NoteMentions::NoteId)
+                    .col(NoteMentions::Href)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Mentions::Table)
+                    .if_not_exists()
+                    .col(big_integer(Mentions::Id).auto_increment().primary_key())
+                    .col(big_integer(Mentions::UserId).not_null())
+                    .col(text(Mentions::Note).not_null())
+                    .col(text(Mentions::Actor).not_null())
+                    .col(
+                        date_time(Mentions::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_mentions_user_id")
+                            .from(Mentions::Table, Mentions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_mentions_user_note")
+                    .table(Mentions::Table)
+                    .col(Mentions::UserId)
+                    .col(Mentions::Note)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Mentions::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(NoteMentions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NoteMentions {
+    Table,
+    Id,
+    NoteId,
+    Href,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum Mentions {
+    Table,
+    Id,
+    UserId,
+    Note,
+    Actor,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
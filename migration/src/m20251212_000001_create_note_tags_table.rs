@@ -0,0 +1,104 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // `note` is the URI of the tagged note; exactly one of `note_id` and `remote_note_id` is set.
+        manager
+            .create_table(
+                Table::create()
+                    .table(NoteTags::Table)
+                    .if_not_exists()
+                    .col(big_integer(NoteTags::Id).auto_increment().primary_key())
+                    .col(text(NoteTags::Name).not_null())
+                    .col(text(NoteTags::Note).not_null())
+                    .col(big_integer_null(NoteTags::NoteId))
+                    .col(big_integer_null(NoteTags::RemoteNoteId))
+                    .col(
+                        date_time(NoteTags::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_note_tags_note_id")
+                            .from(NoteTags::Table, NoteTags::NoteId)
+                            .to(Notes::Table, Notes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_note_tags_remote_note_id")
+                            .from(NoteTags::Table, NoteTags::RemoteNoteId)
+                            .to(RemoteNotes::Table, RemoteNotes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_note_tags_name_note")
+                    .table(NoteTags::Table)
+                    .col(NoteTags::Name)
+                    .col(NoteTags::Note)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_note_tags_note_id")
+                    .table(NoteTags::Table)
+                    .col(NoteTags::NoteId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_note_tags_remote_note_id")
+                    .table(NoteTags::Table)
+                    .col(NoteTags::RemoteNoteId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NoteTags::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NoteTags {
+    Table,
+    Id,
+    Name,
+    Note,
+    NoteId,
+    RemoteNoteId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum RemoteNotes {
+    Table,
+    Id,
+}
@@ -0,0 +1,182 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Conversations::Table)
+                    .if_not_exists()
+                    .col(
+                        big_integer(Conversations::Id)
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(big_integer(Conversations::UserId).not_null())
+                    .col(text(Conversations::Context).not_null())
+                    .col(
+                        ColumnDef::new(Conversations::Participants)
+                            .array(ColumnType::Text)
+                            .not_null()
+                            .default("{}"),
+                    )
+                    .col(
+                        date_time(Conversations::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        date_time(Conversations::UpdatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversations_user_id")
+                            .from(Conversations::Table, Conversations::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_conversations_user_id_context")
+                    .table(Conversations::Table)
+                    .col(Conversations::UserId)
+                    .col(Conversations::Context)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // `note` is the URI of the message; exactly one of `note_id` and `remote_note_id` is set.
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConversationMessages::Table)
+                    .if_not_exists()
+                    .col(
+                        big_integer(ConversationMessages::Id)
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(big_integer(ConversationMessages::ConversationId).not_null())
+                    .col(text(ConversationMessages::Note).not_null())
+                    .col(big_integer_null(ConversationMessages::NoteId))
+                    .col(big_integer_null(ConversationMessages::RemoteNoteId))
+                    .col(
+                        date_time(ConversationMessages::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_messages_conversation_id")
+                            .from(
+                                ConversationMessages::Table,
+                                ConversationMessages::ConversationId,
+                            )
+                            .to(Conversations::Table, Conversations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_messages_note_id")
+                            .from(ConversationMessages::Table, ConversationMessages::NoteId)
+                            .to(Notes::Table, Notes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_messages_remote_note_id")
+                            .from(
+                                ConversationMessages::Table,
+                                ConversationMessages::RemoteNoteId,
+                            )
+                            .to(RemoteNotes::Table, RemoteNotes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_conversation_messages_conversation_id_note")
+                    .table(ConversationMessages::Table)
+                    .col(ConversationMessages::ConversationId)
+                    .col(ConversationMessages::Note)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_conversation_messages_note")
+                    .table(ConversationMessages::Table)
+                    .col(ConversationMessages::Note)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ConversationMessages::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Conversations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Conversations {
+    Table,
+    Id,
+    UserId,
+    Context,
+    Participants,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ConversationMessages {
+    Table,
+    Id,
+    ConversationId,
+    Note,
+    NoteId,
+    RemoteNoteId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum RemoteNotes {
+    Table,
+    Id,
+}
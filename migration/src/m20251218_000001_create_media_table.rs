@@ -0,0 +1,118 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // An upload belongs to `user_id` and is attached to at most one note, `note_id`.
+        manager
+            .create_table(
+                Table::create()
+                    .table(Media::Table)
+                    .if_not_exists()
+                    .col(big_integer(Media::Id).auto_increment().primary_key())
+                    .col(big_integer_null(Media::UserId))
+                    .col(big_integer_null(Media::NoteId))
+                    .col(big_integer_null(Media::RemoteNoteId))
+                    .col(text_null(Media::FileName).unique_key())
+                    .col(text_null(Media::RemoteUrl))
+                    .col(text(Media::Type).not_null())
+                    .col(big_integer_null(Media::Size))
+                    .col(integer_null(Media::Width))
+                    .col(integer_null(Media::Height))
+                    .col(text_null(Media::Description))
+                    .col(
+                        date_time(Media::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_user_id")
+                            .from(Media::Table, Media::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_note_id")
+                            .from(Media::Table, Media::NoteId)
+                            .to(Notes::Table, Notes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_remote_note_id")
+                            .from(Media::Table, Media::RemoteNoteId)
+                            .to(RemoteNotes::Table, RemoteNotes::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_note_id")
+                    .table(Media::Table)
+                    .col(Media::NoteId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_remote_note_id")
+                    .table(Media::Table)
+                    .col(Media::RemoteNoteId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Media::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Media {
+    Table,
+    Id,
+    UserId,
+    NoteId,
+    RemoteNoteId,
+    FileName,
+    RemoteUrl,
+    #[sea_orm(iden = "media_type")]
+    Type,
+    Size,
+    Width,
+    Height,
+    Description,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum RemoteNotes {
+    Table,
+    Id,
+}
@@ -0,0 +1,47 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Media::Table)
+                    .add_column(text_null(Media::ThumbnailFileName).unique_key())
+                    .add_column(text_null(Media::Blurhash))
+                    .add_column(double_null(Media::FocalX))
+                    .add_column(double_null(Media::FocalY))
+                    .add_column(date_time_null(Media::ProcessedAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Media::Table)
+                    .drop_column(Media::ThumbnailFileName)
+                    .drop_column(Media::Blurhash)
+                    .drop_column(Media::FocalX)
+                    .drop_column(Media::FocalY)
+                    .drop_column(Media::ProcessedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Media {
+    Table,
+    ThumbnailFileName,
+    Blurhash,
+    FocalX,
+    FocalY,
+    ProcessedAt,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The content warning shown in place of a note, and whether the note is sensitive.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notes::Table)
+                    .add_column(text_null(Notes::Summary))
+                    .add_column(boolean(Notes::Sensitive).default(false))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RemoteNotes::Table)
+                    .add_column(text_null(RemoteNotes::Summary))
+                    .add_column(boolean(RemoteNotes::Sensitive).default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RemoteNotes::Table)
+                    .drop_column(RemoteNotes::Summary)
+                    .drop_column(RemoteNotes::Sensitive)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Notes::Table)
+                    .drop_column(Notes::Summary)
+                    .drop_column(Notes::Sensitive)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Notes {
+    Table,
+    Summary,
+    Sensitive,
+}
+
+#[derive(DeriveIden)]
+enum RemoteNotes {
+    Table,
+    Summary,
+    Sensitive,
+}
@@ -1,7 +1,14 @@
+mod conversations;
 mod handlers;
+mod hashtags;
+pub mod jobs;
+pub mod keys;
+mod media;
+mod mentions;
 mod object_builders;
 mod routes;
 pub mod state;
+mod threads;
 pub mod types;
 
 pub fn create_app(state: state::AppState) -> axum::Router {
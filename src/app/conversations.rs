@@ -0,0 +1,116 @@
+use crate::app::object_builders::activity_pub::person;
+use crate::domain::entities::{remote_notes, users};
+use crate::domain::repositories::{ConversationsRepository, UsersRepository, notes::DirectMessage};
+use crate::federation::same_origin;
+use calmi_activity_streams::types::PUBLIC;
+use sea_orm::DbErr;
+
+/// Without the followers collection of the author, a note for followers cannot be told apart.
+pub fn is_direct(note: &remote_notes::Model, followers: Option<&str>) -> bool {
+    followers.is_some_and(|followers| {
+        !note
+            .to
+            .iter()
+            .chain(&note.cc)
+            .any(|to| to == PUBLIC || to == followers)
+    })
+}
+
+/// How a direct note about to be written by `author` is filed for them and its local recipients.
+pub async fn local_direct_messages<T: ConversationsRepository + UsersRepository>(
+    storage: &T,
+    base_url: &str,
+    author: &users::Model,
+    to: &[String],
+    in_reply_to: Option<&str>,
+    context: Option<&str>,
+) -> Result<Vec<DirectMessage>, DbErr> {
+    let participants: Vec<String> = std::iter::once(person::endpoint_uri(base_url, author))
+        .chain(to.iter().filter(|to| to.as_str() != PUBLIC).cloned())
+        .collect();
+
+    let mut recipients = vec![author.clone()];
+    recipients.extend(
+        local_users(storage, base_url, to)
+            .await?
+            .into_iter()
+            .filter(|user| user.id != author.id),
+    );
+    let mut messages = Vec::with_capacity(recipients.len());
+    for user in recipients {
+        messages.push(DirectMessage {
+            user_id: user.id,
+            context: context_of(storage, user.id, in_reply_to, context).await?,
+            participants: participants.clone(),
+        });
+    }
+    Ok(messages)
+}
+
+pub async fn record_remote<T: ConversationsRepository + UsersRepository>(
+    storage: &T,
+    base_url: &str,
+    note: &remote_notes::Model,
+) -> Result<(), DbErr> {
+    let addressed: Vec<String> = note.to.iter().chain(&note.cc).cloned().collect();
+    let participants: Vec<String> = std::iter::once(note.actor.clone())
+        .chain(addressed.iter().cloned())
+        .collect();
+
+    // Otherwise anyone could slip a message into a conversation held on another server.
+    let named = note
+        .context
+        .as_deref()
+        .filter(|context| same_origin(context, &note.actor));
+    for user in local_users(storage, base_url, &addressed).await? {
+        let context = context_of(storage, user.id, note.in_reply_to.as_deref(), named)
+            .await?
+            .unwrap_or_else(|| note.ap_id.clone());
+        storage
+            .add_conversation_message(
+                user.id,
+                &context,
+                &participants,
+                &note.ap_id,
+                None,
+                Some(note.id),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// The conversation a message joins for `user_id`: that of the message it answers if the user
+/// holds it, else the one the message names; `None` starts one with the message.
+async fn context_of<T: ConversationsRepository>(
+    storage: &T,
+    user_id: i64,
+    in_reply_to: Option<&str>,
+    context: Option<&str>,
+) -> Result<Option<String>, DbErr> {
+    if let Some(in_reply_to) = in_reply_to
+        && let Some(conversation) = storage
+            .find_conversation_by_message(user_id, in_reply_to)
+            .await?
+    {
+        return Ok(Some(conversation.context));
+    }
+    Ok(context.map(str::to_string))
+}
+
+async fn local_users<T: UsersRepository>(
+    storage: &T,
+    base_url: &str,
+    actors: &[String],
+) -> Result<Vec<users::Model>, DbErr> {
+    let mut users = Vec::new();
+    for actor in actors {
+        let Some(username) = person::local_username(base_url, actor) else {
+            continue;
+        };
+        if let Some(user) = storage.find_user_by_username(username).await? {
+            users.push(user);
+        }
+    }
+    Ok(users)
+}
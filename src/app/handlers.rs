@@ -1,2 +1,4 @@
 pub mod activity_pub;
+pub mod api;
+pub mod media;
 pub mod webfinger;
@@ -1,5 +1,122 @@
 pub mod create;
+pub mod followers;
+pub mod following;
 pub mod inbox;
+pub mod liked;
+pub mod likes;
 pub mod note;
 pub mod outbox;
 pub mod person;
+pub mod replies;
+pub mod shares;
+pub mod tag;
+
+use crate::app::state::AppState;
+use crate::domain::entities::notes;
+use crate::domain::repositories::FollowsRepository;
+use crate::domain::visibility::Visibility;
+use crate::federation::http_signature;
+use axum::{
+    body::Body,
+    http::{HeaderMap, Method, StatusCode, Uri, header},
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+
+pub const ACTIVITY_JSON: &str = "application/activity+json";
+
+/// `?page=N` on paged collections; without it the collection itself is served.
+#[derive(Deserialize)]
+pub struct PageQuery {
+    pub page: Option<u64>,
+}
+
+/// Whether the client asked for ActivityPub JSON rather than a web page.
+/// https://www.w3.org/TR/activitypub/#retrieving-objects
+/// Requests without an `Accept` header get JSON too.
+pub fn wants_activity_json(headers: &HeaderMap) -> bool {
+    let Some(accept) = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return true;
+    };
+
+    accept.split(',').any(|range| {
+        let mut parts = range.split(';').map(str::trim);
+        let media_type = parts.next().unwrap_or_default();
+        let refused = parts.any(|param| {
+            param
+                .split_once('=')
+                .is_some_and(|(name, value)| name.trim() == "q" && value.trim().parse() == Ok(0.0))
+        });
+        !refused
+            && (media_type.eq_ignore_ascii_case(ACTIVITY_JSON)
+                || media_type.eq_ignore_ascii_case("application/ld+json"))
+    })
+}
+
+/// Serves `body` as ActivityPub JSON.
+pub fn activity_json<T: Serialize>(status: StatusCode, body: &T) -> Result<Response, StatusCode> {
+    let json = serde_json::to_string(body).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, ACTIVITY_JSON)
+        .header(header::VARY, "Accept")
+        .body(Body::from(json))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Serves a web page in place of the ActivityPub representation.
+pub fn html(status: StatusCode, page: String) -> Result<Response, StatusCode> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+        .header(header::VARY, "Accept")
+        .body(Body::from(page))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Refused requests get 404, so that the note is not known to exist.
+/// https://docs.joinmastodon.org/spec/security/#http
+pub async fn authorize_note_fetch(
+    state: &AppState,
+    note: &notes::Model,
+    method: &Method,
+    uri: &Uri,
+    headers: &HeaderMap,
+) -> Result<(), StatusCode> {
+    let visibility = Visibility::of(note);
+    if visibility.is_readable_by_anyone() {
+        return Ok(());
+    }
+
+    let path_and_query = uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or(uri.path());
+    let signer = http_signature::verify_request(
+        &state.public_keys,
+        &state.storage,
+        method,
+        path_and_query,
+        headers,
+        &[],
+    )
+    .await
+    .map_err(|_| StatusCode::NOT_FOUND)?;
+    if note.to.contains(&signer) {
+        return Ok(());
+    }
+    if visibility == Visibility::Followers {
+        let follow = state
+            .storage
+            .find_follow(note.author_id, &signer)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        if follow.is_some_and(|follow| !follow.pending) {
+            return Ok(());
+        }
+    }
+    Err(StatusCode::NOT_FOUND)
+}
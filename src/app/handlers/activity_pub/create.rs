@@ -1,20 +1,34 @@
-use crate::app::{object_builders::activity_pub::create::build_create_activity, state::AppState};
-use crate::domain::{repositories::notes::NotesRepository, repositories::users::UsersRepository};
+use super::{activity_json, authorize_note_fetch, html, wants_activity_json};
+use crate::app::object_builders::{
+    activity_pub::{create::build_create_activity, note::NoteDetails},
+    html::note::build_note_page,
+};
+use crate::app::state::AppState;
+use crate::domain::repositories::{
+    media::MediaRepository, note_mentions::NoteMentionsRepository, note_tags::NoteTagsRepository,
+    notes::NotesRepository, users::UsersRepository,
+};
 use axum::{
-    body::Body,
     extract::{Path, State},
-    http::{StatusCode, header},
+    http::{HeaderMap, Method, StatusCode, Uri},
     response::Response,
 };
 
 pub async fn get(
     Path((_, id)): Path<(String, i64)>,
     State(state): State<AppState>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
 ) -> Result<Response, StatusCode> {
     let note = NotesRepository::find_note_by_id(&state.storage, id)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
+    authorize_note_fetch(&state, &note, &method, &uri, &headers).await?;
+    if note.deleted_at.is_some() {
+        return Err(StatusCode::GONE);
+    }
 
     let author = UsersRepository::find_user_by_id(&state.storage, note.author_id)
         .await
@@ -23,11 +37,31 @@ pub async fn get(
 
     let base_url = &state.config.base_url;
 
-    let create = build_create_activity(base_url, &note, &author);
-    let json = serde_json::to_string(&create).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let response = Response::builder()
-        .header(header::CONTENT_TYPE, "application/activity+json")
-        .body(Body::from(json))
+    // Browsers get the page of the note the activity created.
+    if !wants_activity_json(&headers) {
+        return html(StatusCode::OK, build_note_page(base_url, &note, &author));
+    }
+
+    let mentions = state
+        .storage
+        .list_note_mentions(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let tags = state
+        .storage
+        .list_note_tags(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let media = state
+        .storage
+        .list_note_media(note.id)
+        .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(response)
+    let details = NoteDetails {
+        mentions,
+        tags,
+        media,
+    };
+    let create = build_create_activity(base_url, &note, &author, &details);
+    activity_json(StatusCode::OK, &create)
 }
@@ -0,0 +1,48 @@
+use super::{PageQuery, activity_json};
+use crate::app::object_builders::activity_pub::collection::{PAGE_SIZE, page_offset};
+use crate::app::object_builders::activity_pub::following::{build_following, build_following_page};
+use crate::app::state::AppState;
+use crate::domain::repositories::{FollowingRepository, UsersRepository};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Response,
+};
+
+pub async fn get(
+    Path(username): Path<String>,
+    Query(query): Query<PageQuery>,
+    State(state): State<AppState>,
+) -> Result<Response, StatusCode> {
+    let storage = &state.storage;
+
+    let user = UsersRepository::find_user_by_username(storage, &username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let total_items = storage
+        .count_following(user.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let base_url = &state.config.base_url;
+
+    match query.page {
+        None => activity_json(
+            StatusCode::OK,
+            &build_following(base_url, &user, total_items),
+        ),
+        Some(0) => Err(StatusCode::BAD_REQUEST),
+        Some(page) => {
+            let offset = page_offset(page).ok_or(StatusCode::BAD_REQUEST)?;
+            let following = storage
+                .list_following_page(user.id, PAGE_SIZE, offset)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            activity_json(
+                StatusCode::OK,
+                &build_following_page(base_url, &user, page, total_items, &following),
+            )
+        }
+    }
+}
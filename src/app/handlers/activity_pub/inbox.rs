@@ -1,18 +1,26 @@
 mod accept;
 mod announce;
 mod create;
+mod delete;
 mod follow;
 mod like;
+mod reject;
 mod undo;
+mod update;
 
 use crate::app::state::AppState;
 use crate::app::types::InboxActivity;
 use crate::domain::repositories::UsersRepository;
+use crate::federation::http_signature;
 use axum::{
-    Json,
+    body::Bytes,
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, Method, StatusCode, Uri},
 };
+use calmi_activity_streams::types::enums::{
+    ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple,
+};
+use calmi_activity_streams::types::properties::Actor;
 
 pub fn endpoint_uri_template() -> &'static str {
     "/users/{username}/inbox"
@@ -21,10 +29,53 @@ pub fn endpoint_uri_template() -> &'static str {
 pub async fn post(
     Path(username): Path<String>,
     State(state): State<AppState>,
-    Json(activity): Json<InboxActivity>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> Result<StatusCode, StatusCode> {
     let storage = &state.storage;
 
+    let path_and_query = uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or(uri.path());
+    let signer = http_signature::verify_request(
+        &state.public_keys,
+        storage,
+        &method,
+        path_and_query,
+        &headers,
+        &body,
+    )
+    .await
+    .map_err(|err| {
+        eprintln!("Rejected inbox request for {}: {}", username, err);
+        StatusCode::UNAUTHORIZED
+    })?;
+
+    let activity: InboxActivity = serde_json::from_slice(&body).map_err(|err| {
+        eprintln!("Failed to parse inbox activity: {}", err);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let actor_id = activity
+        .actor()
+        .ok_or(StatusCode::BAD_REQUEST)
+        .and_then(|actor| {
+            extract_actor_id(actor).map_err(|err| {
+                eprintln!("Failed to read activity actor: {}", err);
+                StatusCode::BAD_REQUEST
+            })
+        })?;
+    if actor_id != signer {
+        eprintln!(
+            "Signer mismatch: activity actor {}, key owner {}",
+            actor_id, signer
+        );
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
     let inbox_owner = UsersRepository::find_user_by_username(storage, &username)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
@@ -36,7 +87,15 @@ pub async fn post(
 
     match activity {
         InboxActivity::Follow(follow) => {
-            follow::handle(follow, &base_url, &username, &inbox_owner, storage).await
+            follow::handle(
+                follow,
+                &base_url,
+                &username,
+                &inbox_owner,
+                storage,
+                &state.actors,
+            )
+            .await
         }
         InboxActivity::Like(like) => {
             like::handle(like, &base_url, &username, &inbox_owner, storage).await
@@ -70,7 +129,48 @@ pub async fn post(
                 Err(StatusCode::BAD_REQUEST)
             }
         },
-        InboxActivity::Create(create) => create::handle(create, &username).await,
-        InboxActivity::Accept(accept) => accept::handle(accept, &username).await,
+        InboxActivity::Create(create) => {
+            create::handle(
+                create,
+                &actor_id,
+                storage,
+                &state.http_client,
+                &state.actors,
+                &state.config.base_url,
+            )
+            .await
+        }
+        InboxActivity::Delete(delete) => delete::handle(delete, &actor_id, storage).await,
+        InboxActivity::Update(update) => update::handle(update, &actor_id, storage).await,
+        InboxActivity::Accept(accept) => {
+            accept::handle(accept, &actor_id, &inbox_owner, storage).await
+        }
+        InboxActivity::Reject(reject) => {
+            reject::handle(reject, &actor_id, &inbox_owner, storage).await
+        }
+    }
+}
+
+fn extract_actor_id(actor: &Actor) -> Result<String, String> {
+    match actor {
+        SingleOrMultiple::Single(value) => match value {
+            ObjectOrLinkOrStringUrl::Str(id) => Ok(id.clone()),
+            ObjectOrLinkOrStringUrl::Object(obj) => match obj {
+                ObjectBased::Person(person) => person
+                    .id
+                    .clone()
+                    .ok_or_else(|| "Person has no id".to_string()),
+                ObjectBased::Object(object) => object
+                    .id
+                    .clone()
+                    .ok_or_else(|| "Actor object has no id".to_string()),
+                _ => Err("Unsupported actor object type".to_string()),
+            },
+            ObjectOrLinkOrStringUrl::Link(link) => link
+                .href
+                .clone()
+                .ok_or_else(|| "Link has no href".to_string()),
+        },
+        SingleOrMultiple::Multiple(_) => Err("Multiple actors not supported".to_string()),
     }
 }
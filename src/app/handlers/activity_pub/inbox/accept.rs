@@ -1,7 +1,77 @@
+use crate::domain::entities::users::Model as User;
+use crate::domain::repositories::FollowingRepository;
 use axum::http::StatusCode;
+use calmi_activity_streams::types::enums::{
+    ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple,
+};
 use calmi_activity_streams::types::object::accept::Accept;
+use calmi_activity_streams::types::properties::ObjectProperty;
 
-pub async fn handle(_accept: Accept, _username: &str) -> Result<StatusCode, StatusCode> {
-    println!("Accept activity processed");
+pub async fn handle<T: FollowingRepository>(
+    accept: Accept,
+    actor_id: &str,
+    inbox_owner: &User,
+    storage: &T,
+) -> Result<StatusCode, StatusCode> {
+    let object = accept.object.as_deref().ok_or_else(|| {
+        eprintln!("Accept activity missing object");
+        StatusCode::BAD_REQUEST
+    })?;
+    let follow_id = extract_object_id(object).map_err(|err| {
+        eprintln!("Failed to handle Accept activity: {}", err);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let record = storage
+        .find_following_by_activity_id(&follow_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some(record) = record else {
+        println!("Accept for unknown activity {} ignored", follow_id);
+        return Ok(StatusCode::ACCEPTED);
+    };
+
+    // Only the followed actor may accept, and only in the follower's inbox.
+    if record.user_id != inbox_owner.id || record.actor != actor_id {
+        eprintln!(
+            "Accept of {} by {} does not match the recorded follow",
+            follow_id, actor_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    storage
+        .accept_following(record.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    println!(
+        "Follow accepted: {} now follows {}",
+        inbox_owner.username, actor_id
+    );
     Ok(StatusCode::ACCEPTED)
 }
+
+/// The id of the object an Accept, Reject or Delete refers to.
+pub(super) fn extract_object_id(object: &ObjectProperty) -> Result<String, String> {
+    match object {
+        SingleOrMultiple::Single(value) => match value {
+            ObjectOrLinkOrStringUrl::Str(id) => Ok(id.clone()),
+            ObjectOrLinkOrStringUrl::Link(link) => link
+                .href
+                .clone()
+                .ok_or_else(|| "Link has no href".to_string()),
+            ObjectOrLinkOrStringUrl::Object(obj) => match obj {
+                ObjectBased::Follow(follow) => follow.id.clone(),
+                ObjectBased::Activity(activity) => activity.id.clone(),
+                ObjectBased::Object(object) => object.id.clone(),
+                ObjectBased::Note(note) => note.id.clone(),
+                ObjectBased::Person(person) => person.id.clone(),
+                ObjectBased::Tombstone(tombstone) => tombstone.id.clone(),
+                _ => None,
+            }
+            .ok_or_else(|| "Embedded object has no id".to_string()),
+        },
+        SingleOrMultiple::Multiple(_) => Err("Multiple objects are not supported".to_string()),
+    }
+}
@@ -1,95 +1,179 @@
+use crate::app::{conversations, hashtags, media, mentions};
+use crate::domain::repositories::{
+    ConversationsRepository, MediaRepository, MentionsRepository, NoteTagsRepository,
+    RemoteActorsRepository, RemoteNotesRepository, UsersRepository,
+};
+use crate::federation::actor::ActorResolver;
+use crate::federation::note::{fetch_note, ids_of, store_note};
+use crate::federation::same_origin;
 use axum::http::StatusCode;
+use calmi_activity_streams::types::PUBLIC;
 use calmi_activity_streams::types::enums::{
     ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple,
 };
 use calmi_activity_streams::types::object::create::Create;
-use calmi_activity_streams::types::properties::{Actor, ObjectProperty};
+use calmi_activity_streams::types::object::note::Note;
+
+pub async fn handle<
+    T: RemoteNotesRepository
+        + UsersRepository
+        + MentionsRepository
+        + NoteTagsRepository
+        + MediaRepository
+        + RemoteActorsRepository
+        + ConversationsRepository,
+>(
+    create: Create,
+    actor_id: &str,
+    storage: &T,
+    client: &reqwest::Client,
+    actors: &ActorResolver,
+    base_url: &str,
+) -> Result<StatusCode, StatusCode> {
+    let object = match create.object.as_deref() {
+        Some(SingleOrMultiple::Single(object)) => object,
+        Some(SingleOrMultiple::Multiple(_)) => {
+            eprintln!("Failed to handle Create activity: multiple objects not supported");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        None => {
+            eprintln!("Failed to handle Create activity: missing object");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    };
 
-pub async fn handle(create: Create, _username: &str) -> Result<StatusCode, StatusCode> {
-    match parse_create_activity(&create) {
-        Ok(data) => {
+    let note = match object {
+        ObjectOrLinkOrStringUrl::Object(ObjectBased::Note(note)) => note.clone(),
+        ObjectOrLinkOrStringUrl::Str(id) => fetch(client, id).await?,
+        ObjectOrLinkOrStringUrl::Link(link) => match &link.href {
+            Some(href) => fetch(client, href).await?,
+            None => {
+                eprintln!("Failed to handle Create activity: object link has no href");
+                return Err(StatusCode::BAD_REQUEST);
+            }
+        },
+        ObjectOrLinkOrStringUrl::Object(_) => {
             println!(
-                "Create activity: actor={}, object_type={}, object_id={:?}, activity_id={:?}",
-                data.actor_id, data.object_type, data.object_id, data.activity_id
+                "Ignoring Create activity {:?} from {}: object is not a Note",
+                create.id, actor_id
             );
-            Ok(StatusCode::ACCEPTED)
+            return Ok(StatusCode::ACCEPTED);
         }
+    };
+
+    let Some(note_id) = note.id.as_deref() else {
+        eprintln!("Failed to handle Create activity: note has no id");
+        return Err(StatusCode::BAD_REQUEST);
+    };
+
+    // Only the author may create a note, and only on their own server.
+    let attributed_to = note.attributed_to.as_deref().map(ids_of);
+    if attributed_to.is_some_and(|authors| authors.iter().all(|author| author != actor_id)) {
+        eprintln!(
+            "Create activity by {} for note {} attributed to someone else",
+            actor_id, note_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+    if !same_origin(note_id, actor_id) {
+        eprintln!(
+            "Create activity by {} for note {} from another origin",
+            actor_id, note_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    match store_note(storage, actor_id, &note).await {
+        Ok(true) => index_note(storage, note_id, &note).await?,
+        Ok(false) => println!("Note {} already stored", note_id),
         Err(err) => {
-            eprintln!("Failed to handle Create activity: {}", err);
-            Err(StatusCode::BAD_REQUEST)
+            eprintln!("{}", err);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
     }
-}
-
-struct CreateActivityData {
-    actor_id: String,
-    object_type: String,
-    object_id: Option<String>,
-    activity_id: Option<String>,
-}
-
-fn parse_create_activity(create: &Create) -> Result<CreateActivityData, String> {
-    let actor = create
-        .actor
-        .as_ref()
-        .ok_or_else(|| "Missing actor".to_string())?
-        .as_ref();
 
-    let actor_id = extract_actor_id(actor)?;
+    // The note arrives once per local recipient; recording a mention twice is harmless.
+    mentions::record(
+        storage,
+        base_url,
+        note_id,
+        actor_id,
+        &mentions::mentioned_actors(&note),
+    )
+    .await
+    .map_err(|err| {
+        eprintln!("Failed to record mentions in note {}: {}", note_id, err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
-    let object = create
-        .object
-        .as_ref()
-        .ok_or_else(|| "Missing object in Create activity".to_string())?;
+    file_direct_message(storage, actors, base_url, note_id, actor_id).await?;
+    Ok(StatusCode::ACCEPTED)
+}
 
-    let (object_type, object_id) = extract_object_info(object.as_ref())?;
-    let activity_id = create.id.clone();
+/// Telling direct notes from those for followers takes the followers collection of the author.
+async fn file_direct_message<
+    T: RemoteNotesRepository + UsersRepository + RemoteActorsRepository + ConversationsRepository,
+>(
+    storage: &T,
+    actors: &ActorResolver,
+    base_url: &str,
+    note_id: &str,
+    actor_id: &str,
+) -> Result<(), StatusCode> {
+    let stored = storage
+        .find_remote_note_by_ap_id(note_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    if stored.to.iter().chain(&stored.cc).any(|to| to == PUBLIC) {
+        return Ok(());
+    }
 
-    Ok(CreateActivityData {
-        actor_id,
-        object_type,
-        object_id,
-        activity_id,
-    })
+    let followers = match actors.resolve(storage, actor_id).await {
+        Ok(actor) => actor.followers,
+        Err(err) => {
+            eprintln!("Cannot tell whether note {} is direct: {}", note_id, err);
+            return Ok(());
+        }
+    };
+    if !conversations::is_direct(&stored, followers.as_deref()) {
+        return Ok(());
+    }
+    conversations::record_remote(storage, base_url, &stored)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to file direct note {}: {}", note_id, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
 }
 
-fn extract_actor_id(actor: &Actor) -> Result<String, String> {
-    match actor {
-        SingleOrMultiple::Single(value) => match value {
-            ObjectOrLinkOrStringUrl::Str(id) => Ok(id.clone()),
-            ObjectOrLinkOrStringUrl::Object(obj) => match obj {
-                ObjectBased::Person(person) => person
-                    .id
-                    .clone()
-                    .ok_or_else(|| "Person has no id".to_string()),
-                _ => Err("Unsupported actor object type".to_string()),
-            },
-            ObjectOrLinkOrStringUrl::Link(link) => link
-                .href
-                .clone()
-                .ok_or_else(|| "Link has no href".to_string()),
-        },
-        SingleOrMultiple::Multiple(_) => Err("Multiple actors not supported".to_string()),
-    }
+async fn index_note<T: RemoteNotesRepository + NoteTagsRepository + MediaRepository>(
+    storage: &T,
+    note_id: &str,
+    note: &Note,
+) -> Result<(), StatusCode> {
+    let stored = storage
+        .find_remote_note_by_ap_id(note_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    hashtags::index_remote(storage, &stored, note)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to index hashtags of note {}: {}", note_id, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    media::record_remote(storage, &stored, note)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to record attachments of note {}: {}", note_id, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
 }
 
-fn extract_object_info(object: &ObjectProperty) -> Result<(String, Option<String>), String> {
-    match object {
-        SingleOrMultiple::Single(value) => match value {
-            ObjectOrLinkOrStringUrl::Str(id) => Ok(("Unknown".to_string(), Some(id.clone()))),
-            ObjectOrLinkOrStringUrl::Object(obj) => match obj {
-                ObjectBased::Note(note) => Ok(("Note".to_string(), note.id.clone())),
-                ObjectBased::Object(object) => Ok((
-                    object
-                        .r#type
-                        .clone()
-                        .unwrap_or_else(|| "Object".to_string()),
-                    object.id.clone(),
-                )),
-                _ => Ok(("Unknown".to_string(), None)),
-            },
-            ObjectOrLinkOrStringUrl::Link(_link) => Ok(("Link".to_string(), None)),
-        },
-        SingleOrMultiple::Multiple(_) => Err("Multiple objects not supported".to_string()),
-    }
+async fn fetch(client: &reqwest::Client, id: &str) -> Result<Note, StatusCode> {
+    fetch_note(client, id).await.map_err(|err| {
+        eprintln!("Failed to handle Create activity: {}", err);
+        StatusCode::BAD_GATEWAY
+    })
 }
@@ -0,0 +1,92 @@
+use super::accept::extract_object_id;
+use crate::domain::repositories::{
+    FollowsRepository, NoteAnnouncesRepository, NoteLikesRepository, RemoteActorsRepository,
+    RemoteNotesRepository,
+};
+use axum::http::StatusCode;
+use calmi_activity_streams::types::object::delete::Delete;
+
+pub async fn handle<T>(
+    delete: Delete,
+    actor_id: &str,
+    storage: &T,
+) -> Result<StatusCode, StatusCode>
+where
+    T: FollowsRepository
+        + NoteLikesRepository
+        + NoteAnnouncesRepository
+        + RemoteActorsRepository
+        + RemoteNotesRepository,
+{
+    let object = delete.object.as_deref().ok_or_else(|| {
+        eprintln!("Delete activity missing object");
+        StatusCode::BAD_REQUEST
+    })?;
+    let object_id = extract_object_id(object).map_err(|err| {
+        eprintln!("Failed to handle Delete activity: {}", err);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    if object_id == actor_id {
+        return delete_actor(actor_id, storage).await;
+    }
+
+    let note = storage
+        .find_remote_note_by_ap_id(&object_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some(note) = note else {
+        println!("Delete for unknown object {} ignored", object_id);
+        return Ok(StatusCode::ACCEPTED);
+    };
+
+    if note.actor != actor_id {
+        eprintln!(
+            "Delete of {} by {} who is not its author",
+            object_id, actor_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    storage
+        .remove_remote_note(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    println!("Remote note deleted: {}", object_id);
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Forgets everything an actor left behind once their account is gone.
+async fn delete_actor<T>(actor_id: &str, storage: &T) -> Result<StatusCode, StatusCode>
+where
+    T: FollowsRepository
+        + NoteLikesRepository
+        + NoteAnnouncesRepository
+        + RemoteActorsRepository
+        + RemoteNotesRepository,
+{
+    storage
+        .remove_likes_by_actor(actor_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    storage
+        .remove_announces_by_actor(actor_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    storage
+        .remove_follows_by_actor(actor_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    storage
+        .remove_remote_notes_by_actor(actor_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    storage
+        .remove_remote_actor_by_uri(actor_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    println!("Remote actor deleted: {}", actor_id);
+    Ok(StatusCode::ACCEPTED)
+}
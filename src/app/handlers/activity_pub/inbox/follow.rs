@@ -1,5 +1,8 @@
+use crate::app::jobs::delivery;
+use crate::app::object_builders::activity_pub::accept::build_accept_follow;
 use crate::domain::entities::users::Model as User;
-use crate::domain::repositories::FollowsRepository;
+use crate::domain::repositories::{FollowsRepository, JobsRepository, RemoteActorsRepository};
+use crate::federation::actor::ActorResolver;
 use axum::http::StatusCode;
 use calmi_activity_streams::types::enums::{
     ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple,
@@ -7,12 +10,13 @@ use calmi_activity_streams::types::enums::{
 use calmi_activity_streams::types::object::follow::Follow;
 use calmi_activity_streams::types::properties::{Actor, ObjectProperty};
 
-pub async fn handle<T: FollowsRepository>(
+pub async fn handle<T: FollowsRepository + JobsRepository + RemoteActorsRepository>(
     follow: Follow,
     base_url: &str,
     username: &str,
     inbox_owner: &User,
     storage: &T,
+    actors: &ActorResolver,
 ) -> Result<StatusCode, StatusCode> {
     let data = match parse_follow_activity(&follow, base_url, username) {
         Ok(data) => data,
@@ -30,18 +34,54 @@ pub async fn handle<T: FollowsRepository>(
         return Err(StatusCode::BAD_REQUEST);
     }
 
+    let pending = inbox_owner.manually_approves_followers;
     if let Err(err) = storage
-        .add_follow(inbox_owner.id, &data.follower_id, &data.activity_id)
+        .add_follow(
+            inbox_owner.id,
+            &data.follower_id,
+            &data.activity_id,
+            pending,
+        )
         .await
     {
         eprintln!("Failed to persist follow: {}", err);
         return Err(StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    let record = storage
+        .find_follow_by_activity_id(&data.activity_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let record = match record {
+        Some(record) => record,
+        // Already known under an earlier Follow; answer for that one.
+        None => storage
+            .find_follow(inbox_owner.id, &data.follower_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?,
+    };
+
+    if record.pending {
+        println!(
+            "Follow request recorded: {} asks to follow {}",
+            data.follower_id, data.followee_username
+        );
+        return Ok(StatusCode::ACCEPTED);
+    }
+
     println!(
         "Follow recorded: {} now follows {}",
         data.follower_id, data.followee_username
     );
+
+    let accept = build_accept_follow(base_url, inbox_owner, &record);
+    if let Err(err) =
+        delivery::enqueue_to_actor(storage, actors, inbox_owner, &data.follower_id, &accept).await
+    {
+        eprintln!("Cannot send Accept to {}: {}", data.follower_id, err);
+    }
+
     Ok(StatusCode::ACCEPTED)
 }
 
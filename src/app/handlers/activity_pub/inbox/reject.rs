@@ -0,0 +1,50 @@
+use super::accept::extract_object_id;
+use crate::domain::entities::users::Model as User;
+use crate::domain::repositories::FollowingRepository;
+use axum::http::StatusCode;
+use calmi_activity_streams::types::object::reject::Reject;
+
+pub async fn handle<T: FollowingRepository>(
+    reject: Reject,
+    actor_id: &str,
+    inbox_owner: &User,
+    storage: &T,
+) -> Result<StatusCode, StatusCode> {
+    let object = reject.object.as_deref().ok_or_else(|| {
+        eprintln!("Reject activity missing object");
+        StatusCode::BAD_REQUEST
+    })?;
+    let follow_id = extract_object_id(object).map_err(|err| {
+        eprintln!("Failed to handle Reject activity: {}", err);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let record = storage
+        .find_following_by_activity_id(&follow_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some(record) = record else {
+        println!("Reject for unknown activity {} ignored", follow_id);
+        return Ok(StatusCode::ACCEPTED);
+    };
+
+    if record.user_id != inbox_owner.id || record.actor != actor_id {
+        eprintln!(
+            "Reject of {} by {} does not match the recorded follow",
+            follow_id, actor_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // A Reject may also arrive after an Accept, which ends the follow.
+    storage
+        .remove_following(record.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    println!(
+        "Follow rejected: {} does not follow {}",
+        inbox_owner.username, actor_id
+    );
+    Ok(StatusCode::ACCEPTED)
+}
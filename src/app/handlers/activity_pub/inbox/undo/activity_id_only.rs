@@ -9,21 +9,21 @@ pub async fn handle<T: FollowsRepository + NoteLikesRepository + NoteAnnouncesRe
     storage: &T,
 ) -> Result<StatusCode, StatusCode> {
     let removed = storage
-        .remove_follow_by_activity_id(&activity_id)
+        .remove_follow_by_activity_id(&activity_id, &actor_id)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     if removed > 0 {
         return Ok(StatusCode::ACCEPTED);
     }
     let removed = storage
-        .remove_like_by_activity_id(&activity_id)
+        .remove_like_by_activity_id(&activity_id, &actor_id)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     if removed > 0 {
         return Ok(StatusCode::ACCEPTED);
     }
     let removed = storage
-        .remove_announce_by_activity_id(&activity_id)
+        .remove_announce_by_activity_id(&activity_id, &actor_id)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     if removed == 0 {
@@ -33,7 +33,7 @@ pub async fn handle<T: NotesRepository + NoteAnnouncesRepository>(
         let mut removed = 0;
         if let Some(activity_id) = announce_data.activity_id.as_deref() {
             removed = storage
-                .remove_announce_by_activity_id(activity_id)
+                .remove_announce_by_activity_id(activity_id, &announce_data.actor_id)
                 .await
                 .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
         }
@@ -20,7 +20,7 @@ pub async fn handle<T: FollowsRepository>(
     let mut removed = 0;
     if let Some(activity_id) = follow_data.activity_id.as_deref() {
         removed = storage
-            .remove_follow_by_activity_id(activity_id)
+            .remove_follow_by_activity_id(activity_id, &follow_data.follower_id)
             .await
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     }
@@ -33,7 +33,7 @@ pub async fn handle<T: NotesRepository + NoteLikesRepository>(
         let mut removed = 0;
         if let Some(activity_id) = like_data.activity_id.as_deref() {
             removed = storage
-                .remove_like_by_activity_id(activity_id)
+                .remove_like_by_activity_id(activity_id, &like_data.actor_id)
                 .await
                 .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
         }
@@ -0,0 +1,122 @@
+use crate::app::{hashtags, media};
+use crate::domain::repositories::{
+    MediaRepository, NoteTagsRepository, RemoteActorsRepository, RemoteNotesRepository,
+};
+use crate::federation::actor::store_person;
+use crate::federation::note::{ids_of, update_note};
+use axum::http::StatusCode;
+use calmi_activity_streams::types::enums::{
+    ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple,
+};
+use calmi_activity_streams::types::object::note::Note;
+use calmi_activity_streams::types::object::person::Person;
+use calmi_activity_streams::types::object::update::Update;
+
+pub async fn handle<
+    T: RemoteActorsRepository + RemoteNotesRepository + NoteTagsRepository + MediaRepository,
+>(
+    update: Update,
+    actor_id: &str,
+    storage: &T,
+) -> Result<StatusCode, StatusCode> {
+    match update.object.as_deref() {
+        Some(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Object(object))) => match object {
+            ObjectBased::Note(note) => update_remote_note(note, actor_id, storage).await,
+            ObjectBased::Person(person) => update_remote_actor(person, actor_id, storage).await,
+            _ => {
+                println!(
+                    "Ignoring Update activity {:?} from {}: unsupported object",
+                    update.id, actor_id
+                );
+                Ok(StatusCode::ACCEPTED)
+            }
+        },
+        Some(_) => {
+            println!(
+                "Ignoring Update activity {:?} from {}: object is not embedded",
+                update.id, actor_id
+            );
+            Ok(StatusCode::ACCEPTED)
+        }
+        None => {
+            eprintln!("Update activity missing object");
+            Err(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+async fn update_remote_note<T: RemoteNotesRepository + NoteTagsRepository + MediaRepository>(
+    note: &Note,
+    actor_id: &str,
+    storage: &T,
+) -> Result<StatusCode, StatusCode> {
+    let Some(note_id) = note.id.as_deref() else {
+        eprintln!("Failed to handle Update activity: note has no id");
+        return Err(StatusCode::BAD_REQUEST);
+    };
+
+    let stored = storage
+        .find_remote_note_by_ap_id(note_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some(stored) = stored else {
+        println!("Update for unknown note {} ignored", note_id);
+        return Ok(StatusCode::ACCEPTED);
+    };
+
+    // Only the author may edit, and the edit may not hand the note to someone else.
+    let attributed_to = note.attributed_to.as_deref().map(ids_of);
+    if stored.actor != actor_id
+        || attributed_to.is_some_and(|authors| authors.iter().all(|author| author != actor_id))
+    {
+        eprintln!(
+            "Update of {} by {} who is not its author",
+            note_id, actor_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let stored = update_note(storage, stored, note).await.map_err(|err| {
+        eprintln!("{}", err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    hashtags::index_remote(storage, &stored, note)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to index hashtags of note {}: {}", note_id, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    media::record_remote(storage, &stored, note)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to record attachments of note {}: {}", note_id, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    println!("Remote note updated: {}", note_id);
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn update_remote_actor<T: RemoteActorsRepository>(
+    person: &Person,
+    actor_id: &str,
+    storage: &T,
+) -> Result<StatusCode, StatusCode> {
+    if person.id.as_deref() != Some(actor_id) {
+        eprintln!(
+            "Update of actor {:?} by {} who is someone else",
+            person.id, actor_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    store_person(storage, actor_id, person)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to handle Update activity: {}", err);
+            StatusCode::BAD_REQUEST
+        })?;
+
+    println!("Remote actor updated: {}", actor_id);
+    Ok(StatusCode::ACCEPTED)
+}
@@ -1,17 +1,28 @@
-use crate::app::object_builders::activity_pub::note::build_note;
+use super::{activity_json, authorize_note_fetch, html, wants_activity_json};
+use crate::app::object_builders::activity_pub::note::{
+    NoteDetails, build_note_with_interactions, build_tombstone,
+};
+use crate::app::object_builders::html::note::{build_deleted_note_page, build_note_page};
 use crate::app::state::AppState;
+use crate::domain::repositories::media::MediaRepository;
+use crate::domain::repositories::note_announces::NoteAnnouncesRepository;
+use crate::domain::repositories::note_likes::NoteLikesRepository;
+use crate::domain::repositories::note_mentions::NoteMentionsRepository;
+use crate::domain::repositories::note_tags::NoteTagsRepository;
 use crate::domain::repositories::notes::NotesRepository;
 use crate::domain::repositories::users::UsersRepository;
 use axum::{
-    body::Body,
     extract::{Path, State},
-    http::{StatusCode, header},
+    http::{HeaderMap, Method, StatusCode, Uri},
     response::Response,
 };
 
 pub async fn get(
     Path((_, id)): Path<(String, i64)>,
     State(state): State<AppState>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
 ) -> Result<Response, StatusCode> {
     let storage = &state.storage;
 
@@ -19,6 +30,7 @@ pub async fn get(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
+    authorize_note_fetch(&state, &note, &method, &uri, &headers).await?;
     let author = UsersRepository::find_user_by_id(storage, note.author_id)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
@@ -26,11 +38,47 @@ pub async fn get(
 
     let base_url = &state.config.base_url;
 
-    let note = build_note(base_url, &note, &author);
-    let json = serde_json::to_string(&note).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let response = Response::builder()
-        .header(header::CONTENT_TYPE, "application/activity+json")
-        .body(Body::from(json))
+    if !wants_activity_json(&headers) {
+        return if note.deleted_at.is_some() {
+            html(
+                StatusCode::GONE,
+                build_deleted_note_page(base_url, &note, &author),
+            )
+        } else {
+            html(StatusCode::OK, build_note_page(base_url, &note, &author))
+        };
+    }
+
+    if note.deleted_at.is_some() {
+        let tombstone = build_tombstone(base_url, &note, &author);
+        return activity_json(StatusCode::GONE, &tombstone);
+    }
+
+    let likes = storage
+        .count_likes(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let shares = storage
+        .count_announces(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mentions = storage
+        .list_note_mentions(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let tags = storage
+        .list_note_tags(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let media = storage
+        .list_note_media(note.id)
+        .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(response)
+    let details = NoteDetails {
+        mentions,
+        tags,
+        media,
+    };
+    let note = build_note_with_interactions(base_url, &note, &author, &details, likes, shares);
+    activity_json(StatusCode::OK, &note)
 }
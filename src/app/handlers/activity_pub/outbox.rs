@@ -1,27 +1,156 @@
-use crate::app::object_builders::activity_pub::outbox::build_outbox;
+use super::activity_json;
+use crate::app::object_builders::activity_pub::collection::PAGE_SIZE;
+use crate::app::object_builders::activity_pub::note::NoteDetails;
+use crate::app::object_builders::activity_pub::outbox::{
+    OutboxCursor, build_outbox, build_outbox_page,
+};
 use crate::app::state::AppState;
-use crate::domain::repositories::{notes::NotesRepository, users::UsersRepository};
+use crate::domain::repositories::{
+    media::MediaRepository, note_mentions::NoteMentionsRepository, note_tags::NoteTagsRepository,
+    notes::NotesRepository, users::UsersRepository,
+};
 use axum::{
-    Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
+    response::Response,
 };
-use calmi_activity_streams::types::object::ordered_collection::OrderedCollection;
+use serde::Deserialize;
+
+/// `?page=true` serves the newest page; `max_id` and `min_id` walk to older and newer notes.
+#[derive(Deserialize)]
+pub struct OutboxQuery {
+    pub page: Option<bool>,
+    pub max_id: Option<i64>,
+    pub min_id: Option<i64>,
+}
 
 pub async fn get(
     Path(username): Path<String>,
+    Query(query): Query<OutboxQuery>,
     State(state): State<AppState>,
-) -> Result<Json<OrderedCollection>, StatusCode> {
+) -> Result<Response, StatusCode> {
     let storage = &state.storage;
 
     let user = UsersRepository::find_user_by_username(storage, &username)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
-    let notes = NotesRepository::find_note_by_author_id(storage, user.id, 20, 0)
+    let total_items = storage
+        .count_notes_by_author(user.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let cursor = match (query.page, query.max_id, query.min_id) {
+        (_, Some(_), Some(_)) => return Err(StatusCode::BAD_REQUEST),
+        (_, Some(max_id), None) => OutboxCursor::MaxId(max_id),
+        (_, None, Some(min_id)) => OutboxCursor::MinId(min_id),
+        (Some(true), None, None) => OutboxCursor::Newest,
+        (_, None, None) => {
+            let outbox = build_outbox(&state.config, &user, total_items);
+            return activity_json(StatusCode::OK, &outbox);
+        }
+    };
+
+    // The note a page is cut at; it has to be one of the author's.
+    let anchor = match cursor {
+        OutboxCursor::MaxId(id) | OutboxCursor::MinId(id) if id != 0 => Some(
+            storage
+                .find_note_by_id(id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+                .filter(|note| note.author_id == user.id)
+                .ok_or(StatusCode::BAD_REQUEST)?,
+        ),
+        _ => None,
+    };
+
+    // One extra row tells whether there is more beyond this page.
+    let (notes, next, prev) = match cursor {
+        OutboxCursor::Newest | OutboxCursor::MaxId(_) => {
+            let mut notes = storage
+                .list_notes_by_author_before(user.id, anchor.as_ref(), PAGE_SIZE + 1)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let has_older = notes.len() as u64 > PAGE_SIZE;
+            notes.truncate(PAGE_SIZE as usize);
+            let next = notes
+                .last()
+                .filter(|_| has_older)
+                .map(|note| OutboxCursor::MaxId(note.id));
+            let prev = notes
+                .first()
+                .filter(|_| anchor.is_some())
+                .map(|note| OutboxCursor::MinId(note.id));
+            (notes, next, prev)
+        }
+        OutboxCursor::MinId(_) => {
+            let mut notes = storage
+                .list_notes_by_author_after(user.id, anchor.as_ref(), PAGE_SIZE + 1)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let has_newer = notes.len() as u64 > PAGE_SIZE;
+            notes.truncate(PAGE_SIZE as usize);
+            notes.reverse();
+            let next = notes
+                .last()
+                .filter(|_| anchor.is_some())
+                .map(|note| OutboxCursor::MaxId(note.id));
+            let prev = notes
+                .first()
+                .filter(|_| has_newer)
+                .map(|note| OutboxCursor::MinId(note.id));
+            (notes, next, prev)
+        }
+    };
+
+    let note_ids: Vec<i64> = notes.iter().map(|note| note.id).collect();
+    let mentions = storage
+        .list_note_mentions_of_notes(&note_ids)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let tags = storage
+        .list_note_tags_of_notes(&note_ids)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let media = storage
+        .list_note_media_of_notes(&note_ids)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let notes: Vec<_> = notes
+        .into_iter()
+        .map(|note| {
+            let own_mentions = mentions
+                .iter()
+                .filter(|mention| mention.note_id == note.id)
+                .cloned()
+                .collect();
+            let own_tags = tags
+                .iter()
+                .filter(|tag| tag.note_id == Some(note.id))
+                .cloned()
+                .collect();
+            let own_media = media
+                .iter()
+                .filter(|media| media.note_id == Some(note.id))
+                .cloned()
+                .collect();
+            let details = NoteDetails {
+                mentions: own_mentions,
+                tags: own_tags,
+                media: own_media,
+            };
+            (note, details)
+        })
+        .collect();
 
-    let outbox = build_outbox(&state.config, &user, &notes);
-    Ok(Json(outbox))
+    let page = build_outbox_page(
+        &state.config,
+        &user,
+        cursor,
+        &notes,
+        total_items,
+        next,
+        prev,
+    );
+    activity_json(StatusCode::OK, &page)
 }
@@ -1,16 +1,21 @@
+use super::{activity_json, html, wants_activity_json};
 use crate::app::object_builders::activity_pub::person::build_person;
+use crate::app::object_builders::html::person::build_profile_page;
 use crate::app::state::AppState;
-use crate::domain::repositories::users::UsersRepository;
+use crate::domain::repositories::{notes::NotesRepository, users::UsersRepository};
 use axum::{
-    body::Body,
     extract::{Path, State},
-    http::{StatusCode, header},
+    http::{HeaderMap, StatusCode},
     response::Response,
 };
 
+/// How many notes the profile page shows.
+const PROFILE_NOTES: u64 = 20;
+
 pub async fn get(
     Path(username): Path<String>,
     State(state): State<AppState>,
+    headers: HeaderMap,
 ) -> Result<Response, StatusCode> {
     let user = state
         .storage
@@ -19,11 +24,18 @@ pub async fn get(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
 
-    let person = build_person(&state.config, &user);
-    let json = serde_json::to_string(&person).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let response = Response::builder()
-        .header(header::CONTENT_TYPE, "application/activity+json")
-        .body(Body::from(json))
+    if wants_activity_json(&headers) {
+        let person = build_person(&state.config, &user);
+        return activity_json(StatusCode::OK, &person);
+    }
+
+    let notes = state
+        .storage
+        .list_notes_by_author_before(user.id, None, PROFILE_NOTES)
+        .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(response)
+    html(
+        StatusCode::OK,
+        build_profile_page(&state.config, &user, &notes),
+    )
 }
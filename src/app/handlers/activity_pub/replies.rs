@@ -0,0 +1,67 @@
+use super::{PageQuery, activity_json, authorize_note_fetch};
+use crate::app::object_builders::activity_pub::collection::{PAGE_SIZE, page_offset};
+use crate::app::object_builders::activity_pub::note::endpoint_uri;
+use crate::app::object_builders::activity_pub::replies::{build_replies, build_replies_page};
+use crate::app::state::AppState;
+use crate::app::threads;
+use crate::domain::repositories::{NotesRepository, UsersRepository};
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, Method, StatusCode, Uri},
+    response::Response,
+};
+
+pub async fn get(
+    Path((_, id)): Path<(String, i64)>,
+    Query(query): Query<PageQuery>,
+    State(state): State<AppState>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let storage = &state.storage;
+
+    let note = NotesRepository::find_note_by_id(storage, id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    authorize_note_fetch(&state, &note, &method, &uri, &headers).await?;
+    if note.deleted_at.is_some() {
+        return Err(StatusCode::GONE);
+    }
+    let author = UsersRepository::find_user_by_id(storage, note.author_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let base_url = &state.config.base_url;
+    let replies: Vec<String> =
+        threads::replies(storage, base_url, &endpoint_uri(base_url, &note, &author))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .into_iter()
+            .filter(|reply| reply.public && !reply.deleted)
+            .map(|reply| reply.id)
+            .collect();
+    let total_items = replies.len() as u64;
+
+    match query.page {
+        None => activity_json(
+            StatusCode::OK,
+            &build_replies(base_url, &note, &author, total_items),
+        ),
+        Some(0) => Err(StatusCode::BAD_REQUEST),
+        Some(page) => {
+            let offset = page_offset(page).ok_or(StatusCode::BAD_REQUEST)?;
+            let replies: Vec<String> = replies
+                .into_iter()
+                .skip(usize::try_from(offset).unwrap_or(usize::MAX))
+                .take(PAGE_SIZE as usize)
+                .collect();
+            activity_json(
+                StatusCode::OK,
+                &build_replies_page(base_url, &note, &author, page, total_items, &replies),
+            )
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::{PageQuery, activity_json, authorize_note_fetch};
+use crate::app::object_builders::activity_pub::collection::{PAGE_SIZE, page_offset};
+use crate::app::object_builders::activity_pub::shares::{build_shares, build_shares_page};
+use crate::app::state::AppState;
+use crate::domain::repositories::{NoteAnnouncesRepository, NotesRepository, UsersRepository};
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, Method, StatusCode, Uri},
+    response::Response,
+};
+
+pub async fn get(
+    Path((_, id)): Path<(String, i64)>,
+    Query(query): Query<PageQuery>,
+    State(state): State<AppState>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let storage = &state.storage;
+
+    let note = NotesRepository::find_note_by_id(storage, id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    authorize_note_fetch(&state, &note, &method, &uri, &headers).await?;
+    if note.deleted_at.is_some() {
+        return Err(StatusCode::GONE);
+    }
+    let author = UsersRepository::find_user_by_id(storage, note.author_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let total_items = storage
+        .count_announces(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let base_url = &state.config.base_url;
+
+    match query.page {
+        None => activity_json(
+            StatusCode::OK,
+            &build_shares(base_url, &note, &author, total_items),
+        ),
+        Some(0) => Err(StatusCode::BAD_REQUEST),
+        Some(page) => {
+            let offset = page_offset(page).ok_or(StatusCode::BAD_REQUEST)?;
+            let announces = storage
+                .list_announces_page(note.id, PAGE_SIZE, offset)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            activity_json(
+                StatusCode::OK,
+                &build_shares_page(base_url, &note, &author, page, total_items, &announces),
+            )
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use super::{PageQuery, activity_json};
+use crate::app::object_builders::activity_pub::collection::{PAGE_SIZE, page_offset};
+use crate::app::object_builders::activity_pub::tag::{
+    build_tag_collection, build_tag_collection_page,
+};
+use crate::app::state::AppState;
+use crate::domain::repositories::NoteTagsRepository;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Response,
+};
+
+/// Tags nothing is tagged with are served empty rather than missing.
+pub async fn get(
+    Path(name): Path<String>,
+    Query(query): Query<PageQuery>,
+    State(state): State<AppState>,
+) -> Result<Response, StatusCode> {
+    let storage = &state.storage;
+    let name = name.to_lowercase();
+    let total_items = storage
+        .count_tagged_notes(&name)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let base_url = &state.config.base_url;
+
+    match query.page {
+        None => activity_json(
+            StatusCode::OK,
+            &build_tag_collection(base_url, &name, total_items),
+        ),
+        Some(0) => Err(StatusCode::BAD_REQUEST),
+        Some(page) => {
+            let offset = page_offset(page).ok_or(StatusCode::BAD_REQUEST)?;
+            let tagged = storage
+                .list_tagged_notes_page(&name, PAGE_SIZE, offset)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            activity_json(
+                StatusCode::OK,
+                &build_tag_collection_page(base_url, &name, page, total_items, &tagged),
+            )
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// Management API for the people running this instance.
+// Every request must carry `Authorization: Bearer <API_TOKEN>`.
+
+pub mod conversations;
+pub mod follow_requests;
+pub mod following;
+pub mod liked;
+pub mod media;
+pub mod mentions;
+pub mod notes;
+pub mod users;
+
+use crate::config::Config;
+use axum::http::{HeaderMap, StatusCode, header};
+
+pub fn authorize(config: &Config, headers: &HeaderMap) -> Result<(), StatusCode> {
+    let expected = config
+        .api_token
+        .as_deref()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if provided.as_bytes() == expected.as_bytes() {
+        Ok(())
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
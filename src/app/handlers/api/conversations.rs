@@ -0,0 +1,110 @@
+use super::authorize;
+use crate::app::state::AppState;
+use crate::app::threads::{self, ThreadNote};
+use crate::domain::entities::{conversations, users};
+use crate::domain::repositories::{ConversationsRepository, UsersRepository};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+const LIST_LIMIT: u64 = 100;
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/conversations"
+}
+
+pub fn item_endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/conversations/{id}"
+}
+
+#[derive(Serialize)]
+pub struct ConversationView {
+    pub id: i64,
+    pub participants: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Serialize)]
+pub struct Conversation {
+    #[serde(flatten)]
+    pub conversation: ConversationView,
+    pub messages: Vec<ThreadNote>,
+}
+
+pub async fn list(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<ConversationView>>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let conversations = state
+        .storage
+        .list_conversations_page(user.id, LIST_LIMIT, 0)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(
+        conversations.into_iter().map(conversation_view).collect(),
+    ))
+}
+
+pub async fn get(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Conversation>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+    let storage = &state.storage;
+
+    let conversation = storage
+        .find_conversation(user.id, id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let messages = storage
+        .list_conversation_messages(conversation.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let base_url = &state.config.base_url;
+    let mut notes = Vec::with_capacity(messages.len());
+    for message in messages {
+        if let Some(note) = threads::find(storage, base_url, &message.note)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        {
+            notes.push(note);
+        }
+    }
+
+    Ok(Json(Conversation {
+        conversation: conversation_view(conversation),
+        messages: notes,
+    }))
+}
+
+async fn find_user(state: &AppState, username: &str) -> Result<users::Model, StatusCode> {
+    state
+        .storage
+        .find_user_by_username(username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+fn conversation_view(conversation: conversations::Model) -> ConversationView {
+    ConversationView {
+        id: conversation.id,
+        participants: conversation.participants,
+        created_at: conversation.created_at,
+        updated_at: conversation.updated_at,
+    }
+}
@@ -0,0 +1,148 @@
+use super::authorize;
+use crate::app::jobs::delivery;
+use crate::app::object_builders::activity_pub::{
+    accept::build_accept_follow, reject::build_reject_follow,
+};
+use crate::app::state::AppState;
+use crate::domain::entities::{follows, users};
+use crate::domain::repositories::{FollowsRepository, UsersRepository};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/follow_requests"
+}
+
+pub fn accept_endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/follow_requests/{id}/accept"
+}
+
+pub fn reject_endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/follow_requests/{id}/reject"
+}
+
+#[derive(Serialize)]
+pub struct FollowRequest {
+    pub id: i64,
+    pub actor: String,
+    pub activity_id: String,
+    pub created_at: NaiveDateTime,
+}
+
+pub async fn list(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<FollowRequest>>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let requests = state
+        .storage
+        .list_follow_requests(user.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(
+        requests
+            .into_iter()
+            .map(|request| FollowRequest {
+                id: request.id,
+                actor: request.actor,
+                activity_id: request.activity_id,
+                created_at: request.created_at,
+            })
+            .collect(),
+    ))
+}
+
+pub async fn accept(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+    let request = find_request(&state, &user, id).await?;
+
+    state
+        .storage
+        .approve_follow(request.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let accept = build_accept_follow(&state.config.base_url, &user, &request);
+    if let Err(err) = delivery::enqueue_to_actor(
+        &state.storage,
+        &state.actors,
+        &user,
+        &request.actor,
+        &accept,
+    )
+    .await
+    {
+        eprintln!("Cannot send Accept to {}: {}", request.actor, err);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn reject(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+    let request = find_request(&state, &user, id).await?;
+
+    state
+        .storage
+        .remove_follow_by_id(request.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let reject = build_reject_follow(&state.config.base_url, &user, &request);
+    if let Err(err) = delivery::enqueue_to_actor(
+        &state.storage,
+        &state.actors,
+        &user,
+        &request.actor,
+        &reject,
+    )
+    .await
+    {
+        eprintln!("Cannot send Reject to {}: {}", request.actor, err);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find_user(state: &AppState, username: &str) -> Result<users::Model, StatusCode> {
+    state
+        .storage
+        .find_user_by_username(username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// A pending follow of `user`; anything else is reported as missing.
+async fn find_request(
+    state: &AppState,
+    user: &users::Model,
+    id: i64,
+) -> Result<follows::Model, StatusCode> {
+    state
+        .storage
+        .find_follow_by_id(id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .filter(|follow| follow.user_id == user.id && follow.pending)
+        .ok_or(StatusCode::NOT_FOUND)
+}
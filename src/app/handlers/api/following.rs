@@ -0,0 +1,153 @@
+use super::authorize;
+use crate::app::jobs::delivery;
+use crate::app::object_builders::activity_pub::{
+    follow::{build_follow, new_follow_activity_id},
+    undo::build_undo_follow,
+};
+use crate::app::state::AppState;
+use crate::domain::entities::{following, users};
+use crate::domain::repositories::{FollowingRepository, UsersRepository};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/following"
+}
+
+pub fn item_endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/following/{id}"
+}
+
+#[derive(Serialize)]
+pub struct FollowedActor {
+    pub id: i64,
+    pub actor: String,
+    pub activity_id: String,
+    pub pending: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize)]
+pub struct FollowRequest {
+    pub actor: String,
+}
+
+pub async fn list(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<FollowedActor>>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let following = state
+        .storage
+        .list_following(user.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(following.into_iter().map(followed_actor).collect()))
+}
+
+/// Sends a Follow to a remote actor. The follow stays pending until it is accepted.
+pub async fn follow(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<FollowRequest>,
+) -> Result<(StatusCode, Json<FollowedActor>), StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let existing = state
+        .storage
+        .find_following(user.id, &request.actor)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if let Some(existing) = existing {
+        return Ok((StatusCode::OK, Json(followed_actor(existing))));
+    }
+
+    let actor = state
+        .actors
+        .resolve(&state.storage, &request.actor)
+        .await
+        .map_err(|err| {
+            eprintln!("Cannot follow {}: {}", request.actor, err);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    let activity_id = new_follow_activity_id(&state.config.base_url, &user);
+    let record = state
+        .storage
+        .add_following(user.id, &request.actor, &activity_id)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to persist following: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let follow = build_follow(&state.config.base_url, &user, &record);
+    delivery::enqueue(&state.storage, &user, &[actor.inbox], &follow)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((StatusCode::ACCEPTED, Json(followed_actor(record))))
+}
+
+/// Stops following, or withdraws a pending follow, and tells the remote actor.
+pub async fn unfollow(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let record = state
+        .storage
+        .find_following_by_id(id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .filter(|record| record.user_id == user.id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    state
+        .storage
+        .remove_following(record.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let undo = build_undo_follow(&state.config.base_url, &user, &record);
+    if let Err(err) =
+        delivery::enqueue_to_actor(&state.storage, &state.actors, &user, &record.actor, &undo).await
+    {
+        eprintln!("Cannot send Undo to {}: {}", record.actor, err);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find_user(state: &AppState, username: &str) -> Result<users::Model, StatusCode> {
+    state
+        .storage
+        .find_user_by_username(username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+fn followed_actor(record: following::Model) -> FollowedActor {
+    FollowedActor {
+        id: record.id,
+        actor: record.actor,
+        activity_id: record.activity_id,
+        pending: record.pending,
+        created_at: record.created_at,
+    }
+}
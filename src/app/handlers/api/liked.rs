@@ -0,0 +1,173 @@
+use super::authorize;
+use crate::app::jobs::delivery;
+use crate::app::object_builders::activity_pub::{
+    like::{build_like, new_like_activity_id},
+    undo::build_undo_like,
+};
+use crate::app::state::AppState;
+use crate::domain::entities::{liked, users};
+use crate::domain::repositories::{LikedRepository, RemoteNotesRepository, UsersRepository};
+use crate::federation::note::{fetch_note, ids_of};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// How many likes `GET` returns; the `liked` collection pages through all of them.
+const LIST_LIMIT: u64 = 100;
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/liked"
+}
+
+pub fn item_endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/liked/{id}"
+}
+
+#[derive(Serialize)]
+pub struct LikedObject {
+    pub id: i64,
+    pub object: String,
+    pub actor: String,
+    pub activity_id: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize)]
+pub struct LikeRequest {
+    pub object: String,
+}
+
+pub async fn list(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<LikedObject>>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let liked = state
+        .storage
+        .list_liked_page(user.id, LIST_LIMIT, 0)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(liked.into_iter().map(liked_object).collect()))
+}
+
+/// Likes a remote object and sends the Like to its author.
+pub async fn like(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<LikeRequest>,
+) -> Result<(StatusCode, Json<LikedObject>), StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let existing = state
+        .storage
+        .find_liked(user.id, &request.object)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if let Some(existing) = existing {
+        return Ok((StatusCode::OK, Json(liked_object(existing))));
+    }
+
+    let author = find_author(&state, &request.object).await.map_err(|err| {
+        eprintln!("Cannot like {}: {}", request.object, err);
+        StatusCode::BAD_GATEWAY
+    })?;
+
+    let activity_id = new_like_activity_id(&state.config.base_url, &user);
+    let record = state
+        .storage
+        .add_liked(user.id, &request.object, &author, &activity_id)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to persist like: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let like = build_like(&state.config.base_url, &user, &record);
+    if let Err(err) =
+        delivery::enqueue_to_actor(&state.storage, &state.actors, &user, &author, &like).await
+    {
+        eprintln!("Cannot send Like to {}: {}", author, err);
+    }
+
+    Ok((StatusCode::ACCEPTED, Json(liked_object(record))))
+}
+
+/// Takes back a like and tells the author of the object.
+pub async fn unlike(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let record = state
+        .storage
+        .find_liked_by_id(id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .filter(|record| record.user_id == user.id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    state
+        .storage
+        .remove_liked(record.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let undo = build_undo_like(&state.config.base_url, &user, &record);
+    if let Err(err) =
+        delivery::enqueue_to_actor(&state.storage, &state.actors, &user, &record.actor, &undo).await
+    {
+        eprintln!("Cannot send Undo to {}: {}", record.actor, err);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The author of `object`, from our copy of the note or from the note itself.
+async fn find_author(state: &AppState, object: &str) -> Result<String, String> {
+    let stored = state
+        .storage
+        .find_remote_note_by_ap_id(object)
+        .await
+        .map_err(|e| format!("Failed to look up {}: {}", object, e))?;
+    if let Some(note) = stored {
+        return Ok(note.actor);
+    }
+
+    let note = fetch_note(&state.http_client, object).await?;
+    note.attributed_to
+        .as_deref()
+        .and_then(|attributed_to| ids_of(attributed_to).into_iter().next())
+        .ok_or_else(|| format!("{} has no attributedTo", object))
+}
+
+async fn find_user(state: &AppState, username: &str) -> Result<users::Model, StatusCode> {
+    state
+        .storage
+        .find_user_by_username(username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+fn liked_object(record: liked::Model) -> LikedObject {
+    LikedObject {
+        id: record.id,
+        object: record.object,
+        actor: record.actor,
+        activity_id: record.activity_id,
+        created_at: record.created_at,
+    }
+}
@@ -0,0 +1,110 @@
+use super::authorize;
+use crate::app::media::{self, UploadError};
+use crate::app::object_builders::activity_pub::attachment;
+use crate::app::state::AppState;
+use crate::domain::repositories::UsersRepository;
+use axum::{
+    Json,
+    extract::{Multipart, Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use serde::Serialize;
+
+pub const MAX_UPLOAD_SIZE: usize = 40 * 1024 * 1024;
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/media"
+}
+
+#[derive(Serialize)]
+pub struct MediaView {
+    pub id: i64,
+    pub url: String,
+    pub media_type: String,
+    pub size: Option<i64>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub description: Option<String>,
+    pub focal_point: Option<[f64; 2]>,
+}
+
+/// `focus` is the point to keep in view when cropping, written `x,y` from -1.0 to 1.0.
+pub async fn upload(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<MediaView>), StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = state
+        .storage
+        .find_user_by_username(&username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut file = None;
+    let mut description = None;
+    let mut focal_point = None;
+    while let Some(field) = multipart.next_field().await.map_err(|err| err.status())? {
+        match field.name() {
+            Some("file") => {
+                let media_type = field.content_type().unwrap_or_default().to_string();
+                let bytes = field.bytes().await.map_err(|err| err.status())?;
+                file = Some((media_type, bytes));
+            }
+            Some("description") => {
+                let text = field.text().await.map_err(|err| err.status())?;
+                description = Some(text).filter(|text| !text.trim().is_empty());
+            }
+            Some("focus") => {
+                let text = field.text().await.map_err(|err| err.status())?;
+                focal_point = Some(parse_focus(&text).ok_or(StatusCode::UNPROCESSABLE_ENTITY)?);
+            }
+            _ => {}
+        }
+    }
+    let (media_type, bytes) = file.ok_or(StatusCode::UNPROCESSABLE_ENTITY)?;
+
+    let stored = media::store_upload(
+        &state.storage,
+        &state.config,
+        user.id,
+        &media_type,
+        &bytes,
+        description.as_deref(),
+        focal_point,
+    )
+    .await
+    .map_err(|err| match err {
+        UploadError::UnsupportedType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        UploadError::Unreadable => StatusCode::UNPROCESSABLE_ENTITY,
+        UploadError::Failed(err) => {
+            eprintln!("Failed to store upload of {}: {}", username, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(MediaView {
+            id: stored.id,
+            url: attachment::endpoint_uri(&state.config.base_url, &stored),
+            focal_point: attachment::focal_point(&stored),
+            media_type: stored.media_type,
+            size: stored.size,
+            width: stored.width,
+            height: stored.height,
+            description: stored.description,
+        }),
+    ))
+}
+
+fn parse_focus(text: &str) -> Option<[f64; 2]> {
+    let (x, y) = text.split_once(',')?;
+    let point = [x.trim().parse::<f64>().ok()?, y.trim().parse::<f64>().ok()?];
+    point
+        .iter()
+        .all(|axis| (-1.0..=1.0).contains(axis))
+        .then_some(point)
+}
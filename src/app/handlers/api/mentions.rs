@@ -0,0 +1,60 @@
+use super::authorize;
+use crate::app::state::AppState;
+use crate::domain::entities::{mentions, users};
+use crate::domain::repositories::{MentionsRepository, UsersRepository};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+const LIST_LIMIT: u64 = 100;
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/mentions"
+}
+
+#[derive(Serialize)]
+pub struct MentionView {
+    pub id: i64,
+    pub note: String,
+    pub actor: String,
+    pub created_at: NaiveDateTime,
+}
+
+pub async fn list(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<MentionView>>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+
+    let mentions = state
+        .storage
+        .list_mentions_page(user.id, LIST_LIMIT, 0)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(mentions.into_iter().map(mention_view).collect()))
+}
+
+async fn find_user(state: &AppState, username: &str) -> Result<users::Model, StatusCode> {
+    state
+        .storage
+        .find_user_by_username(username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+fn mention_view(mention: mentions::Model) -> MentionView {
+    MentionView {
+        id: mention.id,
+        note: mention.note,
+        actor: mention.actor,
+        created_at: mention.created_at,
+    }
+}
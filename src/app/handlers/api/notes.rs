@@ -0,0 +1,654 @@
+use super::authorize;
+use crate::app::conversations;
+use crate::app::hashtags;
+use crate::app::jobs::delivery;
+use crate::app::mentions;
+use crate::app::object_builders::activity_pub::{
+    create::build_create_activity,
+    delete::build_delete_note,
+    note::{self, NoteDetails},
+    person,
+    update::build_update_note,
+};
+use crate::app::state::AppState;
+use crate::app::threads::{self, ThreadNote};
+use crate::domain::entities::{note_mentions, note_tags, notes, users};
+use crate::domain::repositories::{
+    FollowsRepository, MediaRepository, NoteAnnouncesRepository, NoteLikesRepository,
+    NoteMentionsRepository, NoteRevisionsRepository, NoteTagsRepository, NotesRepository,
+    RemoteNotesRepository, UsersRepository, notes::NoteRelations,
+};
+use crate::domain::visibility::Visibility;
+use crate::federation::note::{fetch_note, ids_of};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use calmi_activity_streams::types::PUBLIC;
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::{ActiveValue, IntoActiveModel};
+use serde::{Deserialize, Serialize};
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/notes"
+}
+
+pub fn item_endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/notes/{id}"
+}
+
+pub fn revisions_endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/notes/{id}/revisions"
+}
+
+pub fn thread_endpoint_uri_template() -> &'static str {
+    "/api/users/{username}/notes/{id}/thread"
+}
+
+#[derive(Serialize)]
+pub struct NoteView {
+    pub id: i64,
+    pub summary: Option<String>,
+    pub content: String,
+    pub sensitive: bool,
+    pub to: Vec<String>,
+    pub visibility: Visibility,
+    pub in_reply_to: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+pub struct NewNote {
+    /// The content warning, shown in place of the content until the reader asks for it.
+    pub summary: Option<String>,
+    pub content: String,
+    /// Hides the content and media by default. Notes with a content warning are always sensitive.
+    #[serde(default)]
+    pub sensitive: bool,
+    #[serde(default)]
+    pub to: Vec<String>,
+    #[serde(default)]
+    pub visibility: Visibility,
+    pub in_reply_to: Option<String>,
+    #[serde(default)]
+    pub media_ids: Vec<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateNote {
+    pub summary: Option<String>,
+    pub content: String,
+    #[serde(default)]
+    pub sensitive: bool,
+}
+
+/// One version of a note, as it read from `created_at` on.
+#[derive(Serialize)]
+pub struct NoteRevision {
+    pub summary: Option<String>,
+    pub content: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize)]
+pub struct Thread {
+    pub ancestors: Vec<ThreadNote>,
+    pub note: ThreadNote,
+    pub descendants: Vec<ThreadNote>,
+}
+
+struct Parent {
+    context: String,
+    author: String,
+}
+
+pub async fn create(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(new_note): Json<NewNote>,
+) -> Result<(StatusCode, Json<NoteView>), StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+    let base_url = &state.config.base_url;
+    check_attachable(&state, &user, &new_note.media_ids).await?;
+
+    let mentioned = mentions::resolve(
+        &state.storage,
+        &state.http_client,
+        &state.config,
+        &new_note.content,
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let content = hashtags::link(base_url, &mentions::link(&new_note.content, &mentioned));
+    let mut to = new_note.to;
+    let (summary, sensitive) = content_warning(new_note.summary.as_deref(), new_note.sensitive);
+    for mention in &mentioned {
+        if !to.contains(&mention.href) {
+            to.push(mention.href.clone());
+        }
+    }
+
+    let context = match &new_note.in_reply_to {
+        None => None,
+        Some(in_reply_to) => {
+            let parent = find_parent(&state, in_reply_to).await?;
+            if !to.contains(&parent.author) {
+                to.push(parent.author);
+            }
+            Some(parent.context)
+        }
+    };
+
+    let mentioned_users = mentions::local_users(
+        &state.storage,
+        base_url,
+        &mentioned
+            .iter()
+            .map(|mention| mention.href.clone())
+            .collect::<Vec<_>>(),
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let direct_messages = if new_note.visibility == Visibility::Direct {
+        conversations::local_direct_messages(
+            &state.storage,
+            base_url,
+            &user,
+            &to,
+            new_note.in_reply_to.as_deref(),
+            context.as_deref(),
+        )
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    } else {
+        Vec::new()
+    };
+    let relations = NoteRelations {
+        author: person::endpoint_uri(base_url, &user),
+        mentions: mentioned
+            .into_iter()
+            .map(|mention| (mention.href, mention.name))
+            .collect(),
+        mentioned_users,
+        tags: hashtags::names(&new_note.content),
+        media_ids: new_note.media_ids,
+        direct_messages,
+    };
+
+    let note = notes::ActiveModel {
+        id: ActiveValue::NotSet,
+        content: ActiveValue::Set(content),
+        author_id: ActiveValue::Set(user.id),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+        to: ActiveValue::Set(to),
+        deleted_at: ActiveValue::Set(None),
+        updated_at: ActiveValue::Set(None),
+        in_reply_to: ActiveValue::Set(new_note.in_reply_to),
+        context: ActiveValue::Set(context),
+        visibility: ActiveValue::Set(new_note.visibility.as_str().to_string()),
+        summary: ActiveValue::Set(summary),
+        sensitive: ActiveValue::Set(sensitive),
+    };
+    let note = state
+        .storage
+        .add_note_with_relations(note, relations, &|id| note::id_uri(base_url, &user, id))
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to persist note: {}", err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let inboxes = audience_inboxes(&state, &user, &note).await?;
+    let details = note_details(&state, &note).await?;
+    let activity = build_create_activity(base_url, &note, &user, &details);
+    delivery::enqueue(&state.storage, &user, &inboxes, &activity)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((StatusCode::CREATED, Json(note_view(note))))
+}
+
+pub async fn thread(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Thread>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+    let note = find_note(&state, &user, id).await?;
+
+    let storage = &state.storage;
+    let base_url = &state.config.base_url;
+    let note = threads::local_note(base_url, note, &user);
+    let ancestors = threads::ancestors(storage, base_url, &note)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let descendants = threads::descendants(storage, base_url, &note)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let ancestors = threads::readable_by(storage, base_url, &user, ancestors)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let descendants = threads::readable_by(storage, base_url, &user, descendants)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(Thread {
+        ancestors,
+        note,
+        descendants,
+    }))
+}
+
+/// Edits a note, keeping the previous version, and sends the new one to its audience.
+pub async fn patch(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(update): Json<UpdateNote>,
+) -> Result<Json<NoteView>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+    let note = find_note(&state, &user, id).await?;
+    let base_url = &state.config.base_url;
+    let mentioned = mentions::resolve(
+        &state.storage,
+        &state.http_client,
+        &state.config,
+        &update.content,
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let content = hashtags::link(base_url, &mentions::link(&update.content, &mentioned));
+    let (summary, sensitive) = content_warning(update.summary.as_deref(), update.sensitive);
+    if note.content == content && note.summary == summary && note.sensitive == sensitive {
+        return Ok(Json(note_view(note)));
+    }
+
+    let mut to = note.to.clone();
+    for mention in &mentioned {
+        if !to.contains(&mention.href) {
+            to.push(mention.href.clone());
+        }
+    }
+    let mut model = note.into_active_model();
+    model.content = ActiveValue::Set(content);
+    model.summary = ActiveValue::Set(summary);
+    model.sensitive = ActiveValue::Set(sensitive);
+    model.to = ActiveValue::Set(to);
+    model.updated_at = ActiveValue::Set(Some(Utc::now().naive_utc()));
+    let note = state.storage.edit_note(model).await.map_err(|err| {
+        eprintln!("Failed to update note {}: {}", id, err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let inboxes = audience_inboxes(&state, &user, &note).await?;
+    let mentions = set_mentions(&state, &user, &note, &mentioned).await?;
+    let tags = set_tags(
+        &state,
+        &note,
+        &note::endpoint_uri(base_url, &note, &user),
+        &hashtags::names(&update.content),
+    )
+    .await?;
+    let media = state
+        .storage
+        .list_note_media(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let details = NoteDetails {
+        mentions,
+        tags,
+        media,
+    };
+    let activity = build_update_note(base_url, &note, &user, &details);
+    delivery::enqueue(&state.storage, &user, &inboxes, &activity)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(note_view(note)))
+}
+
+/// Every version of a note, oldest first, ending with the current one.
+/// Deleted notes keep their earlier versions.
+pub async fn revisions(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<NoteRevision>>, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+    let note = state
+        .storage
+        .find_note_by_id(id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .filter(|note| note.author_id == user.id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut revisions: Vec<NoteRevision> = state
+        .storage
+        .list_note_revisions(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|revision| NoteRevision {
+            summary: revision.summary,
+            content: revision.content,
+            created_at: revision.created_at,
+        })
+        .collect();
+    if note.deleted_at.is_none() {
+        revisions.push(NoteRevision {
+            created_at: note.updated_at.unwrap_or(note.created_at),
+            summary: note.summary,
+            content: note.content,
+        });
+    }
+
+    Ok(Json(revisions))
+}
+
+/// Deletes a note, leaving a tombstone, and tells everyone who may hold a copy.
+pub async fn delete(
+    Path((username, id)): Path<(String, i64)>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, StatusCode> {
+    authorize(&state.config, &headers)?;
+    let user = find_user(&state, &username).await?;
+    let note = find_note(&state, &user, id).await?;
+
+    state
+        .storage
+        .delete_note(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let note = state
+        .storage
+        .find_note_by_id(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let base_url = &state.config.base_url;
+    set_tags(
+        &state,
+        &note,
+        &note::endpoint_uri(base_url, &note, &user),
+        &[],
+    )
+    .await?;
+
+    let inboxes = audience_inboxes(&state, &user, &note).await?;
+    let activity = build_delete_note(base_url, &note, &user);
+    delivery::enqueue(&state.storage, &user, &inboxes, &activity)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn audience_inboxes(
+    state: &AppState,
+    author: &users::Model,
+    note: &notes::Model,
+) -> Result<Vec<String>, StatusCode> {
+    let storage = &state.storage;
+    let base_url = &state.config.base_url;
+    let note_uri = note::endpoint_uri(base_url, note, author);
+
+    let mut actors: Vec<String> = note
+        .to
+        .iter()
+        .filter(|to| to.as_str() != PUBLIC && !to.starts_with(base_url.as_str()))
+        .cloned()
+        .collect();
+    let visibility = Visibility::of(note);
+    if visibility == Visibility::Direct {
+        return Ok(delivery::resolve_inboxes(storage, &state.actors, &actors).await);
+    }
+    actors.extend(
+        storage
+            .list_followers(author.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .into_iter()
+            .map(|follow| follow.actor),
+    );
+    if !visibility.is_readable_by_anyone() {
+        return Ok(delivery::resolve_inboxes(storage, &state.actors, &actors).await);
+    }
+    actors.extend(
+        storage
+            .list_likes(note.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .into_iter()
+            .map(|like| like.actor),
+    );
+    actors.extend(
+        storage
+            .list_announces(note.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .into_iter()
+            .map(|announce| announce.actor),
+    );
+    actors.extend(
+        storage
+            .list_remote_notes_in_reply_to(&note_uri)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .into_iter()
+            .map(|reply| reply.actor),
+    );
+
+    Ok(delivery::resolve_inboxes(storage, &state.actors, &actors).await)
+}
+
+async fn set_mentions(
+    state: &AppState,
+    author: &users::Model,
+    note: &notes::Model,
+    mentioned: &[mentions::Mention],
+) -> Result<Vec<note_mentions::Model>, StatusCode> {
+    let base_url = &state.config.base_url;
+    state
+        .storage
+        .remove_note_mentions(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut note_mentions = Vec::with_capacity(mentioned.len());
+    for mention in mentioned {
+        note_mentions.push(
+            state
+                .storage
+                .add_note_mention(note.id, &mention.href, &mention.name)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        );
+    }
+    mentions::record(
+        &state.storage,
+        base_url,
+        &note::endpoint_uri(base_url, note, author),
+        &person::endpoint_uri(base_url, author),
+        &note_mentions
+            .iter()
+            .map(|mention| mention.href.clone())
+            .collect::<Vec<_>>(),
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(note_mentions)
+}
+
+async fn set_tags(
+    state: &AppState,
+    note: &notes::Model,
+    note_uri: &str,
+    names: &[String],
+) -> Result<Vec<note_tags::Model>, StatusCode> {
+    state
+        .storage
+        .set_note_tags(note.id, note_uri, names)
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to index hashtags of note {}: {}", note.id, err);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    state
+        .storage
+        .list_note_tags(note.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Notes with a content warning are always sensitive.
+fn content_warning(summary: Option<&str>, sensitive: bool) -> (Option<String>, bool) {
+    let summary = summary
+        .map(str::trim)
+        .filter(|summary| !summary.is_empty())
+        .map(str::to_string);
+    let sensitive = sensitive || summary.is_some();
+    (summary, sensitive)
+}
+
+/// Images are not served until processed, so notes cannot carry them before then.
+async fn check_attachable(
+    state: &AppState,
+    author: &users::Model,
+    ids: &[i64],
+) -> Result<(), StatusCode> {
+    for &id in ids {
+        let media = state
+            .storage
+            .find_media_by_id(id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .filter(|media| media.user_id == Some(author.id) && media.note_id.is_none())
+            .ok_or(StatusCode::UNPROCESSABLE_ENTITY)?;
+        if media.media_type.starts_with("image/") && media.processed_at.is_none() {
+            return Err(StatusCode::CONFLICT);
+        }
+    }
+    Ok(())
+}
+
+async fn note_details(state: &AppState, note: &notes::Model) -> Result<NoteDetails, StatusCode> {
+    let storage = &state.storage;
+    Ok(NoteDetails {
+        mentions: storage
+            .list_note_mentions(note.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        tags: storage
+            .list_note_tags(note.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        media: storage
+            .list_note_media(note.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    })
+}
+
+async fn find_parent(state: &AppState, uri: &str) -> Result<Parent, StatusCode> {
+    let base_url = &state.config.base_url;
+    if let Some(id) = note::local_note_id(base_url, uri) {
+        let parent = state
+            .storage
+            .find_note_by_id(id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .filter(|parent| parent.deleted_at.is_none())
+            .ok_or(StatusCode::UNPROCESSABLE_ENTITY)?;
+        let author = state
+            .storage
+            .find_user_by_id(parent.author_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        if note::endpoint_uri(base_url, &parent, &author) != uri {
+            return Err(StatusCode::UNPROCESSABLE_ENTITY);
+        }
+        return Ok(Parent {
+            context: parent.context.unwrap_or_else(|| uri.to_string()),
+            author: person::endpoint_uri(base_url, &author),
+        });
+    }
+
+    let stored = state
+        .storage
+        .find_remote_note_by_ap_id(uri)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if let Some(parent) = stored {
+        return Ok(Parent {
+            context: parent.context.unwrap_or_else(|| uri.to_string()),
+            author: parent.actor,
+        });
+    }
+
+    let parent = fetch_note(&state.http_client, uri).await.map_err(|err| {
+        eprintln!("Cannot reply to {}: {}", uri, err);
+        StatusCode::BAD_GATEWAY
+    })?;
+    let author = parent
+        .attributed_to
+        .as_deref()
+        .and_then(|attributed_to| ids_of(attributed_to).into_iter().next())
+        .ok_or_else(|| {
+            eprintln!("Cannot reply to {}: it has no attributedTo", uri);
+            StatusCode::BAD_GATEWAY
+        })?;
+    Ok(Parent {
+        context: parent
+            .thread_context
+            .as_deref()
+            .and_then(|context| ids_of(context).into_iter().next())
+            .unwrap_or_else(|| uri.to_string()),
+        author,
+    })
+}
+
+async fn find_user(state: &AppState, username: &str) -> Result<users::Model, StatusCode> {
+    state
+        .storage
+        .find_user_by_username(username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn find_note(
+    state: &AppState,
+    author: &users::Model,
+    id: i64,
+) -> Result<notes::Model, StatusCode> {
+    state
+        .storage
+        .find_note_by_id(id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .filter(|note| note.author_id == author.id && note.deleted_at.is_none())
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+fn note_view(note: notes::Model) -> NoteView {
+    NoteView {
+        visibility: Visibility::of(&note),
+        id: note.id,
+        summary: note.summary,
+        content: note.content,
+        sensitive: note.sensitive,
+        to: note.to,
+        in_reply_to: note.in_reply_to,
+        created_at: note.created_at,
+        updated_at: note.updated_at,
+    }
+}
@@ -0,0 +1,92 @@
+use super::authorize;
+use crate::app::state::AppState;
+use crate::domain::entities::users;
+use crate::domain::repositories::UsersRepository;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use sea_orm::{ActiveValue, IntoActiveModel};
+use serde::{Deserialize, Serialize};
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/api/users/{username}"
+}
+
+#[derive(Serialize)]
+pub struct UserSettings {
+    pub username: String,
+    pub display_name: String,
+    pub manually_approves_followers: bool,
+    pub hide_followers: bool,
+}
+
+/// Fields left out of the request body are kept as they are.
+#[derive(Deserialize)]
+pub struct UpdateUserSettings {
+    pub display_name: Option<String>,
+    pub manually_approves_followers: Option<bool>,
+    /// Publish only the number of followers, not who they are.
+    pub hide_followers: Option<bool>,
+}
+
+pub async fn get(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<UserSettings>, StatusCode> {
+    authorize(&state.config, &headers)?;
+
+    let user = state
+        .storage
+        .find_user_by_username(&username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(settings(user)))
+}
+
+pub async fn patch(
+    Path(username): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(update): Json<UpdateUserSettings>,
+) -> Result<Json<UserSettings>, StatusCode> {
+    authorize(&state.config, &headers)?;
+
+    let user = state
+        .storage
+        .find_user_by_username(&username)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut model = user.into_active_model();
+    if let Some(display_name) = update.display_name {
+        model.display_name = ActiveValue::Set(display_name);
+    }
+    if let Some(manually_approves_followers) = update.manually_approves_followers {
+        model.manually_approves_followers = ActiveValue::Set(manually_approves_followers);
+    }
+    if let Some(hide_followers) = update.hide_followers {
+        model.hide_followers = ActiveValue::Set(hide_followers);
+    }
+
+    let user = state.storage.update_user(model).await.map_err(|err| {
+        eprintln!("Failed to update user {}: {}", username, err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(settings(user)))
+}
+
+fn settings(user: users::Model) -> UserSettings {
+    UserSettings {
+        username: user.username,
+        display_name: user.display_name,
+        manually_approves_followers: user.manually_approves_followers,
+        hide_followers: user.hide_followers,
+    }
+}
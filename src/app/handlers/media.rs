@@ -0,0 +1,45 @@
+use crate::app::media;
+use crate::app::state::AppState;
+use crate::domain::repositories::MediaRepository;
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::Response,
+};
+
+/// Only files recorded as uploads are served, so names cannot reach outside the media directory.
+/// Images wait until processing has stripped their metadata.
+pub async fn get(
+    Path(file_name): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Response, StatusCode> {
+    let media = state
+        .storage
+        .find_media_by_file_name(&file_name)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if media::is_image(&media) && media.processed_at.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let media_type = if media.thumbnail_file_name.as_deref() == Some(file_name.as_str()) {
+        "image/jpeg".to_string()
+    } else {
+        media.media_type
+    };
+
+    let bytes = tokio::fs::read(state.config.media_dir.join(&file_name))
+        .await
+        .map_err(|err| {
+            eprintln!("Failed to read media file {}: {}", file_name, err);
+            StatusCode::NOT_FOUND
+        })?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, media_type)
+        .header(header::CONTENT_LENGTH, bytes.len())
+        .body(Body::from(bytes))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
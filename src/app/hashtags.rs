@@ -0,0 +1,113 @@
+use crate::app::object_builders::activity_pub::tag;
+use crate::domain::entities::remote_notes;
+use crate::domain::repositories::NoteTagsRepository;
+use calmi_activity_streams::types::PUBLIC;
+use calmi_activity_streams::types::enums::{SingleOrMultiple, TagEntry};
+use calmi_activity_streams::types::object::note::Note;
+use sea_orm::DbErr;
+use std::ops::Range;
+
+/// Names are kept without their `#` and in lowercase, so `#Rust` and `#rust` are one tag.
+pub fn names(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (_, name) in find(content) {
+        let name = name.to_lowercase();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+pub fn link(base_url: &str, content: &str) -> String {
+    let mut linked = String::with_capacity(content.len());
+    let mut copied = 0;
+    for (range, name) in find(content) {
+        linked.push_str(&content[copied..range.start]);
+        linked.push_str(&format!(
+            r#"<a href="{}" class="mention hashtag" rel="tag">#{}</a>"#,
+            tag::endpoint_uri(base_url, &name.to_lowercase()),
+            name
+        ));
+        copied = range.end;
+    }
+    linked.push_str(&content[copied..]);
+    linked
+}
+
+pub async fn index_remote<T: NoteTagsRepository>(
+    storage: &T,
+    stored: &remote_notes::Model,
+    note: &Note,
+) -> Result<(), DbErr> {
+    let names = if stored.to.iter().any(|to| to == PUBLIC) {
+        tagged_names(note)
+    } else {
+        Vec::new()
+    };
+    storage
+        .set_remote_note_tags(stored.id, &stored.ap_id, &names)
+        .await
+}
+
+fn tagged_names(note: &Note) -> Vec<String> {
+    let tags = match note.tag.as_deref() {
+        Some(SingleOrMultiple::Single(tag)) => std::slice::from_ref(tag),
+        Some(SingleOrMultiple::Multiple(tags)) => tags.as_slice(),
+        None => &[],
+    };
+    let mut names: Vec<String> = Vec::new();
+    for tag in tags {
+        let TagEntry::Hashtag(hashtag) = tag else {
+            continue;
+        };
+        let name = hashtag.name.strip_prefix('#').unwrap_or(&hashtag.name);
+        if !is_name(name) {
+            continue;
+        }
+        let name = name.to_lowercase();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// A hashtag starts a word, so fragments of URLs and character references are skipped.
+fn find(content: &str) -> Vec<(Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut previous = None;
+    let mut chars = content.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let starts_word = previous.is_none_or(|p: char| p.is_whitespace() || p == '(' || p == '>');
+        previous = Some(c);
+        if c != '#' || !starts_word {
+            continue;
+        }
+
+        let start = i + 1;
+        let mut end = start;
+        while let Some(&(j, c)) = chars.peek() {
+            if !is_name_char(c) {
+                break;
+            }
+            end = j + c.len_utf8();
+            previous = Some(c);
+            chars.next();
+        }
+        let name = &content[start..end];
+        if is_name(name) {
+            found.push((i..end, name));
+        }
+    }
+    found
+}
+
+/// Letters, digits and underscores, not only digits, so `#1` stays a number.
+fn is_name(name: &str) -> bool {
+    name.chars().all(is_name_char) && name.chars().any(|c| !c.is_numeric())
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
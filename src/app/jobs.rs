@@ -0,0 +1,88 @@
+// Background work, persisted in the `jobs` table so that it survives restarts.
+
+pub mod delivery;
+pub mod media_processing;
+
+use crate::app::state::AppState;
+use crate::domain::entities::jobs;
+use crate::domain::repositories::JobsRepository;
+use chrono::{Duration, Utc};
+use sea_orm::DbErr;
+
+const BATCH_SIZE: u64 = 16;
+/// How long a claimed job stays invisible to other workers.
+const LEASE_SECONDS: i64 = 5 * 60;
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+const BASE_RETRY_DELAY_SECONDS: i64 = 30;
+const MAX_RETRY_DELAY_SECONDS: i64 = 6 * 60 * 60;
+
+pub enum JobError {
+    /// Worth trying again later, e.g. the remote server is down.
+    Retryable(String),
+    /// Will never succeed, e.g. the remote server rejected the request.
+    Permanent(String),
+}
+
+/// Starts `count` workers polling for due jobs on the current tokio runtime.
+pub fn spawn_workers(state: AppState, count: usize) {
+    for _ in 0..count {
+        let state = state.clone();
+        tokio::spawn(async move {
+            loop {
+                match run_due_jobs(&state).await {
+                    Ok(0) => tokio::time::sleep(POLL_INTERVAL).await,
+                    Ok(_) => {}
+                    Err(err) => {
+                        eprintln!("Failed to run jobs: {}", err);
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Claims and runs one batch of due jobs, returning how many were claimed.
+pub async fn run_due_jobs(state: &AppState) -> Result<usize, DbErr> {
+    let storage = &state.storage;
+    let locked_until = Utc::now().naive_utc() + Duration::seconds(LEASE_SECONDS);
+    let due = storage.claim_due_jobs(BATCH_SIZE, locked_until).await?;
+
+    for job in &due {
+        match perform(state, job).await {
+            Ok(()) => storage.complete_job(job.id).await?,
+            Err(JobError::Permanent(err)) => {
+                eprintln!("Job {} ({}) failed permanently: {}", job.id, job.kind, err);
+                storage.fail_job(job.id, &err).await?;
+            }
+            Err(JobError::Retryable(err)) => {
+                let run_at = Utc::now().naive_utc() + retry_delay(job.attempts);
+                let horizon =
+                    Duration::from_std(state.config.job_retry_horizon).unwrap_or(Duration::MAX);
+                if run_at > job.created_at + horizon {
+                    eprintln!("Job {} ({}) gave up retrying: {}", job.id, job.kind, err);
+                    storage.fail_job(job.id, &err).await?;
+                } else {
+                    storage.retry_job(job.id, run_at, &err).await?;
+                }
+            }
+        }
+    }
+
+    Ok(due.len())
+}
+
+async fn perform(state: &AppState, job: &jobs::Model) -> Result<(), JobError> {
+    match job.kind.as_str() {
+        delivery::KIND => delivery::perform(state, &job.payload).await,
+        media_processing::KIND => media_processing::perform(state, &job.payload).await,
+        other => Err(JobError::Permanent(format!("Unknown job kind {}", other))),
+    }
+}
+
+/// Exponential backoff: 30s, 1m, 2m, ... capped at 6h.
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.clamp(0, 20) as u32;
+    let seconds = BASE_RETRY_DELAY_SECONDS.saturating_mul(2_i64.pow(exponent));
+    Duration::seconds(seconds.min(MAX_RETRY_DELAY_SECONDS))
+}
@@ -0,0 +1,151 @@
+// Delivers an activity to a single remote inbox with a signed POST.
+// https://www.w3.org/TR/activitypub/#delivery
+
+use super::JobError;
+use crate::app::object_builders::activity_pub::person::public_key_id;
+use crate::app::state::AppState;
+use crate::domain::entities::users::Model as User;
+use crate::domain::repositories::{JobsRepository, RemoteActorsRepository, UsersRepository};
+use crate::federation::actor::ActorResolver;
+use crate::federation::http_signature::sign_request;
+use axum::http::Method;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+pub const KIND: &str = "deliver";
+
+#[derive(Serialize, Deserialize)]
+pub struct DeliveryPayload {
+    pub sender_id: i64,
+    pub inbox: String,
+    pub activity: serde_json::Value,
+}
+
+/// Queues one delivery job per distinct inbox.
+pub async fn enqueue<T: JobsRepository, A: Serialize>(
+    storage: &T,
+    sender: &User,
+    inboxes: &[String],
+    activity: &A,
+) -> Result<(), DbErr> {
+    let activity = serde_json::to_value(activity).map_err(|e| DbErr::Custom(e.to_string()))?;
+    let inboxes: BTreeSet<&String> = inboxes.iter().collect();
+
+    for inbox in inboxes {
+        let payload = DeliveryPayload {
+            sender_id: sender.id,
+            inbox: inbox.clone(),
+            activity: activity.clone(),
+        };
+        let payload = serde_json::to_value(&payload).map_err(|e| DbErr::Custom(e.to_string()))?;
+        storage.enqueue_job(KIND, payload).await?;
+    }
+    Ok(())
+}
+
+/// Looks up a remote actor's inbox and queues `activity` for it.
+pub async fn enqueue_to_actor<T: JobsRepository + RemoteActorsRepository, A: Serialize>(
+    storage: &T,
+    actors: &ActorResolver,
+    sender: &User,
+    actor_id: &str,
+    activity: &A,
+) -> Result<(), String> {
+    let actor = actors.resolve(storage, actor_id).await?;
+    enqueue(storage, sender, &[actor.inbox], activity)
+        .await
+        .map_err(|e| format!("Failed to queue delivery to {}: {}", actor_id, e))
+}
+
+/// The inboxes to reach `actor_ids` at, preferring shared inboxes.
+/// Actors that cannot be resolved are skipped.
+pub async fn resolve_inboxes<T: RemoteActorsRepository>(
+    storage: &T,
+    actors: &ActorResolver,
+    actor_ids: &[String],
+) -> Vec<String> {
+    let actor_ids: BTreeSet<&String> = actor_ids.iter().collect();
+    let mut inboxes = Vec::with_capacity(actor_ids.len());
+    for actor_id in actor_ids {
+        match actors.resolve(storage, actor_id).await {
+            Ok(actor) => inboxes.push(actor.shared_inbox.unwrap_or(actor.inbox)),
+            Err(err) => eprintln!("Skipping delivery to {}: {}", actor_id, err),
+        }
+    }
+    inboxes
+}
+
+pub async fn perform(state: &AppState, payload: &serde_json::Value) -> Result<(), JobError> {
+    let payload: DeliveryPayload = serde_json::from_value(payload.clone())
+        .map_err(|e| JobError::Permanent(format!("Malformed delivery payload: {}", e)))?;
+
+    let sender = UsersRepository::find_user_by_id(&state.storage, payload.sender_id)
+        .await
+        .map_err(|e| JobError::Retryable(e.to_string()))?
+        .ok_or_else(|| JobError::Permanent(format!("Unknown sender {}", payload.sender_id)))?;
+    let private_key_pem = sender.private_key_pem.as_deref().ok_or_else(|| {
+        JobError::Permanent(format!("User {} has no signing key", sender.username))
+    })?;
+
+    let url = reqwest::Url::parse(&payload.inbox)
+        .map_err(|e| JobError::Permanent(format!("Invalid inbox {}: {}", payload.inbox, e)))?;
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host, port),
+        (Some(host), None) => host.to_string(),
+        (None, _) => {
+            return Err(JobError::Permanent(format!(
+                "Inbox {} has no host",
+                payload.inbox
+            )));
+        }
+    };
+    let path_and_query = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+
+    let body = serde_json::to_vec(&payload.activity)
+        .map_err(|e| JobError::Permanent(format!("Failed to serialize activity: {}", e)))?;
+    let signed = sign_request(
+        &Method::POST,
+        &path_and_query,
+        &host,
+        Some(&body),
+        &public_key_id(&state.config.base_url, &sender),
+        private_key_pem,
+    )
+    .map_err(JobError::Permanent)?;
+
+    let mut request = state
+        .http_client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/activity+json")
+        .header(reqwest::header::DATE, signed.date)
+        .header("Signature", signed.signature);
+    if let Some(digest) = signed.digest {
+        request = request.header("Digest", digest);
+    }
+
+    let response = request
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| JobError::Retryable(format!("POST {} failed: {}", payload.inbox, e)))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let err = format!("POST {} returned {}", payload.inbox, status);
+    // Client errors mean the remote will never take this activity, except for throttling.
+    if status.is_client_error()
+        && status != reqwest::StatusCode::REQUEST_TIMEOUT
+        && status != reqwest::StatusCode::TOO_MANY_REQUESTS
+    {
+        Err(JobError::Permanent(err))
+    } else {
+        Err(JobError::Retryable(err))
+    }
+}
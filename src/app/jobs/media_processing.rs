@@ -0,0 +1,218 @@
+use super::JobError;
+use crate::app::state::AppState;
+use crate::domain::repositories::{JobsRepository, MediaRepository};
+use chrono::Utc;
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use sea_orm::{ActiveValue, DbErr, IntoActiveModel};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::Path;
+
+pub const KIND: &str = "process_media";
+
+const THUMBNAIL_SIZE: u32 = 400;
+const THUMBNAIL_QUALITY: u8 = 80;
+const REENCODE_QUALITY: u8 = 90;
+const BLURHASH_SOURCE_SIZE: u32 = 64;
+const BLURHASH_COMPONENTS: (u32, u32) = (4, 4);
+
+#[derive(Serialize, Deserialize)]
+pub struct MediaProcessingPayload {
+    pub media_id: i64,
+}
+
+pub async fn enqueue<T: JobsRepository>(storage: &T, media_id: i64) -> Result<(), DbErr> {
+    let payload = serde_json::to_value(MediaProcessingPayload { media_id })
+        .map_err(|e| DbErr::Custom(e.to_string()))?;
+    storage.enqueue_job(KIND, payload).await?;
+    Ok(())
+}
+
+pub async fn perform(state: &AppState, payload: &serde_json::Value) -> Result<(), JobError> {
+    let payload: MediaProcessingPayload = serde_json::from_value(payload.clone())
+        .map_err(|e| JobError::Permanent(format!("Malformed media processing payload: {}", e)))?;
+
+    let storage = &state.storage;
+    let Some(media) = storage
+        .find_media_by_id(payload.media_id)
+        .await
+        .map_err(|e| JobError::Retryable(e.to_string()))?
+    else {
+        // Removed before it was processed.
+        return Ok(());
+    };
+    if media.processed_at.is_some() {
+        return Ok(());
+    }
+    let file_name = media
+        .file_name
+        .clone()
+        .ok_or_else(|| JobError::Permanent(format!("Media {} is not an upload", media.id)))?;
+
+    let media_dir = &state.config.media_dir;
+    let bytes = tokio::fs::read(media_dir.join(&file_name))
+        .await
+        .map_err(|e| JobError::Retryable(format!("Cannot read {}: {}", file_name, e)))?;
+    let media_type = media.media_type.clone();
+    let processed = tokio::task::spawn_blocking(move || process(&bytes, &media_type))
+        .await
+        .map_err(|e| JobError::Retryable(format!("Processing {} panicked: {}", file_name, e)))?
+        .map_err(|e| JobError::Permanent(format!("Cannot process {}: {}", file_name, e)))?;
+
+    let thumbnail_file_name = format!(
+        "{}-small.jpg",
+        file_name.split('.').next().unwrap_or(&file_name)
+    );
+    write(media_dir, &thumbnail_file_name, &processed.thumbnail).await?;
+    write(media_dir, &file_name, &processed.bytes).await?;
+
+    let mut model = media.into_active_model();
+    model.size = ActiveValue::Set(Some(processed.bytes.len() as i64));
+    model.width = ActiveValue::Set(Some(processed.width));
+    model.height = ActiveValue::Set(Some(processed.height));
+    model.thumbnail_file_name = ActiveValue::Set(Some(thumbnail_file_name));
+    model.blurhash = ActiveValue::Set(Some(processed.blurhash));
+    model.processed_at = ActiveValue::Set(Some(Utc::now().naive_utc()));
+    storage
+        .update_media(model)
+        .await
+        .map_err(|e| JobError::Retryable(e.to_string()))?;
+    Ok(())
+}
+
+struct Processed {
+    bytes: Vec<u8>,
+    width: i32,
+    height: i32,
+    thumbnail: Vec<u8>,
+    blurhash: String,
+}
+
+/// The EXIF orientation is applied before encoding drops it with the rest of the EXIF.
+/// GIFs are not encoded again, which would lose their animation; see `strip_gif_metadata`.
+fn process(bytes: &[u8], media_type: &str) -> Result<Processed, String> {
+    let format = ImageFormat::from_mime_type(media_type)
+        .ok_or_else(|| format!("{} is not an image type", media_type))?;
+    let mut decoder = ImageReader::with_format(Cursor::new(bytes), format)
+        .into_decoder()
+        .map_err(|e| e.to_string())?;
+    let orientation = decoder.orientation().map_err(|e| e.to_string())?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|e| e.to_string())?;
+    image.apply_orientation(orientation);
+
+    let bytes = match format {
+        ImageFormat::Gif => strip_gif_metadata(bytes)?,
+        ImageFormat::Jpeg => encode_jpeg(&image, REENCODE_QUALITY)?,
+        _ => {
+            let mut encoded = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut encoded), format)
+                .map_err(|e| e.to_string())?;
+            encoded
+        }
+    };
+
+    let thumbnail = if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+        image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+    } else {
+        image.clone()
+    };
+    let small = image
+        .thumbnail(BLURHASH_SOURCE_SIZE, BLURHASH_SOURCE_SIZE)
+        .to_rgba8();
+    let (x_components, y_components) = BLURHASH_COMPONENTS;
+    let blurhash = blurhash::encode(
+        x_components,
+        y_components,
+        small.width(),
+        small.height(),
+        small.as_raw(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(Processed {
+        bytes,
+        width: image.width().try_into().map_err(|_| "Image too wide")?,
+        height: image.height().try_into().map_err(|_| "Image too tall")?,
+        thumbnail: encode_jpeg(&thumbnail, THUMBNAIL_QUALITY)?,
+        blurhash,
+    })
+}
+
+/// Drops the comment and application extensions of a GIF, where XMP and other metadata go,
+/// but keeps the application extension that makes animations loop.
+/// https://www.w3.org/Graphics/GIF/spec-gif89a.txt
+fn strip_gif_metadata(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let truncated = || "Truncated GIF".to_string();
+    let color_table_len = |flags: u8| {
+        if flags & 0x80 != 0 {
+            3 << ((flags & 0x07) + 1)
+        } else {
+            0
+        }
+    };
+
+    let header_len = 13 + color_table_len(*bytes.get(10).ok_or_else(truncated)?);
+    let mut stripped = bytes.get(..header_len).ok_or_else(truncated)?.to_vec();
+    let mut i = header_len;
+    loop {
+        match bytes.get(i) {
+            None | Some(0x3B) => {
+                stripped.push(0x3B);
+                return Ok(stripped);
+            }
+            Some(0x21) => {
+                let label = *bytes.get(i + 1).ok_or_else(truncated)?;
+                let end = sub_blocks_end(bytes, i + 2)?;
+                let keep = match label {
+                    0xFE => false,
+                    0xFF => bytes
+                        .get(i + 3..i + 14)
+                        .is_some_and(|id| id == b"NETSCAPE2.0" || id == b"ANIMEXTS1.0"),
+                    _ => true,
+                };
+                if keep {
+                    stripped.extend_from_slice(&bytes[i..end]);
+                }
+                i = end;
+            }
+            Some(0x2C) => {
+                let flags = *bytes.get(i + 9).ok_or_else(truncated)?;
+                // The image data starts after its LZW minimum code size.
+                let end = sub_blocks_end(bytes, i + 10 + color_table_len(flags) + 1)?;
+                stripped.extend_from_slice(&bytes[i..end]);
+                i = end;
+            }
+            Some(other) => return Err(format!("Unexpected GIF block 0x{:02x}", other)),
+        }
+    }
+}
+
+fn sub_blocks_end(bytes: &[u8], mut i: usize) -> Result<usize, String> {
+    loop {
+        let size = *bytes.get(i).ok_or("Truncated GIF")? as usize;
+        i += 1 + size;
+        if size == 0 {
+            return Ok(i);
+        }
+    }
+}
+
+fn encode_jpeg(image: &DynamicImage, quality: u8) -> Result<Vec<u8>, String> {
+    let mut encoded = Vec::new();
+    DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_with_encoder(JpegEncoder::new_with_quality(&mut encoded, quality))
+        .map_err(|e| e.to_string())?;
+    Ok(encoded)
+}
+
+async fn write(media_dir: &Path, file_name: &str, bytes: &[u8]) -> Result<(), JobError> {
+    let partial = media_dir.join(format!("{}.partial", file_name));
+    tokio::fs::write(&partial, bytes)
+        .await
+        .map_err(|e| JobError::Retryable(format!("Cannot write {}: {}", file_name, e)))?;
+    tokio::fs::rename(&partial, media_dir.join(file_name))
+        .await
+        .map_err(|e| JobError::Retryable(format!("Cannot write {}: {}", file_name, e)))
+}
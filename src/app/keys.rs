@@ -0,0 +1,21 @@
+use crate::domain::repositories::UsersRepository;
+use crate::federation::keys;
+use sea_orm::{ActiveValue, DbErr, IntoActiveModel};
+
+/// Gives a keypair to the users created before keys were generated with each user.
+/// Returns how many users were given one.
+pub async fn backfill_user_keys<T: UsersRepository>(storage: &T) -> Result<usize, DbErr> {
+    let users = storage.list_users_without_keys().await?;
+    let count = users.len();
+    for user in users {
+        let keypair = tokio::task::spawn_blocking(keys::generate_keypair)
+            .await
+            .map_err(|e| DbErr::Custom(e.to_string()))?
+            .map_err(DbErr::Custom)?;
+        let mut user = user.into_active_model();
+        user.public_key_pem = ActiveValue::Set(Some(keypair.public_key_pem));
+        user.private_key_pem = ActiveValue::Set(Some(keypair.private_key_pem));
+        storage.update_user(user).await?;
+    }
+    Ok(count)
+}
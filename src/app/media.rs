@@ -0,0 +1,231 @@
+use crate::app::jobs::media_processing;
+use crate::config::Config;
+use crate::domain::entities::{media, remote_notes};
+use crate::domain::repositories::{JobsRepository, MediaRepository};
+use calmi_activity_streams::types::enums::{
+    LinkOrStringUrl, ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple,
+};
+use calmi_activity_streams::types::object::{
+    document::Document, image::Image, note::Note, video::Video,
+};
+use chrono::Utc;
+use sea_orm::{ActiveValue, DbErr};
+use std::io::Cursor;
+
+const ACCEPTED_TYPES: &[(&str, &str)] = &[
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+    ("video/mp4", "mp4"),
+    ("video/webm", "webm"),
+    ("audio/mpeg", "mp3"),
+    ("audio/ogg", "ogg"),
+];
+
+pub enum UploadError {
+    UnsupportedType,
+    Unreadable,
+    Failed(String),
+}
+
+pub async fn store_upload<T: MediaRepository + JobsRepository>(
+    storage: &T,
+    config: &Config,
+    user_id: i64,
+    media_type: &str,
+    bytes: &[u8],
+    description: Option<&str>,
+    focal_point: Option<[f64; 2]>,
+) -> Result<media::Model, UploadError> {
+    let Some((_, extension)) = ACCEPTED_TYPES
+        .iter()
+        .find(|(accepted, _)| *accepted == media_type)
+    else {
+        return Err(UploadError::UnsupportedType);
+    };
+    let (width, height) = if media_type.starts_with("image/") {
+        let (width, height) = dimensions(bytes).ok_or(UploadError::Unreadable)?;
+        (Some(width), Some(height))
+    } else {
+        (None, None)
+    };
+
+    let file_name = format!("{}.{}", uuid::Uuid::new_v4().simple(), extension);
+    tokio::fs::create_dir_all(&config.media_dir)
+        .await
+        .map_err(|err| UploadError::Failed(format!("Cannot create media directory: {}", err)))?;
+    tokio::fs::write(config.media_dir.join(&file_name), bytes)
+        .await
+        .map_err(|err| UploadError::Failed(format!("Cannot write {}: {}", file_name, err)))?;
+
+    let stored = storage
+        .add_media(media::ActiveModel {
+            id: ActiveValue::NotSet,
+            user_id: ActiveValue::Set(Some(user_id)),
+            note_id: ActiveValue::Set(None),
+            remote_note_id: ActiveValue::Set(None),
+            file_name: ActiveValue::Set(Some(file_name.clone())),
+            remote_url: ActiveValue::Set(None),
+            media_type: ActiveValue::Set(media_type.to_string()),
+            size: ActiveValue::Set(Some(bytes.len() as i64)),
+            width: ActiveValue::Set(width),
+            height: ActiveValue::Set(height),
+            description: ActiveValue::Set(description.map(str::to_string)),
+            created_at: ActiveValue::Set(Utc::now().naive_utc()),
+            thumbnail_file_name: ActiveValue::Set(None),
+            blurhash: ActiveValue::Set(None),
+            focal_x: ActiveValue::Set(focal_point.map(|[x, _]| x)),
+            focal_y: ActiveValue::Set(focal_point.map(|[_, y]| y)),
+            processed_at: ActiveValue::Set(None),
+        })
+        .await
+        .map_err(|err| UploadError::Failed(format!("Cannot record {}: {}", file_name, err)))?;
+    if is_image(&stored) {
+        media_processing::enqueue(storage, stored.id)
+            .await
+            .map_err(|err| {
+                UploadError::Failed(format!("Cannot queue processing of {}: {}", file_name, err))
+            })?;
+    }
+    Ok(stored)
+}
+
+pub fn is_image(media: &media::Model) -> bool {
+    media.media_type.starts_with("image/")
+}
+
+fn dimensions(bytes: &[u8]) -> Option<(i32, i32)> {
+    let (width, height) = image::ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()?;
+    Some((width.try_into().ok()?, height.try_into().ok()?))
+}
+
+pub async fn record_remote<T: MediaRepository>(
+    storage: &T,
+    stored: &remote_notes::Model,
+    note: &Note,
+) -> Result<(), DbErr> {
+    let attachments = match note.attachment.as_deref() {
+        Some(SingleOrMultiple::Single(attachment)) => std::slice::from_ref(attachment),
+        Some(SingleOrMultiple::Multiple(attachments)) => attachments.as_slice(),
+        None => &[],
+    };
+    let now = Utc::now().naive_utc();
+    let media = attachments
+        .iter()
+        .filter_map(remote_attachment)
+        .map(|attachment| media::ActiveModel {
+            id: ActiveValue::NotSet,
+            user_id: ActiveValue::Set(None),
+            note_id: ActiveValue::Set(None),
+            remote_note_id: ActiveValue::Set(Some(stored.id)),
+            file_name: ActiveValue::Set(None),
+            remote_url: ActiveValue::Set(Some(attachment.url)),
+            media_type: ActiveValue::Set(attachment.media_type),
+            size: ActiveValue::Set(None),
+            width: ActiveValue::Set(attachment.width),
+            height: ActiveValue::Set(attachment.height),
+            description: ActiveValue::Set(attachment.description),
+            created_at: ActiveValue::Set(now),
+            thumbnail_file_name: ActiveValue::Set(None),
+            blurhash: ActiveValue::Set(attachment.blurhash),
+            focal_x: ActiveValue::Set(attachment.focal_point.map(|[x, _]| x)),
+            focal_y: ActiveValue::Set(attachment.focal_point.map(|[_, y]| y)),
+            processed_at: ActiveValue::Set(None),
+        })
+        .collect();
+    storage.set_remote_note_media(stored.id, media).await
+}
+
+struct RemoteAttachment {
+    url: String,
+    media_type: String,
+    description: Option<String>,
+    width: Option<i32>,
+    height: Option<i32>,
+    blurhash: Option<String>,
+    focal_point: Option<[f64; 2]>,
+}
+
+fn remote_attachment(attachment: &ObjectOrLinkOrStringUrl) -> Option<RemoteAttachment> {
+    let mut kept = match attachment {
+        ObjectOrLinkOrStringUrl::Object(
+            ObjectBased::Document(Document {
+                url,
+                media_type,
+                name,
+                width,
+                height,
+                blurhash,
+                focal_point,
+                ..
+            })
+            | ObjectBased::Image(Image {
+                url,
+                media_type,
+                name,
+                width,
+                height,
+                blurhash,
+                focal_point,
+                ..
+            })
+            | ObjectBased::Video(Video {
+                url,
+                media_type,
+                name,
+                width,
+                height,
+                blurhash,
+                focal_point,
+                ..
+            }),
+        ) => {
+            let url = match url.as_deref()? {
+                SingleOrMultiple::Single(url) => url,
+                SingleOrMultiple::Multiple(urls) => urls.first()?,
+            };
+            // A link may tell the media type the object leaves out.
+            let (url, link_media_type) = match url {
+                LinkOrStringUrl::Str(url) => (url.clone(), None),
+                LinkOrStringUrl::Link(link) => (link.href.clone()?, link.media_type.clone()),
+            };
+            RemoteAttachment {
+                url,
+                media_type: media_type.clone().or(link_media_type).unwrap_or_default(),
+                description: name.clone(),
+                width: width.and_then(|width| width.try_into().ok()),
+                height: height.and_then(|height| height.try_into().ok()),
+                blurhash: blurhash.clone(),
+                focal_point: *focal_point,
+            }
+        }
+        ObjectOrLinkOrStringUrl::Link(link) => RemoteAttachment {
+            url: link.href.clone()?,
+            media_type: link.media_type.clone().unwrap_or_default(),
+            description: link.name.clone(),
+            width: None,
+            height: None,
+            blurhash: None,
+            focal_point: None,
+        },
+        ObjectOrLinkOrStringUrl::Str(url) => RemoteAttachment {
+            url: url.clone(),
+            media_type: String::new(),
+            description: None,
+            width: None,
+            height: None,
+            blurhash: None,
+            focal_point: None,
+        },
+        ObjectOrLinkOrStringUrl::Object(_) => return None,
+    };
+    if kept.media_type.is_empty() {
+        kept.media_type = "application/octet-stream".to_string();
+    }
+    Some(kept)
+}
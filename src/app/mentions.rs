@@ -0,0 +1,182 @@
+use crate::app::object_builders::{activity_pub::person, html::escape};
+use crate::config::Config;
+use crate::domain::repositories::{MentionsRepository, UsersRepository};
+use crate::federation::webfinger::resolve_account;
+use calmi_activity_streams::types::enums::{SingleOrMultiple, TagEntry};
+use calmi_activity_streams::types::object::note::Note;
+use sea_orm::DbErr;
+use std::ops::Range;
+
+pub struct Mention {
+    pub name: String,
+    pub href: String,
+}
+
+pub async fn resolve<T: UsersRepository>(
+    storage: &T,
+    client: &reqwest::Client,
+    config: &Config,
+    content: &str,
+) -> Result<Vec<Mention>, DbErr> {
+    let mut mentions: Vec<Mention> = Vec::new();
+    for (range, username, domain) in find(content) {
+        let name = &content[range];
+        if mentions
+            .iter()
+            .any(|mention| mention.name.eq_ignore_ascii_case(name))
+        {
+            continue;
+        }
+
+        let href = if domain.eq_ignore_ascii_case(&config.domain) {
+            storage
+                .find_user_by_username(username)
+                .await?
+                .map(|user| person::endpoint_uri(&config.base_url, &user))
+        } else {
+            match resolve_account(client, &config.webfinger_scheme, username, domain).await {
+                Ok(href) => Some(href),
+                Err(err) => {
+                    eprintln!("Leaving mention {} unresolved: {}", name, err);
+                    None
+                }
+            }
+        };
+        if let Some(href) = href {
+            mentions.push(Mention {
+                name: name.to_string(),
+                href,
+            });
+        }
+    }
+    Ok(mentions)
+}
+
+pub fn link(content: &str, mentions: &[Mention]) -> String {
+    let mut linked = String::with_capacity(content.len());
+    let mut copied = 0;
+    for (range, _, _) in find(content) {
+        let name = &content[range.clone()];
+        let Some(mention) = mentions
+            .iter()
+            .find(|mention| mention.name.eq_ignore_ascii_case(name))
+        else {
+            continue;
+        };
+        linked.push_str(&content[copied..range.start]);
+        linked.push_str(&format!(
+            r#"<a href="{}" class="u-url mention">{}</a>"#,
+            escape(&mention.href),
+            name
+        ));
+        copied = range.end;
+    }
+    linked.push_str(&content[copied..]);
+    linked
+}
+
+pub async fn record<T: UsersRepository + MentionsRepository>(
+    storage: &T,
+    base_url: &str,
+    note: &str,
+    actor: &str,
+    hrefs: &[String],
+) -> Result<(), DbErr> {
+    for user_id in local_users(storage, base_url, hrefs).await? {
+        storage.add_mention(user_id, note, actor).await?;
+    }
+    Ok(())
+}
+
+/// The ids of the local users among the mentioned `hrefs`.
+pub async fn local_users<T: UsersRepository>(
+    storage: &T,
+    base_url: &str,
+    hrefs: &[String],
+) -> Result<Vec<i64>, DbErr> {
+    let mut users = Vec::new();
+    for href in hrefs {
+        let Some(username) = person::local_username(base_url, href) else {
+            continue;
+        };
+        if let Some(user) = storage.find_user_by_username(username).await? {
+            users.push(user.id);
+        }
+    }
+    Ok(users)
+}
+
+pub fn mentioned_actors(note: &Note) -> Vec<String> {
+    let tags = match note.tag.as_deref() {
+        Some(SingleOrMultiple::Single(tag)) => std::slice::from_ref(tag),
+        Some(SingleOrMultiple::Multiple(tags)) => tags.as_slice(),
+        None => &[],
+    };
+    tags.iter()
+        .filter_map(|tag| match tag {
+            TagEntry::Link(link) if link.r#type.as_deref() == Some("Mention") => link.href.clone(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A mention starts a word, so addresses inside URLs are skipped.
+fn find(content: &str) -> Vec<(Range<usize>, &str, &str)> {
+    let bytes = content.as_bytes();
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        // `@` is a single byte in UTF-8, so `i` is a char boundary past this check.
+        if bytes[i] != b'@' {
+            i += 1;
+            continue;
+        }
+        let starts_word = content[..i]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || c == '(' || c == '>');
+        if !starts_word {
+            i += 1;
+            continue;
+        }
+
+        let user_start = i + 1;
+        let user_end = user_start + span(&bytes[user_start..], is_username_byte);
+        if user_end == user_start || bytes.get(user_end) != Some(&b'@') {
+            i = user_start;
+            continue;
+        }
+
+        let domain_start = user_end + 1;
+        let mut domain_end = domain_start + span(&bytes[domain_start..], is_domain_byte);
+        // Punctuation after the domain ends the sentence, not the domain.
+        while domain_end > domain_start && matches!(bytes[domain_end - 1], b'.' | b'-' | b':') {
+            domain_end -= 1;
+        }
+        if domain_end == domain_start {
+            i = domain_start;
+            continue;
+        }
+
+        found.push((
+            i..domain_end,
+            &content[user_start..user_end],
+            &content[domain_start..domain_end],
+        ));
+        i = domain_end;
+    }
+    found
+}
+
+fn span(bytes: &[u8], accept: fn(u8) -> bool) -> usize {
+    bytes.iter().take_while(|&&b| accept(b)).count()
+}
+
+fn is_username_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-')
+}
+
+/// Ports are allowed, as in `localhost:8080`.
+fn is_domain_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b':')
+}
@@ -2,4 +2,5 @@
 // https://www.w3.org/TR/activitypub/#outbox
 
 pub mod activity_pub;
+pub mod html;
 pub mod webfinger;
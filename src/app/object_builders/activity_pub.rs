@@ -1,4 +1,20 @@
+pub mod accept;
+pub mod attachment;
+pub mod collection;
 pub mod create;
+pub mod delete;
+pub mod follow;
+pub mod followers;
+pub mod following;
+pub mod like;
+pub mod liked;
+pub mod likes;
 pub mod note;
 pub mod outbox;
 pub mod person;
+pub mod reject;
+pub mod replies;
+pub mod shares;
+pub mod tag;
+pub mod undo;
+pub mod update;
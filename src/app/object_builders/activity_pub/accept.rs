@@ -0,0 +1,31 @@
+use super::follow;
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::{ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::accept::Accept,
+};
+
+/// Accept(Follow) sent back to a follower once the follow is approved.
+/// https://www.w3.org/TR/activitypub/#accept-activity-inbox
+pub fn build_accept_follow(
+    base_url: &str,
+    followee: &entities::users::Model,
+    follow_record: &entities::follows::Model,
+) -> Accept {
+    let actor = format!("{}/users/{}", base_url, followee.username);
+    let follow = follow::build_received_follow(base_url, followee, follow_record);
+
+    Accept {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(format!("{}#accepts/follows/{}", actor, follow_record.id)),
+        r#type: Some("Accept".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(actor),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Object(ObjectBased::Follow(follow)),
+        ))),
+    }
+}
@@ -0,0 +1,73 @@
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::{LinkOrStringUrl, ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::{document::Document, image::Image, video::Video},
+};
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-attachment
+pub fn build_attachment(base_url: &str, media: &entities::media::Model) -> ObjectOrLinkOrStringUrl {
+    let url = Some(Box::new(SingleOrMultiple::Single(LinkOrStringUrl::Str(
+        endpoint_uri(base_url, media),
+    ))));
+    let name = media.description.clone();
+    let media_type = Some(media.media_type.clone());
+    let width = media.width.and_then(|width| width.try_into().ok());
+    let height = media.height.and_then(|height| height.try_into().ok());
+    let blurhash = media.blurhash.clone();
+    let focal_point = focal_point(media);
+
+    let object = match media.media_type.split('/').next() {
+        Some("image") => ObjectBased::Image(Image {
+            context: None,
+            id: None,
+            r#type: Some("Image".to_string()),
+            name,
+            media_type,
+            url,
+            width,
+            height,
+            blurhash,
+            focal_point,
+        }),
+        Some("video") => ObjectBased::Video(Video {
+            context: None,
+            id: None,
+            r#type: Some("Video".to_string()),
+            name,
+            media_type,
+            url,
+            width,
+            height,
+            blurhash,
+            focal_point,
+        }),
+        _ => ObjectBased::Document(Document {
+            context: None,
+            id: None,
+            r#type: Some("Document".to_string()),
+            name,
+            media_type,
+            url,
+            width,
+            height,
+            blurhash,
+            focal_point,
+        }),
+    };
+    ObjectOrLinkOrStringUrl::Object(object)
+}
+
+pub fn focal_point(media: &entities::media::Model) -> Option<[f64; 2]> {
+    media.focal_x.zip(media.focal_y).map(|(x, y)| [x, y])
+}
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/media/{file_name}"
+}
+
+pub fn endpoint_uri(base_url: &str, media: &entities::media::Model) -> String {
+    match &media.file_name {
+        Some(file_name) => format!("{}/media/{}", base_url, file_name),
+        None => media.remote_url.clone().unwrap_or_default(),
+    }
+}
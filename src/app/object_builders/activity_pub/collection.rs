@@ -0,0 +1,61 @@
+use calmi_activity_streams::types::{
+    enums::{LinkOrStringUrl, ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
+};
+
+/// Items per page of paged collections.
+pub const PAGE_SIZE: u64 = 20;
+
+/// A paged collection without its items, pointing at its first page.
+/// `first` is left out when the items are not meant to be seen.
+/// https://www.w3.org/TR/activitystreams-core/#paging
+pub fn build_paged_collection(id: &str, total_items: u64, show_items: bool) -> OrderedCollection {
+    OrderedCollection {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(id.to_string()),
+        r#type: Some("OrderedCollection".to_string()),
+        total_items: Some(total_items as usize),
+        ordered_items: None,
+        first: show_items.then(|| Box::new(LinkOrStringUrl::Str(page_uri(id, 1)))),
+        last: None,
+    }
+}
+
+/// Page `page` (1-based) of the collection `collection_id`.
+pub fn build_collection_page(
+    collection_id: &str,
+    page: u64,
+    total_items: u64,
+    items: Vec<ObjectOrLinkOrStringUrl>,
+) -> OrderedCollectionPage {
+    OrderedCollectionPage {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(page_uri(collection_id, page)),
+        r#type: Some("OrderedCollectionPage".to_string()),
+        total_items: Some(total_items as usize),
+        ordered_items: Some(items),
+        part_of: Some(Box::new(LinkOrStringUrl::Str(collection_id.to_string()))),
+        next: page
+            .checked_mul(PAGE_SIZE)
+            .is_some_and(|seen| seen < total_items)
+            .then(|| Box::new(LinkOrStringUrl::Str(page_uri(collection_id, page + 1)))),
+        prev: (page > 1).then(|| Box::new(LinkOrStringUrl::Str(page_uri(collection_id, page - 1)))),
+    }
+}
+
+/// Rows to skip to reach page `page` (1-based), or none for pages no query can reach.
+pub fn page_offset(page: u64) -> Option<u64> {
+    page.checked_sub(1)?
+        .checked_mul(PAGE_SIZE)
+        .filter(|&offset| offset <= i64::MAX as u64)
+}
+
+fn page_uri(collection_id: &str, page: u64) -> String {
+    format!("{}?page={}", collection_id, page)
+}
@@ -9,13 +9,15 @@ pub fn build_create_activity(
     base_url: &str,
     note: &entities::notes::Model,
     author: &entities::users::Model,
+    details: &note::NoteDetails,
 ) -> Create {
-    let note_object = note::build_note(base_url, note, author);
+    let note_object = note::build_note(base_url, note, author, details);
+    let (to, cc) = note::addressing(base_url, note, author);
     let activity_id = endpoint_uri(base_url, note, author);
 
     Create {
         context: Some(SingleOrMultiple::Multiple(vec![
-            "https://www.w3.org/ns/activitystreams".to_string(),
+            "https://www.w3.org/ns/activitystreams".into(),
         ])),
         id: Some(activity_id),
         r#type: Some("Create".to_string()),
@@ -25,6 +27,8 @@ pub fn build_create_activity(
         object: Some(Box::new(SingleOrMultiple::Single(
             ObjectOrLinkOrStringUrl::Object(ObjectBased::Note(note_object)),
         ))),
+        to: note::audience(to),
+        cc: note::audience(cc),
     }
 }
 
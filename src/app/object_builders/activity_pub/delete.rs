@@ -0,0 +1,33 @@
+use super::note;
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::{ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::delete::Delete,
+};
+
+/// Delete sent to everyone who may hold a copy of a deleted note.
+/// https://www.w3.org/TR/activitypub/#delete-activity-outbox
+pub fn build_delete_note(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> Delete {
+    let tombstone = note::build_tombstone(base_url, note, author);
+
+    Delete {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(format!(
+            "{}#delete",
+            note::endpoint_uri(base_url, note, author)
+        )),
+        r#type: Some("Delete".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, author.username)),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Object(ObjectBased::Tombstone(tombstone)),
+        ))),
+    }
+}
@@ -0,0 +1,67 @@
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::{ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::follow::Follow,
+};
+
+/// The Follow a remote actor sent us, rebuilt from what we recorded of it.
+pub fn build_received_follow(
+    base_url: &str,
+    followee: &entities::users::Model,
+    follow_record: &entities::follows::Model,
+) -> Follow {
+    Follow {
+        context: None,
+        id: Some(follow_record.activity_id.clone()),
+        r#type: Some("Follow".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(follow_record.actor.clone()),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, followee.username)),
+        ))),
+    }
+}
+
+/// Follow sent by a local user to a remote actor.
+/// https://www.w3.org/TR/activitypub/#follow-activity-outbox
+pub fn build_follow(
+    base_url: &str,
+    follower: &entities::users::Model,
+    following_record: &entities::following::Model,
+) -> Follow {
+    Follow {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        ..build_sent_follow(base_url, follower, following_record)
+    }
+}
+
+/// A fresh id for a Follow we are about to send.
+pub fn new_follow_activity_id(base_url: &str, follower: &entities::users::Model) -> String {
+    format!(
+        "{}/users/{}#follows/{}",
+        base_url,
+        follower.username,
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+pub(super) fn build_sent_follow(
+    base_url: &str,
+    follower: &entities::users::Model,
+    following_record: &entities::following::Model,
+) -> Follow {
+    Follow {
+        context: None,
+        id: Some(following_record.activity_id.clone()),
+        r#type: Some("Follow".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, follower.username)),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(following_record.actor.clone()),
+        ))),
+    }
+}
@@ -0,0 +1,49 @@
+use super::collection;
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::ObjectOrLinkOrStringUrl,
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
+};
+
+/// **Followers** is an OrderedCollection of the actors following the user.
+/// https://www.w3.org/TR/activitypub/#followers
+/// Users who hide their followers only publish `totalItems`.
+pub fn build_followers(
+    base_url: &str,
+    user: &entities::users::Model,
+    total_items: u64,
+) -> OrderedCollection {
+    collection::build_paged_collection(
+        &endpoint_uri(base_url, user),
+        total_items,
+        !user.hide_followers,
+    )
+}
+
+pub fn build_followers_page(
+    base_url: &str,
+    user: &entities::users::Model,
+    page: u64,
+    total_items: u64,
+    followers: &[entities::follows::Model],
+) -> OrderedCollectionPage {
+    collection::build_collection_page(
+        &endpoint_uri(base_url, user),
+        page,
+        total_items,
+        followers
+            .iter()
+            .map(|follow| ObjectOrLinkOrStringUrl::Str(follow.actor.clone()))
+            .collect(),
+    )
+}
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/users/{username}/followers"
+}
+
+pub fn endpoint_uri(base_url: &str, user: &entities::users::Model) -> String {
+    format!("{}/users/{}/followers", base_url, user.username)
+}
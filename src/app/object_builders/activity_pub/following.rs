@@ -0,0 +1,45 @@
+use super::collection;
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::ObjectOrLinkOrStringUrl,
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
+};
+
+/// **Following** is an OrderedCollection of the actors the user follows.
+/// https://www.w3.org/TR/activitypub/#following
+/// Follows still waiting for an Accept are not listed.
+pub fn build_following(
+    base_url: &str,
+    user: &entities::users::Model,
+    total_items: u64,
+) -> OrderedCollection {
+    collection::build_paged_collection(&endpoint_uri(base_url, user), total_items, true)
+}
+
+pub fn build_following_page(
+    base_url: &str,
+    user: &entities::users::Model,
+    page: u64,
+    total_items: u64,
+    following: &[entities::following::Model],
+) -> OrderedCollectionPage {
+    collection::build_collection_page(
+        &endpoint_uri(base_url, user),
+        page,
+        total_items,
+        following
+            .iter()
+            .map(|record| ObjectOrLinkOrStringUrl::Str(record.actor.clone()))
+            .collect(),
+    )
+}
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/users/{username}/following"
+}
+
+pub fn endpoint_uri(base_url: &str, user: &entities::users::Model) -> String {
+    format!("{}/users/{}/following", base_url, user.username)
+}
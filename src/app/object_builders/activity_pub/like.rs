@@ -0,0 +1,48 @@
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::{ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::like::Like,
+};
+
+/// Like sent by a local user.
+/// https://www.w3.org/TR/activitypub/#like-activity-outbox
+pub fn build_like(
+    base_url: &str,
+    user: &entities::users::Model,
+    liked_record: &entities::liked::Model,
+) -> Like {
+    Like {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        ..build_sent_like(base_url, user, liked_record)
+    }
+}
+
+/// A fresh id for a Like we are about to send.
+pub fn new_like_activity_id(base_url: &str, user: &entities::users::Model) -> String {
+    format!(
+        "{}/users/{}#likes/{}",
+        base_url,
+        user.username,
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+pub(super) fn build_sent_like(
+    base_url: &str,
+    user: &entities::users::Model,
+    liked_record: &entities::liked::Model,
+) -> Like {
+    Like {
+        context: None,
+        id: Some(liked_record.activity_id.clone()),
+        r#type: Some("Like".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, user.username)),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(liked_record.object.clone()),
+        ))),
+    }
+}
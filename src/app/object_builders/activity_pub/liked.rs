@@ -0,0 +1,44 @@
+use super::collection;
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::ObjectOrLinkOrStringUrl,
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
+};
+
+/// **Liked** is an OrderedCollection of the objects the user has liked.
+/// https://www.w3.org/TR/activitypub/#liked
+pub fn build_liked(
+    base_url: &str,
+    user: &entities::users::Model,
+    total_items: u64,
+) -> OrderedCollection {
+    collection::build_paged_collection(&endpoint_uri(base_url, user), total_items, true)
+}
+
+pub fn build_liked_page(
+    base_url: &str,
+    user: &entities::users::Model,
+    page: u64,
+    total_items: u64,
+    liked: &[entities::liked::Model],
+) -> OrderedCollectionPage {
+    collection::build_collection_page(
+        &endpoint_uri(base_url, user),
+        page,
+        total_items,
+        liked
+            .iter()
+            .map(|record| ObjectOrLinkOrStringUrl::Str(record.object.clone()))
+            .collect(),
+    )
+}
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/users/{username}/liked"
+}
+
+pub fn endpoint_uri(base_url: &str, user: &entities::users::Model) -> String {
+    format!("{}/users/{}/liked", base_url, user.username)
+}
@@ -0,0 +1,50 @@
+use super::{collection, note};
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::ObjectOrLinkOrStringUrl,
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
+};
+
+/// **Likes** is an OrderedCollection of the Like activities of a note.
+/// https://www.w3.org/TR/activitypub/#likes
+pub fn build_likes(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+    total_items: u64,
+) -> OrderedCollection {
+    collection::build_paged_collection(&endpoint_uri(base_url, note, author), total_items, true)
+}
+
+pub fn build_likes_page(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+    page: u64,
+    total_items: u64,
+    likes: &[entities::note_likes::Model],
+) -> OrderedCollectionPage {
+    collection::build_collection_page(
+        &endpoint_uri(base_url, note, author),
+        page,
+        total_items,
+        likes
+            .iter()
+            .map(|record| ObjectOrLinkOrStringUrl::Str(record.activity_id.clone()))
+            .collect(),
+    )
+}
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/users/{username}/notes/{id}/likes"
+}
+
+pub fn endpoint_uri(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> String {
+    format!("{}/likes", note::endpoint_uri(base_url, note, author))
+}
@@ -1,36 +1,228 @@
+use super::{attachment, collection, followers, likes, replies, shares, tag};
 use crate::domain::entities;
+use crate::domain::visibility::Visibility;
 use calmi_activity_streams::types::{
-    enums::{ObjectOrLinkOrStringUrl, SingleOrMultiple},
-    object::note::Note,
+    PUBLIC,
+    enums::{
+        ContextEntry, LinkOrStringUrl, ObjectBased, ObjectOrLinkOrStringUrl, ObjectOrStringUrl,
+        SingleOrMultiple, TagEntry,
+    },
+    link::Link,
+    object::{note::Note, ordered_collection::OrderedCollection, tombstone::Tombstone},
+    properties::To,
 };
 
+const TOOT: &str = "http://joinmastodon.org/ns#";
+
+#[derive(Default)]
+pub struct NoteDetails {
+    pub mentions: Vec<entities::note_mentions::Model>,
+    pub tags: Vec<entities::note_tags::Model>,
+    pub media: Vec<entities::media::Model>,
+}
+
 pub fn build_note(
     base_url: &str,
     note: &entities::notes::Model,
     author: &entities::users::Model,
+    details: &NoteDetails,
 ) -> Note {
+    let NoteDetails {
+        mentions,
+        tags,
+        media,
+    } = details;
+    let mut terms = serde_json::Map::new();
+    if !tags.is_empty() {
+        terms.insert("Hashtag".to_string(), "as:Hashtag".into());
+    }
+    if media.iter().any(|media| media.blurhash.is_some()) {
+        terms.insert("toot".to_string(), TOOT.into());
+        terms.insert("blurhash".to_string(), "toot:blurhash".into());
+    }
+    if media
+        .iter()
+        .any(|media| attachment::focal_point(media).is_some())
+    {
+        terms.insert("toot".to_string(), TOOT.into());
+        terms.insert(
+            "focalPoint".to_string(),
+            serde_json::json!({ "@container": "@list", "@id": "toot:focalPoint" }),
+        );
+    }
+    if note.sensitive {
+        terms.insert("sensitive".to_string(), "as:sensitive".into());
+    }
+    let mut context = vec!["https://www.w3.org/ns/activitystreams".into()];
+    if !terms.is_empty() {
+        context.push(ContextEntry::Definitions(terms));
+    }
+
+    let (to, cc) = addressing(base_url, note, author);
+
     Note {
-        context: SingleOrMultiple::Multiple(vec![
-            "https://www.w3.org/ns/activitystreams".to_string(),
-        ])
-        .into(),
+        context: SingleOrMultiple::Multiple(context).into(),
         id: Some(endpoint_uri(base_url, note, author)),
         r#type: Some("Note".to_string()),
-        to: if note.to.is_empty() {
+        to: audience(to),
+        cc: audience(cc),
+        attributed_to: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, author.username)),
+        ))),
+        summary: note.summary.clone(),
+        content: Some(note.content.clone()),
+        // Left out rather than false, as most notes are not sensitive.
+        sensitive: note.sensitive.then_some(true),
+        published: Some(note.created_at.and_utc().to_rfc3339()),
+        updated: note
+            .updated_at
+            .map(|updated_at| updated_at.and_utc().to_rfc3339()),
+        in_reply_to: note.in_reply_to.as_ref().map(|in_reply_to| {
+            Box::new(SingleOrMultiple::Single(ObjectOrLinkOrStringUrl::Str(
+                in_reply_to.clone(),
+            )))
+        }),
+        replies: Some(Box::new(ObjectOrStringUrl::Str(replies::endpoint_uri(
+            base_url, note, author,
+        )))),
+        // A note that starts a conversation names it after itself.
+        thread_context: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(
+                note.context
+                    .clone()
+                    .unwrap_or_else(|| endpoint_uri(base_url, note, author)),
+            ),
+        ))),
+        // The note page is served at the note URI to browsers.
+        url: Some(Box::new(SingleOrMultiple::Single(LinkOrStringUrl::Str(
+            endpoint_uri(base_url, note, author),
+        )))),
+        tag: if mentions.is_empty() && tags.is_empty() {
             None
         } else {
             Some(Box::new(SingleOrMultiple::Multiple(
-                note.to
+                mentions
                     .iter()
-                    .map(|s| ObjectOrLinkOrStringUrl::Str(s.clone()))
+                    .map(build_mention)
+                    .chain(
+                        tags.iter().map(|note_tag| {
+                            TagEntry::Hashtag(tag::build_hashtag(base_url, note_tag))
+                        }),
+                    )
                     .collect(),
             )))
         },
-        attributed_to: Some(Box::new(SingleOrMultiple::Single(
-            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, author.username)),
-        ))),
-        content: Some(note.content.clone()),
-        published: Some(note.created_at.and_utc().to_rfc3339()),
+        attachment: if media.is_empty() {
+            None
+        } else {
+            Some(Box::new(SingleOrMultiple::Multiple(
+                media
+                    .iter()
+                    .map(|media| attachment::build_attachment(base_url, media))
+                    .collect(),
+            )))
+        },
+        likes: None,
+        shares: None,
+    }
+}
+
+/// The note as served on its own, with how many times it was liked and shared.
+pub fn build_note_with_interactions(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+    details: &NoteDetails,
+    likes_count: u64,
+    shares_count: u64,
+) -> Note {
+    let summary = |id: String, total_items: u64| {
+        let collection = collection::build_paged_collection(&id, total_items, true);
+        Box::new(ObjectOrStringUrl::Object(Box::new(
+            ObjectBased::OrderedCollection(OrderedCollection {
+                context: None,
+                ..collection
+            }),
+        )))
+    };
+
+    Note {
+        likes: Some(summary(
+            likes::endpoint_uri(base_url, note, author),
+            likes_count,
+        )),
+        shares: Some(summary(
+            shares::endpoint_uri(base_url, note, author),
+            shares_count,
+        )),
+        ..build_note(base_url, note, author, details)
+    }
+}
+
+/// https://docs.joinmastodon.org/spec/activitypub/#to-cc
+pub fn addressing(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> (Vec<String>, Vec<String>) {
+    let followers = followers::endpoint_uri(base_url, author);
+    let actors: Vec<String> = note
+        .to
+        .iter()
+        .filter(|to| to.as_str() != PUBLIC && **to != followers)
+        .cloned()
+        .collect();
+
+    match Visibility::of(note) {
+        Visibility::Public => (
+            vec![PUBLIC.to_string()],
+            std::iter::once(followers).chain(actors).collect(),
+        ),
+        Visibility::Unlisted => (
+            vec![followers],
+            std::iter::once(PUBLIC.to_string()).chain(actors).collect(),
+        ),
+        Visibility::Followers => (vec![followers], actors),
+        Visibility::Direct => (actors, Vec::new()),
+    }
+}
+
+pub fn audience(ids: Vec<String>) -> Option<Box<To>> {
+    if ids.is_empty() {
+        return None;
+    }
+    Some(Box::new(SingleOrMultiple::Multiple(
+        ids.into_iter().map(ObjectOrLinkOrStringUrl::Str).collect(),
+    )))
+}
+
+/// https://www.w3.org/TR/activitystreams-vocabulary/#dfn-mention
+fn build_mention(mention: &entities::note_mentions::Model) -> TagEntry {
+    TagEntry::Link(Link {
+        context: None,
+        r#type: Some("Mention".to_string()),
+        href: Some(mention.href.clone()),
+        rel: None,
+        media_type: None,
+        name: Some(mention.name.clone()),
+    })
+}
+
+/// What remains of a deleted note.
+/// https://www.w3.org/TR/activitypub/#delete-activity-outbox
+pub fn build_tombstone(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> Tombstone {
+    Tombstone {
+        context: None,
+        id: Some(endpoint_uri(base_url, note, author)),
+        r#type: Some("Tombstone".to_string()),
+        former_type: Some(SingleOrMultiple::Single("Note".to_string())),
+        deleted: note
+            .deleted_at
+            .map(|deleted_at| deleted_at.and_utc().to_rfc3339()),
     }
 }
 
@@ -38,10 +230,24 @@ pub fn endpoint_uri_template() -> &'static str {
     "/users/{username}/notes/{id}"
 }
 
-fn endpoint_uri(
+pub fn endpoint_uri(
     base_url: &str,
     note: &entities::notes::Model,
     author: &entities::users::Model,
 ) -> String {
-    format!("{}/users/{}/notes/{}", base_url, author.username, note.id)
+    id_uri(base_url, author, note.id)
+}
+
+/// The URI of the note `id` of `author`, for notes not stored yet.
+pub fn id_uri(base_url: &str, author: &entities::users::Model, id: i64) -> String {
+    format!("{}/users/{}/notes/{}", base_url, author.username, id)
+}
+
+/// The author's username is not checked; compare `endpoint_uri` of the note found.
+pub fn local_note_id(base_url: &str, uri: &str) -> Option<i64> {
+    let path = uri.strip_prefix(base_url)?.strip_prefix("/users/")?;
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        [_, "notes", id] => id.parse().ok(),
+        _ => None,
+    }
 }
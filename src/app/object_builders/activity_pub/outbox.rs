@@ -1,39 +1,90 @@
-use super::create;
+use super::{create, note};
 use crate::config::Config;
 use crate::domain::entities;
 use calmi_activity_streams::types::{
-    enums::{ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple},
-    object::{create::Create, ordered_collection::OrderedCollection},
+    enums::{LinkOrStringUrl, ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
 };
 
+/// Where an outbox page starts. Pages are cut by note id rather than by offset,
+/// so they stay stable while new notes are posted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutboxCursor {
+    /// The newest notes.
+    Newest,
+    /// Notes older than the note with this id.
+    MaxId(i64),
+    /// Notes newer than the note with this id; `0` starts from the oldest note.
+    MinId(i64),
+}
+
 /// **Outbox** is an OrderedCollection.
 /// https://www.w3.org/TR/activitypub/#outbox
-/// This server implementation uses Create activities as the items in the outbox.
+/// Its items are served in OrderedCollectionPages, starting from `first`.
 pub fn build_outbox(
     config: &Config,
     author: &entities::users::Model,
-    notes: &[entities::notes::Model],
+    total_items: u64,
 ) -> OrderedCollection {
-    let activities: Vec<Create> = notes
-        .iter()
-        .map(|note| create::build_create_activity(&config.base_url, note, author))
-        .collect();
-
     OrderedCollection {
         context: Some(SingleOrMultiple::Multiple(vec![
-            "https://www.w3.org/ns/activitystreams".to_string(),
+            "https://www.w3.org/ns/activitystreams".into(),
         ])),
         id: Some(endpoint_uri(&config.base_url, author)),
         r#type: Some("OrderedCollection".to_string()),
-        total_items: Some(notes.len()),
+        total_items: Some(total_items as usize),
+        ordered_items: None,
+        first: Some(Box::new(LinkOrStringUrl::Str(page_uri(
+            &config.base_url,
+            author,
+            OutboxCursor::Newest,
+        )))),
+        last: Some(Box::new(LinkOrStringUrl::Str(page_uri(
+            &config.base_url,
+            author,
+            OutboxCursor::MinId(0),
+        )))),
+    }
+}
+
+/// A page of the outbox. This server uses Create activities as the items,
+/// newest first. Each note comes with its mentions, hashtags and attachments.
+pub fn build_outbox_page(
+    config: &Config,
+    author: &entities::users::Model,
+    cursor: OutboxCursor,
+    notes: &[(entities::notes::Model, note::NoteDetails)],
+    total_items: u64,
+    next: Option<OutboxCursor>,
+    prev: Option<OutboxCursor>,
+) -> OrderedCollectionPage {
+    let base_url = &config.base_url;
+    let link = |cursor| Box::new(LinkOrStringUrl::Str(page_uri(base_url, author, cursor)));
+
+    OrderedCollectionPage {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(page_uri(base_url, author, cursor)),
+        r#type: Some("OrderedCollectionPage".to_string()),
+        total_items: Some(total_items as usize),
         ordered_items: Some(
-            activities
+            notes
                 .iter()
-                .map(|activity| {
-                    ObjectOrLinkOrStringUrl::Object(ObjectBased::Create(activity.clone()))
+                .map(|(note, details)| {
+                    ObjectOrLinkOrStringUrl::Object(ObjectBased::Create(
+                        create::build_create_activity(base_url, note, author, details),
+                    ))
                 })
                 .collect(),
         ),
+        part_of: Some(Box::new(LinkOrStringUrl::Str(endpoint_uri(
+            base_url, author,
+        )))),
+        next: next.map(link),
+        prev: prev.map(link),
     }
 }
 
@@ -44,3 +95,12 @@ pub fn endpoint_uri_template() -> &'static str {
 fn endpoint_uri(base_url: &str, author: &entities::users::Model) -> String {
     format!("{}/users/{}/outbox", base_url, author.username)
 }
+
+fn page_uri(base_url: &str, author: &entities::users::Model, cursor: OutboxCursor) -> String {
+    let outbox = endpoint_uri(base_url, author);
+    match cursor {
+        OutboxCursor::Newest => format!("{}?page=true", outbox),
+        OutboxCursor::MaxId(id) => format!("{}?page=true&max_id={}", outbox, id),
+        OutboxCursor::MinId(id) => format!("{}?page=true&min_id={}", outbox, id),
+    }
+}
@@ -1,19 +1,33 @@
+use super::{followers, following, liked};
 use crate::config::Config;
 use crate::domain::entities;
 use calmi_activity_streams::types::{
-    enums::{ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    enums::{ContextEntry, LinkOrStringUrl, ObjectOrLinkOrStringUrl, SingleOrMultiple},
     object::person::Person,
+    security::PublicKey,
 };
 
 pub fn build_person(config: &Config, user: &entities::users::Model) -> Person {
+    let mut terms = serde_json::Map::new();
+    terms.insert(
+        "manuallyApprovesFollowers".to_string(),
+        "as:manuallyApprovesFollowers".into(),
+    );
     Person {
         context: Some(SingleOrMultiple::Multiple(vec![
-            "https://www.w3.org/ns/activitystreams".to_string(),
-            "https://w3id.org/security/v1".to_string(),
+            "https://www.w3.org/ns/activitystreams".into(),
+            "https://w3id.org/security/v1".into(),
+            ContextEntry::Definitions(terms),
         ])),
         id: Some(endpoint_uri(&config.base_url, user)),
         r#type: Some("Person".to_string()),
         name: Some(user.display_name.clone()),
+        preferred_username: Some(user.username.clone()),
+        // The profile page is served at the actor URI to browsers.
+        url: Some(Box::new(SingleOrMultiple::Single(LinkOrStringUrl::Str(
+            endpoint_uri(&config.base_url, user),
+        )))),
+        icon: None,
         inbox: Some(Box::new(ObjectOrLinkOrStringUrl::Str(format!(
             "{}/users/{}/inbox",
             config.base_url, user.username
@@ -22,6 +36,25 @@ pub fn build_person(config: &Config, user: &entities::users::Model) -> Person {
             "{}/users/{}/outbox",
             config.base_url, user.username
         )))),
+        followers: Some(Box::new(ObjectOrLinkOrStringUrl::Str(
+            followers::endpoint_uri(&config.base_url, user),
+        ))),
+        following: Some(Box::new(ObjectOrLinkOrStringUrl::Str(
+            following::endpoint_uri(&config.base_url, user),
+        ))),
+        liked: Some(Box::new(ObjectOrLinkOrStringUrl::Str(liked::endpoint_uri(
+            &config.base_url,
+            user,
+        )))),
+        endpoints: None,
+        public_key: user.public_key_pem.as_ref().map(|pem| {
+            Box::new(PublicKey {
+                id: public_key_id(&config.base_url, user),
+                owner: endpoint_uri(&config.base_url, user),
+                public_key_pem: pem.clone(),
+            })
+        }),
+        manually_approves_followers: Some(user.manually_approves_followers),
     }
 }
 
@@ -29,6 +62,17 @@ pub fn endpoint_uri_template() -> &'static str {
     "/users/{username}"
 }
 
-fn endpoint_uri(base_url: &str, user: &entities::users::Model) -> String {
+/// The `keyId` other servers see in our HTTP Signatures.
+pub fn public_key_id(base_url: &str, user: &entities::users::Model) -> String {
+    format!("{}#main-key", endpoint_uri(base_url, user))
+}
+
+pub fn endpoint_uri(base_url: &str, user: &entities::users::Model) -> String {
     format!("{}/users/{}", base_url, user.username)
 }
+
+pub fn local_username<'a>(base_url: &str, uri: &'a str) -> Option<&'a str> {
+    uri.strip_prefix(base_url)?
+        .strip_prefix("/users/")
+        .filter(|username| !username.is_empty() && !username.contains(['/', '?', '#']))
+}
@@ -0,0 +1,31 @@
+use super::follow;
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::{ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::reject::Reject,
+};
+
+/// Reject(Follow) sent when a user declines a follow request.
+/// https://www.w3.org/TR/activitypub/#reject-activity-inbox
+pub fn build_reject_follow(
+    base_url: &str,
+    followee: &entities::users::Model,
+    follow_record: &entities::follows::Model,
+) -> Reject {
+    let actor = format!("{}/users/{}", base_url, followee.username);
+    let follow = follow::build_received_follow(base_url, followee, follow_record);
+
+    Reject {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(format!("{}#rejects/follows/{}", actor, follow_record.id)),
+        r#type: Some("Reject".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(actor),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Object(ObjectBased::Follow(follow)),
+        ))),
+    }
+}
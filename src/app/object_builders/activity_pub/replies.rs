@@ -0,0 +1,51 @@
+use super::{collection, note};
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::ObjectOrLinkOrStringUrl,
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
+};
+
+/// **Replies** holds the notes answering a note, local and remote, oldest first.
+/// https://www.w3.org/TR/activitypub/#replies
+pub fn build_replies(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+    total_items: u64,
+) -> OrderedCollection {
+    collection::build_paged_collection(&endpoint_uri(base_url, note, author), total_items, true)
+}
+
+/// `replies` are the ids of the answering notes.
+pub fn build_replies_page(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+    page: u64,
+    total_items: u64,
+    replies: &[String],
+) -> OrderedCollectionPage {
+    collection::build_collection_page(
+        &endpoint_uri(base_url, note, author),
+        page,
+        total_items,
+        replies
+            .iter()
+            .map(|reply| ObjectOrLinkOrStringUrl::Str(reply.clone()))
+            .collect(),
+    )
+}
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/users/{username}/notes/{id}/replies"
+}
+
+pub fn endpoint_uri(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> String {
+    format!("{}/replies", note::endpoint_uri(base_url, note, author))
+}
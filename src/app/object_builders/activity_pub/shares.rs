@@ -0,0 +1,50 @@
+use super::{collection, note};
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::ObjectOrLinkOrStringUrl,
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
+};
+
+/// **Shares** is an OrderedCollection of the Announce activities of a note.
+/// https://www.w3.org/TR/activitypub/#shares
+pub fn build_shares(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+    total_items: u64,
+) -> OrderedCollection {
+    collection::build_paged_collection(&endpoint_uri(base_url, note, author), total_items, true)
+}
+
+pub fn build_shares_page(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+    page: u64,
+    total_items: u64,
+    announces: &[entities::note_announces::Model],
+) -> OrderedCollectionPage {
+    collection::build_collection_page(
+        &endpoint_uri(base_url, note, author),
+        page,
+        total_items,
+        announces
+            .iter()
+            .map(|record| ObjectOrLinkOrStringUrl::Str(record.activity_id.clone()))
+            .collect(),
+    )
+}
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/users/{username}/notes/{id}/shares"
+}
+
+pub fn endpoint_uri(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> String {
+    format!("{}/shares", note::endpoint_uri(base_url, note, author))
+}
@@ -0,0 +1,46 @@
+use super::collection;
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::ObjectOrLinkOrStringUrl,
+    link::hashtag::Hashtag,
+    object::{
+        ordered_collection::OrderedCollection, ordered_collection_page::OrderedCollectionPage,
+    },
+};
+
+pub fn build_tag_collection(base_url: &str, name: &str, total_items: u64) -> OrderedCollection {
+    collection::build_paged_collection(&endpoint_uri(base_url, name), total_items, true)
+}
+
+pub fn build_tag_collection_page(
+    base_url: &str,
+    name: &str,
+    page: u64,
+    total_items: u64,
+    tagged: &[entities::note_tags::Model],
+) -> OrderedCollectionPage {
+    collection::build_collection_page(
+        &endpoint_uri(base_url, name),
+        page,
+        total_items,
+        tagged
+            .iter()
+            .map(|record| ObjectOrLinkOrStringUrl::Str(record.note.clone()))
+            .collect(),
+    )
+}
+
+pub fn build_hashtag(base_url: &str, tag: &entities::note_tags::Model) -> Hashtag {
+    Hashtag {
+        href: Some(endpoint_uri(base_url, &tag.name)),
+        name: format!("#{}", tag.name),
+    }
+}
+
+pub fn endpoint_uri_template() -> &'static str {
+    "/tags/{name}"
+}
+
+pub fn endpoint_uri(base_url: &str, name: &str) -> String {
+    format!("{}/tags/{}", base_url, name)
+}
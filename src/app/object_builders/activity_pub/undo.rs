@@ -0,0 +1,53 @@
+use super::{follow, like};
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::{ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::undo::Undo,
+};
+
+/// Undo(Follow) sent when a local user unfollows a remote actor.
+/// https://www.w3.org/TR/activitypub/#undo-activity-outbox
+pub fn build_undo_follow(
+    base_url: &str,
+    follower: &entities::users::Model,
+    following_record: &entities::following::Model,
+) -> Undo {
+    let follow = follow::build_sent_follow(base_url, follower, following_record);
+
+    Undo {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(format!("{}/undo", following_record.activity_id)),
+        r#type: Some("Undo".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, follower.username)),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Object(ObjectBased::Follow(follow)),
+        ))),
+    }
+}
+
+/// Undo(Like) sent when a local user takes back a like.
+pub fn build_undo_like(
+    base_url: &str,
+    user: &entities::users::Model,
+    liked_record: &entities::liked::Model,
+) -> Undo {
+    let like = like::build_sent_like(base_url, user, liked_record);
+
+    Undo {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(format!("{}/undo", liked_record.activity_id)),
+        r#type: Some("Undo".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, user.username)),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Object(ObjectBased::Like(like)),
+        ))),
+    }
+}
@@ -0,0 +1,36 @@
+use super::note;
+use crate::domain::entities;
+use calmi_activity_streams::types::{
+    enums::{ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple},
+    object::update::Update,
+};
+
+/// Update carrying the edited version of a note.
+/// https://www.w3.org/TR/activitypub/#update-activity-outbox
+pub fn build_update_note(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+    details: &note::NoteDetails,
+) -> Update {
+    let note_object = note::build_note(base_url, note, author, details);
+    let updated_at = note.updated_at.unwrap_or(note.created_at);
+
+    Update {
+        context: Some(SingleOrMultiple::Multiple(vec![
+            "https://www.w3.org/ns/activitystreams".into(),
+        ])),
+        id: Some(format!(
+            "{}#updates/{}",
+            note::endpoint_uri(base_url, note, author),
+            updated_at.and_utc().timestamp_millis()
+        )),
+        r#type: Some("Update".to_string()),
+        actor: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Str(format!("{}/users/{}", base_url, author.username)),
+        ))),
+        object: Some(Box::new(SingleOrMultiple::Single(
+            ObjectOrLinkOrStringUrl::Object(ObjectBased::Note(note_object)),
+        ))),
+    }
+}
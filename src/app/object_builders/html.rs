@@ -0,0 +1,51 @@
+// Web pages served at the same URLs as our ActivityPub objects, for people
+// who follow a link to them in a browser.
+
+pub mod note;
+pub mod person;
+
+/// A complete page. `alternate` is the ActivityPub representation of the same resource.
+fn page(title: &str, alternate: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<link rel="alternate" type="application/activity+json" href="{alternate}">
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+        title = escape(title),
+        alternate = escape(alternate),
+        body = body,
+    )
+}
+
+/// Note content is HTML written by its author, so only harmless markup is kept.
+fn sanitize(html: &str) -> String {
+    ammonia::Builder::default()
+        .add_allowed_classes("a", &["u-url", "mention", "hashtag"])
+        .clean(html)
+        .to_string()
+}
+
+/// Escapes text for use in element content and quoted attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,118 @@
+use super::{escape, page, person, sanitize};
+use crate::app::object_builders::activity_pub::note::endpoint_uri;
+use crate::domain::entities;
+
+/// Page for a single note.
+pub fn build_note_page(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> String {
+    let in_reply_to = note
+        .in_reply_to
+        .as_deref()
+        .map(|in_reply_to| {
+            format!(
+                "<p>In reply to <a href=\"{0}\">{0}</a></p>\n",
+                escape(in_reply_to)
+            )
+        })
+        .unwrap_or_default();
+    let body = format!(
+        r#"<header>
+<h1><a href="{profile}">{name}</a></h1>
+</header>
+<main>
+{in_reply_to}{note}</main>"#,
+        profile = escape(&person::endpoint_uri(base_url, author)),
+        name = escape(&author.display_name),
+        in_reply_to = in_reply_to,
+        note = build_note_summary(base_url, note, author),
+    );
+
+    // The title does not give away what a content warning hides.
+    let title = match &note.summary {
+        Some(summary) => excerpt(summary),
+        None => excerpt(&note.content),
+    };
+    page(
+        &format!("{}: {}", author.display_name, title),
+        &endpoint_uri(base_url, note, author),
+        &body,
+    )
+}
+
+/// Page left behind by a deleted note.
+pub fn build_deleted_note_page(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> String {
+    page(
+        "Deleted note",
+        &endpoint_uri(base_url, note, author),
+        "<main>\n<p>This note has been deleted.</p>\n</main>",
+    )
+}
+
+/// A note as it appears in lists.
+/// Content behind a content warning is folded away until the reader opens it.
+pub(super) fn build_note_summary(
+    base_url: &str,
+    note: &entities::notes::Model,
+    author: &entities::users::Model,
+) -> String {
+    let published = note.created_at.and_utc().to_rfc3339();
+    let edited = note
+        .updated_at
+        .map(|updated_at| {
+            let updated_at = updated_at.and_utc().to_rfc3339();
+            format!(
+                r#" · edited <time datetime="{0}">{0}</time>"#,
+                escape(&updated_at)
+            )
+        })
+        .unwrap_or_default();
+    let content = match &note.summary {
+        Some(summary) => format!(
+            "<details><summary>{}</summary>{}</details>",
+            escape(summary),
+            sanitize(&note.content)
+        ),
+        None => sanitize(&note.content),
+    };
+
+    format!(
+        r#"<article>
+<div>{content}</div>
+<footer><a href="{uri}"><time datetime="{published}">{published}</time></a>{edited}</footer>
+</article>
+"#,
+        content = content,
+        uri = escape(&endpoint_uri(base_url, note, author)),
+        published = escape(&published),
+        edited = edited,
+    )
+}
+
+/// The start of the note's text, for the page title.
+fn excerpt(content: &str) -> String {
+    const LENGTH: usize = 40;
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in content.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > LENGTH {
+        format!("{}…", text.chars().take(LENGTH).collect::<String>())
+    } else {
+        text
+    }
+}
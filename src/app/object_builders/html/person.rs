@@ -0,0 +1,40 @@
+use super::{escape, note, page};
+use crate::config::Config;
+use crate::domain::entities;
+
+/// Profile page with the user's most recent notes.
+pub fn build_profile_page(
+    config: &Config,
+    user: &entities::users::Model,
+    notes: &[entities::notes::Model],
+) -> String {
+    let profile_uri = endpoint_uri(&config.base_url, user);
+    let handle = format!("@{}@{}", user.username, config.domain);
+
+    let notes: String = notes
+        .iter()
+        .map(|n| note::build_note_summary(&config.base_url, n, user))
+        .collect();
+
+    let body = format!(
+        r#"<header>
+<h1>{name}</h1>
+<p>{handle}</p>
+</header>
+<main>
+{notes}</main>"#,
+        name = escape(&user.display_name),
+        handle = escape(&handle),
+        notes = notes,
+    );
+
+    page(
+        &format!("{} ({})", user.display_name, handle),
+        &profile_uri,
+        &body,
+    )
+}
+
+pub(super) fn endpoint_uri(base_url: &str, user: &entities::users::Model) -> String {
+    format!("{}/users/{}", base_url, user.username)
+}
@@ -1,7 +1,7 @@
 use crate::app::handlers;
 use crate::app::object_builders;
 use crate::app::state::AppState;
-use axum::{Router, routing::get, routing::post};
+use axum::{Router, extract::DefaultBodyLimit, routing::delete, routing::get, routing::post};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
@@ -18,12 +18,109 @@ pub fn routes() -> Router<AppState> {
             object_builders::activity_pub::outbox::endpoint_uri_template(),
             get(handlers::activity_pub::outbox::get),
         )
+        .route(
+            object_builders::activity_pub::followers::endpoint_uri_template(),
+            get(handlers::activity_pub::followers::get),
+        )
+        .route(
+            object_builders::activity_pub::following::endpoint_uri_template(),
+            get(handlers::activity_pub::following::get),
+        )
+        .route(
+            object_builders::activity_pub::liked::endpoint_uri_template(),
+            get(handlers::activity_pub::liked::get),
+        )
         .route(
             object_builders::activity_pub::note::endpoint_uri_template(),
             get(handlers::activity_pub::note::get),
         )
+        .route(
+            object_builders::activity_pub::likes::endpoint_uri_template(),
+            get(handlers::activity_pub::likes::get),
+        )
+        .route(
+            object_builders::activity_pub::shares::endpoint_uri_template(),
+            get(handlers::activity_pub::shares::get),
+        )
+        .route(
+            object_builders::activity_pub::replies::endpoint_uri_template(),
+            get(handlers::activity_pub::replies::get),
+        )
         .route(
             object_builders::activity_pub::create::endpoint_uri_template(),
             get(handlers::activity_pub::create::get),
         )
+        .route(
+            object_builders::activity_pub::tag::endpoint_uri_template(),
+            get(handlers::activity_pub::tag::get),
+        )
+        .route(
+            object_builders::activity_pub::attachment::endpoint_uri_template(),
+            get(handlers::media::get),
+        )
+        .route(
+            handlers::api::users::endpoint_uri_template(),
+            get(handlers::api::users::get).patch(handlers::api::users::patch),
+        )
+        .route(
+            handlers::api::conversations::endpoint_uri_template(),
+            get(handlers::api::conversations::list),
+        )
+        .route(
+            handlers::api::conversations::item_endpoint_uri_template(),
+            get(handlers::api::conversations::get),
+        )
+        .route(
+            handlers::api::follow_requests::endpoint_uri_template(),
+            get(handlers::api::follow_requests::list),
+        )
+        .route(
+            handlers::api::follow_requests::accept_endpoint_uri_template(),
+            post(handlers::api::follow_requests::accept),
+        )
+        .route(
+            handlers::api::follow_requests::reject_endpoint_uri_template(),
+            post(handlers::api::follow_requests::reject),
+        )
+        .route(
+            handlers::api::following::endpoint_uri_template(),
+            get(handlers::api::following::list).post(handlers::api::following::follow),
+        )
+        .route(
+            handlers::api::following::item_endpoint_uri_template(),
+            delete(handlers::api::following::unfollow),
+        )
+        .route(
+            handlers::api::liked::endpoint_uri_template(),
+            get(handlers::api::liked::list).post(handlers::api::liked::like),
+        )
+        .route(
+            handlers::api::liked::item_endpoint_uri_template(),
+            delete(handlers::api::liked::unlike),
+        )
+        .route(
+            handlers::api::media::endpoint_uri_template(),
+            post(handlers::api::media::upload)
+                .layer(DefaultBodyLimit::max(handlers::api::media::MAX_UPLOAD_SIZE)),
+        )
+        .route(
+            handlers::api::mentions::endpoint_uri_template(),
+            get(handlers::api::mentions::list),
+        )
+        .route(
+            handlers::api::notes::endpoint_uri_template(),
+            post(handlers::api::notes::create),
+        )
+        .route(
+            handlers::api::notes::item_endpoint_uri_template(),
+            delete(handlers::api::notes::delete).patch(handlers::api::notes::patch),
+        )
+        .route(
+            handlers::api::notes::revisions_endpoint_uri_template(),
+            get(handlers::api::notes::revisions),
+        )
+        .route(
+            handlers::api::notes::thread_endpoint_uri_template(),
+            get(handlers::api::notes::thread),
+        )
 }
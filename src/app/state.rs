@@ -1,14 +1,27 @@
 use crate::config::Config;
+use crate::federation::{self, actor::ActorResolver, public_key::PublicKeyCache};
 use crate::storage::postgres::PostgresStorage;
 
 #[derive(Clone)]
 pub struct AppState {
     pub config: Config,
     pub storage: PostgresStorage,
+    pub http_client: reqwest::Client,
+    pub public_keys: PublicKeyCache,
+    pub actors: ActorResolver,
 }
 
 impl AppState {
     pub fn new(config: Config, storage: PostgresStorage) -> Self {
-        Self { config, storage }
+        let http_client = federation::build_http_client();
+        let actors = ActorResolver::new(http_client.clone(), config.remote_actor_ttl);
+        let public_keys = PublicKeyCache::new(http_client.clone(), config.remote_actor_ttl);
+        Self {
+            config,
+            storage,
+            public_keys,
+            actors,
+            http_client,
+        }
     }
 }
@@ -0,0 +1,218 @@
+// Remote notes we never received are not fetched, so a thread may have gaps.
+
+use crate::app::object_builders::activity_pub::{note, person};
+use crate::domain::entities::{notes, remote_notes, users};
+use crate::domain::repositories::{
+    FollowingRepository, NotesRepository, RemoteActorsRepository, RemoteNotesRepository,
+    UsersRepository,
+};
+use crate::domain::visibility::Visibility;
+use calmi_activity_streams::types::PUBLIC;
+use chrono::NaiveDateTime;
+use sea_orm::DbErr;
+use serde::Serialize;
+use std::collections::HashSet;
+
+const MAX_ANCESTORS: usize = 64;
+const MAX_DESCENDANTS: usize = 256;
+
+#[derive(Serialize, Clone)]
+pub struct ThreadNote {
+    pub id: String,
+    pub actor: String,
+    pub summary: Option<String>,
+    pub content: String,
+    pub sensitive: bool,
+    pub in_reply_to: Option<String>,
+    pub published: NaiveDateTime,
+    pub deleted: bool,
+    #[serde(skip)]
+    pub public: bool,
+    #[serde(skip)]
+    pub addressed: Vec<String>,
+}
+
+pub fn local_note(base_url: &str, note: notes::Model, author: &users::Model) -> ThreadNote {
+    ThreadNote {
+        id: note::endpoint_uri(base_url, &note, author),
+        actor: person::endpoint_uri(base_url, author),
+        deleted: note.deleted_at.is_some(),
+        public: Visibility::of(&note).is_readable_by_anyone(),
+        addressed: note.to,
+        summary: note.summary,
+        content: note.content,
+        sensitive: note.sensitive,
+        in_reply_to: note.in_reply_to,
+        published: note.created_at,
+    }
+}
+
+fn remote_note(note: remote_notes::Model) -> ThreadNote {
+    let addressed: Vec<String> = note.to.into_iter().chain(note.cc).collect();
+    ThreadNote {
+        public: addressed.iter().any(|to| to == PUBLIC),
+        addressed,
+        id: note.ap_id,
+        actor: note.actor,
+        summary: note.summary,
+        content: note.content,
+        sensitive: note.sensitive,
+        in_reply_to: note.in_reply_to,
+        published: note.published.unwrap_or(note.created_at),
+        deleted: false,
+    }
+}
+
+pub async fn find<T>(storage: &T, base_url: &str, uri: &str) -> Result<Option<ThreadNote>, DbErr>
+where
+    T: NotesRepository + RemoteNotesRepository + UsersRepository,
+{
+    if let Some(id) = note::local_note_id(base_url, uri) {
+        let Some(found) = storage.find_note_by_id(id).await? else {
+            return Ok(None);
+        };
+        let Some(author) = storage.find_user_by_id(found.author_id).await? else {
+            return Ok(None);
+        };
+        let found = local_note(base_url, found, &author);
+        return Ok((found.id == uri).then_some(found));
+    }
+
+    Ok(storage
+        .find_remote_note_by_ap_id(uri)
+        .await?
+        .map(remote_note))
+}
+
+/// Oldest first. Deleted local notes are included, since replies to them still belong to the thread.
+pub async fn replies<T>(storage: &T, base_url: &str, uri: &str) -> Result<Vec<ThreadNote>, DbErr>
+where
+    T: NotesRepository + RemoteNotesRepository + UsersRepository,
+{
+    let mut replies = Vec::new();
+    for reply in storage.list_replies(uri).await? {
+        if let Some(author) = storage.find_user_by_id(reply.author_id).await? {
+            replies.push(local_note(base_url, reply, &author));
+        }
+    }
+    replies.extend(
+        storage
+            .list_remote_notes_in_reply_to(uri)
+            .await?
+            .into_iter()
+            .map(remote_note),
+    );
+    replies.sort_by_key(|reply| reply.published);
+    Ok(replies)
+}
+
+/// Oldest first.
+pub async fn ancestors<T>(
+    storage: &T,
+    base_url: &str,
+    note: &ThreadNote,
+) -> Result<Vec<ThreadNote>, DbErr>
+where
+    T: NotesRepository + RemoteNotesRepository + UsersRepository,
+{
+    let mut ancestors = Vec::new();
+    let mut seen = HashSet::from([note.id.clone()]);
+    let mut parent = note.in_reply_to.clone();
+    while let Some(uri) = parent {
+        if ancestors.len() == MAX_ANCESTORS || !seen.insert(uri.clone()) {
+            break;
+        }
+        let Some(found) = find(storage, base_url, &uri).await? else {
+            break;
+        };
+        parent = found.in_reply_to.clone();
+        ancestors.push(found);
+    }
+    ancestors.reverse();
+    Ok(ancestors)
+}
+
+/// Depth first, each reply followed by its own replies.
+pub async fn descendants<T>(
+    storage: &T,
+    base_url: &str,
+    note: &ThreadNote,
+) -> Result<Vec<ThreadNote>, DbErr>
+where
+    T: NotesRepository + RemoteNotesRepository + UsersRepository,
+{
+    let mut descendants = Vec::new();
+    let mut seen = HashSet::from([note.id.clone()]);
+    let mut pending: Vec<ThreadNote> = replies(storage, base_url, &note.id)
+        .await?
+        .into_iter()
+        .rev()
+        .collect();
+    while let Some(reply) = pending.pop() {
+        if descendants.len() == MAX_DESCENDANTS {
+            break;
+        }
+        if !seen.insert(reply.id.clone()) {
+            continue;
+        }
+        pending.extend(
+            replies(storage, base_url, &reply.id)
+                .await?
+                .into_iter()
+                .rev(),
+        );
+        descendants.push(reply);
+    }
+    Ok(descendants)
+}
+
+/// Deleted notes of others are left out, as their content is gone.
+pub async fn readable_by<T>(
+    storage: &T,
+    base_url: &str,
+    viewer: &users::Model,
+    notes: Vec<ThreadNote>,
+) -> Result<Vec<ThreadNote>, DbErr>
+where
+    T: FollowingRepository + RemoteActorsRepository,
+{
+    let viewer_uri = person::endpoint_uri(base_url, viewer);
+    let mut readable = Vec::with_capacity(notes.len());
+    for note in notes {
+        let is_readable = if note.actor == viewer_uri {
+            true
+        } else if note.deleted {
+            false
+        } else if note.public || note.addressed.contains(&viewer_uri) {
+            true
+        } else {
+            for_followers_of_followed(storage, viewer, &note).await?
+        };
+        if is_readable {
+            readable.push(note);
+        }
+    }
+    Ok(readable)
+}
+
+async fn for_followers_of_followed<T>(
+    storage: &T,
+    viewer: &users::Model,
+    note: &ThreadNote,
+) -> Result<bool, DbErr>
+where
+    T: FollowingRepository + RemoteActorsRepository,
+{
+    let follows = storage
+        .find_following(viewer.id, &note.actor)
+        .await?
+        .is_some_and(|following| !following.pending);
+    if !follows {
+        return Ok(false);
+    }
+    let followers = storage
+        .find_remote_actor_by_uri(&note.actor)
+        .await?
+        .and_then(|actor| actor.followers);
+    Ok(followers.is_some_and(|followers| note.addressed.contains(&followers)))
+}
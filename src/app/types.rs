@@ -1,9 +1,13 @@
 use calmi_activity_streams::types::object::accept::Accept;
 use calmi_activity_streams::types::object::announce::Announce;
 use calmi_activity_streams::types::object::create::Create;
+use calmi_activity_streams::types::object::delete::Delete;
 use calmi_activity_streams::types::object::follow::Follow;
 use calmi_activity_streams::types::object::like::Like;
+use calmi_activity_streams::types::object::reject::Reject;
 use calmi_activity_streams::types::object::undo::Undo;
+use calmi_activity_streams::types::object::update::Update;
+use calmi_activity_streams::types::properties::Actor;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -11,8 +15,27 @@ use serde::Deserialize;
 pub enum InboxActivity {
     Follow(Follow),
     Accept(Accept),
+    Reject(Reject),
     Undo(Undo),
     Create(Create),
+    Delete(Delete),
+    Update(Update),
     Like(Like),
     Announce(Announce),
 }
+
+impl InboxActivity {
+    pub fn actor(&self) -> Option<&Actor> {
+        match self {
+            InboxActivity::Follow(follow) => follow.actor.as_deref(),
+            InboxActivity::Accept(accept) => accept.actor.as_deref(),
+            InboxActivity::Reject(reject) => reject.actor.as_deref(),
+            InboxActivity::Undo(undo) => undo.actor.as_deref(),
+            InboxActivity::Create(create) => create.actor.as_deref(),
+            InboxActivity::Delete(delete) => delete.actor.as_deref(),
+            InboxActivity::Update(update) => update.actor.as_deref(),
+            InboxActivity::Like(like) => like.actor.as_deref(),
+            InboxActivity::Announce(announce) => announce.actor.as_deref(),
+        }
+    }
+}
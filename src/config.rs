@@ -1,13 +1,34 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
 #[derive(Clone)]
 pub struct Config {
     pub domain: String,
     pub base_url: String,
+    /// How long a failing job is retried before it is given up on.
+    pub job_retry_horizon: Duration,
+    /// How long a cached remote actor is used before it is fetched again.
+    pub remote_actor_ttl: Duration,
+    /// Bearer token for the management API under `/api`. The API is closed when unset.
+    pub api_token: Option<String>,
+    /// Scheme for reaching other servers by domain alone, as WebFinger lookups do.
+    pub webfinger_scheme: String,
+    /// Directory uploaded media files are kept in, served under `/media`.
+    pub media_dir: PathBuf,
 }
 
 impl Config {
     pub fn new(domain: String) -> Self {
         let base_url = format!("https://{}", domain);
-        Self { domain, base_url }
+        Self {
+            domain,
+            base_url,
+            job_retry_horizon: Duration::from_secs(2 * 24 * 60 * 60),
+            remote_actor_ttl: Duration::from_secs(24 * 60 * 60),
+            api_token: None,
+            webfinger_scheme: "https".to_string(),
+            media_dir: PathBuf::from("media"),
+        }
     }
 }
 
@@ -13,6 +13,7 @@ pub struct Model {
     #[sea_orm(unique)]
     pub activity_id: String,
     pub created_at: DateTime,
+    pub pending: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
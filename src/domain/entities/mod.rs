@@ -2,8 +2,20 @@
 
 pub mod prelude;
 
+pub mod conversation_messages;
+pub mod conversations;
+pub mod following;
 pub mod follows;
+pub mod jobs;
+pub mod liked;
+pub mod media;
+pub mod mentions;
 pub mod note_announces;
 pub mod note_likes;
+pub mod note_mentions;
+pub mod note_revisions;
+pub mod note_tags;
 pub mod notes;
+pub mod remote_actors;
+pub mod remote_notes;
 pub mod users;
@@ -12,14 +12,35 @@ pub struct Model {
     pub author_id: i64,
     pub created_at: DateTime,
     pub to: Vec<String>,
+    pub deleted_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub in_reply_to: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub context: Option<String>,
+    #[sea_orm(column_type = "Text")]
+    pub visibility: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub summary: Option<String>,
+    pub sensitive: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::conversation_messages::Entity")]
+    ConversationMessages,
+    #[sea_orm(has_many = "super::media::Entity")]
+    Media,
     #[sea_orm(has_many = "super::note_announces::Entity")]
     NoteAnnounces,
     #[sea_orm(has_many = "super::note_likes::Entity")]
     NoteLikes,
+    #[sea_orm(has_many = "super::note_mentions::Entity")]
+    NoteMentions,
+    #[sea_orm(has_many = "super::note_revisions::Entity")]
+    NoteRevisions,
+    #[sea_orm(has_many = "super::note_tags::Entity")]
+    NoteTags,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::AuthorId",
@@ -30,6 +51,18 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::conversation_messages::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ConversationMessages.def()
+    }
+}
+
+impl Related<super::media::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Media.def()
+    }
+}
+
 impl Related<super::note_announces::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::NoteAnnounces.def()
@@ -42,6 +75,24 @@ impl Related<super::note_likes::Entity> for Entity {
     }
 }
 
+impl Related<super::note_mentions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NoteMentions.def()
+    }
+}
+
+impl Related<super::note_revisions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NoteRevisions.def()
+    }
+}
+
+impl Related<super::note_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NoteTags.def()
+    }
+}
+
 impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Users.def()
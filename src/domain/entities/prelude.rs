@@ -1,7 +1,19 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.17
 
+pub use super::conversation_messages::Entity as ConversationMessages;
+pub use super::conversations::Entity as Conversations;
+pub use super::following::Entity as Following;
 pub use super::follows::Entity as Follows;
+pub use super::jobs::Entity as Jobs;
+pub use super::liked::Entity as Liked;
+pub use super::media::Entity as Media;
+pub use super::mentions::Entity as Mentions;
 pub use super::note_announces::Entity as NoteAnnounces;
 pub use super::note_likes::Entity as NoteLikes;
+pub use super::note_mentions::Entity as NoteMentions;
+pub use super::note_revisions::Entity as NoteRevisions;
+pub use super::note_tags::Entity as NoteTags;
 pub use super::notes::Entity as Notes;
+pub use super::remote_actors::Entity as RemoteActors;
+pub use super::remote_notes::Entity as RemoteNotes;
 pub use super::users::Entity as Users;
@@ -10,22 +10,68 @@ pub struct Model {
     #[sea_orm(unique)]
     pub username: String,
     pub display_name: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub public_key_pem: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub private_key_pem: Option<String>,
+    pub manually_approves_followers: bool,
+    pub hide_followers: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::conversations::Entity")]
+    Conversations,
     #[sea_orm(has_many = "super::follows::Entity")]
     Follows,
+    #[sea_orm(has_many = "super::following::Entity")]
+    Following,
+    #[sea_orm(has_many = "super::liked::Entity")]
+    Liked,
+    #[sea_orm(has_many = "super::media::Entity")]
+    Media,
+    #[sea_orm(has_many = "super::mentions::Entity")]
+    Mentions,
     #[sea_orm(has_many = "super::notes::Entity")]
     Notes,
 }
 
+impl Related<super::conversations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Conversations.def()
+    }
+}
+
 impl Related<super::follows::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Follows.def()
     }
 }
 
+impl Related<super::following::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Following.def()
+    }
+}
+
+impl Related<super::liked::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Liked.def()
+    }
+}
+
+impl Related<super::media::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Media.def()
+    }
+}
+
+impl Related<super::mentions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Mentions.def()
+    }
+}
+
 impl Related<super::notes::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Notes.def()
@@ -1,11 +1,33 @@
+pub mod conversations;
+pub mod following;
 pub mod follows;
+pub mod jobs;
+pub mod liked;
+pub mod media;
+pub mod mentions;
 pub mod note_announces;
 pub mod note_likes;
+pub mod note_mentions;
+pub mod note_revisions;
+pub mod note_tags;
 pub mod notes;
+pub mod remote_actors;
+pub mod remote_notes;
 pub mod users;
 
+pub use conversations::ConversationsRepository;
+pub use following::FollowingRepository;
 pub use follows::FollowsRepository;
+pub use jobs::JobsRepository;
+pub use liked::LikedRepository;
+pub use media::MediaRepository;
+pub use mentions::MentionsRepository;
 pub use note_announces::NoteAnnouncesRepository;
 pub use note_likes::NoteLikesRepository;
+pub use note_mentions::NoteMentionsRepository;
+pub use note_revisions::NoteRevisionsRepository;
+pub use note_tags::NoteTagsRepository;
 pub use notes::NotesRepository;
+pub use remote_actors::RemoteActorsRepository;
+pub use remote_notes::RemoteNotesRepository;
 pub use users::UsersRepository;
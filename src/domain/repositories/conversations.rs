@@ -0,0 +1,44 @@
+use crate::domain::entities::{conversation_messages, conversations};
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait ConversationsRepository: Send + Sync {
+    /// `participants` join those the conversation already has. Adding a message twice is harmless.
+    /// Exactly one of `note_id` and `remote_note_id` is set.
+    async fn add_conversation_message(
+        &self,
+        user_id: i64,
+        context: &str,
+        participants: &[String],
+        note: &str,
+        note_id: Option<i64>,
+        remote_note_id: Option<i64>,
+    ) -> Result<conversations::Model, DbErr>;
+
+    async fn find_conversation(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<Option<conversations::Model>, DbErr>;
+
+    async fn find_conversation_by_message(
+        &self,
+        user_id: i64,
+        note: &str,
+    ) -> Result<Option<conversations::Model>, DbErr>;
+
+    /// Most recently active first.
+    async fn list_conversations_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<conversations::Model>, DbErr>;
+
+    /// In the order they arrived.
+    async fn list_conversation_messages(
+        &self,
+        conversation_id: i64,
+    ) -> Result<Vec<conversation_messages::Model>, DbErr>;
+}
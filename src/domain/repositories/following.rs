@@ -0,0 +1,44 @@
+use crate::domain::entities::following;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait FollowingRepository: Send + Sync {
+    /// Records a Follow we sent; it stays pending until the remote actor accepts it.
+    async fn add_following(
+        &self,
+        user_id: i64,
+        actor: &str,
+        activity_id: &str,
+    ) -> Result<following::Model, DbErr>;
+
+    async fn accept_following(&self, id: i64) -> Result<(), DbErr>;
+
+    async fn remove_following(&self, id: i64) -> Result<u64, DbErr>;
+
+    async fn find_following_by_id(&self, id: i64) -> Result<Option<following::Model>, DbErr>;
+
+    async fn find_following_by_activity_id(
+        &self,
+        activity_id: &str,
+    ) -> Result<Option<following::Model>, DbErr>;
+
+    async fn find_following(
+        &self,
+        user_id: i64,
+        actor: &str,
+    ) -> Result<Option<following::Model>, DbErr>;
+
+    /// Both accepted and pending follows.
+    async fn list_following(&self, user_id: i64) -> Result<Vec<following::Model>, DbErr>;
+
+    /// Accepted follows only, newest first.
+    async fn list_following_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<following::Model>, DbErr>;
+
+    async fn count_following(&self, user_id: i64) -> Result<u64, DbErr>;
+}
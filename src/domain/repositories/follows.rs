@@ -4,19 +4,53 @@ use sea_orm::DbErr;
 
 #[async_trait]
 pub trait FollowsRepository: Send + Sync {
-    async fn add_follow(&self, user_id: i64, actor: &str, activity_id: &str) -> Result<(), DbErr>;
+    /// Records a follow; `pending` ones wait for the user to approve them.
+    async fn add_follow(
+        &self,
+        user_id: i64,
+        actor: &str,
+        activity_id: &str,
+        pending: bool,
+    ) -> Result<(), DbErr>;
+
+    async fn approve_follow(&self, id: i64) -> Result<(), DbErr>;
 
-    async fn remove_follow_by_activity_id(&self, activity_id: &str) -> Result<u64, DbErr>;
+    async fn remove_follow_by_id(&self, id: i64) -> Result<u64, DbErr>;
+
+    /// Only removes the record if `actor` made it.
+    async fn remove_follow_by_activity_id(
+        &self,
+        activity_id: &str,
+        actor: &str,
+    ) -> Result<u64, DbErr>;
 
     async fn remove_follow(&self, user_id: i64, actor: &str) -> Result<u64, DbErr>;
 
+    /// Removes the actor from every local user's followers, pending or not.
+    async fn remove_follows_by_actor(&self, actor: &str) -> Result<u64, DbErr>;
+
     async fn find_follow_by_activity_id(
         &self,
         activity_id: &str,
     ) -> Result<Option<follows::Model>, DbErr>;
 
+    async fn find_follow_by_id(&self, id: i64) -> Result<Option<follows::Model>, DbErr>;
+
     async fn find_follow(&self, user_id: i64, actor: &str)
     -> Result<Option<follows::Model>, DbErr>;
 
+    /// Accepted followers only.
     async fn list_followers(&self, user_id: i64) -> Result<Vec<follows::Model>, DbErr>;
+
+    /// Accepted followers only, newest first.
+    async fn list_followers_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<follows::Model>, DbErr>;
+
+    async fn count_followers(&self, user_id: i64) -> Result<u64, DbErr>;
+
+    async fn list_follow_requests(&self, user_id: i64) -> Result<Vec<follows::Model>, DbErr>;
 }
@@ -0,0 +1,31 @@
+use crate::domain::entities::jobs;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait JobsRepository: Send + Sync {
+    async fn enqueue_job(
+        &self,
+        kind: &str,
+        payload: serde_json::Value,
+    ) -> Result<jobs::Model, DbErr>;
+
+    /// Locks up to `limit` due jobs until `locked_until`, so that other workers skip them.
+    async fn claim_due_jobs(
+        &self,
+        limit: u64,
+        locked_until: NaiveDateTime,
+    ) -> Result<Vec<jobs::Model>, DbErr>;
+
+    async fn complete_job(&self, id: i64) -> Result<(), DbErr>;
+
+    async fn retry_job(&self, id: i64, run_at: NaiveDateTime, error: &str) -> Result<(), DbErr>;
+
+    /// Gives up on a job. The row is kept for inspection but never claimed again.
+    async fn fail_job(&self, id: i64, error: &str) -> Result<(), DbErr>;
+
+    async fn find_job_by_id(&self, id: i64) -> Result<Option<jobs::Model>, DbErr>;
+
+    async fn list_jobs(&self, kind: &str) -> Result<Vec<jobs::Model>, DbErr>;
+}
@@ -0,0 +1,31 @@
+use crate::domain::entities::liked;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait LikedRepository: Send + Sync {
+    /// Records a Like sent by a local user for `object`, authored by `actor`.
+    async fn add_liked(
+        &self,
+        user_id: i64,
+        object: &str,
+        actor: &str,
+        activity_id: &str,
+    ) -> Result<liked::Model, DbErr>;
+
+    async fn remove_liked(&self, id: i64) -> Result<u64, DbErr>;
+
+    async fn find_liked_by_id(&self, id: i64) -> Result<Option<liked::Model>, DbErr>;
+
+    async fn find_liked(&self, user_id: i64, object: &str) -> Result<Option<liked::Model>, DbErr>;
+
+    /// Newest first.
+    async fn list_liked_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<liked::Model>, DbErr>;
+
+    async fn count_liked(&self, user_id: i64) -> Result<u64, DbErr>;
+}
@@ -0,0 +1,31 @@
+use crate::domain::entities::media;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait MediaRepository: Send + Sync {
+    async fn add_media(&self, media: media::ActiveModel) -> Result<media::Model, DbErr>;
+
+    async fn update_media(&self, media: media::ActiveModel) -> Result<media::Model, DbErr>;
+
+    async fn find_media_by_id(&self, id: i64) -> Result<Option<media::Model>, DbErr>;
+
+    /// The upload stored as `file_name`, or whose thumbnail is.
+    async fn find_media_by_file_name(&self, file_name: &str)
+    -> Result<Option<media::Model>, DbErr>;
+
+    /// In the order they were uploaded.
+    async fn list_note_media(&self, note_id: i64) -> Result<Vec<media::Model>, DbErr>;
+
+    async fn list_note_media_of_notes(&self, note_ids: &[i64]) -> Result<Vec<media::Model>, DbErr>;
+
+    /// In the order the note lists them.
+    async fn list_remote_note_media(&self, remote_note_id: i64)
+    -> Result<Vec<media::Model>, DbErr>;
+
+    async fn set_remote_note_media(
+        &self,
+        remote_note_id: i64,
+        media: Vec<media::ActiveModel>,
+    ) -> Result<(), DbErr>;
+}
@@ -0,0 +1,17 @@
+use crate::domain::entities::mentions;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait MentionsRepository: Send + Sync {
+    /// Returns false when it was already recorded.
+    async fn add_mention(&self, user_id: i64, note: &str, actor: &str) -> Result<bool, DbErr>;
+
+    /// Newest first.
+    async fn list_mentions_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<mentions::Model>, DbErr>;
+}
@@ -7,10 +7,17 @@ pub trait NoteAnnouncesRepository: Send + Sync {
     async fn add_announce(&self, note_id: i64, actor: &str, activity_id: &str)
     -> Result<(), DbErr>;
 
-    async fn remove_announce_by_activity_id(&self, activity_id: &str) -> Result<u64, DbErr>;
+    /// Only removes the record if `actor` made it.
+    async fn remove_announce_by_activity_id(
+        &self,
+        activity_id: &str,
+        actor: &str,
+    ) -> Result<u64, DbErr>;
 
     async fn remove_announce(&self, note_id: i64, actor: &str) -> Result<u64, DbErr>;
 
+    async fn remove_announces_by_actor(&self, actor: &str) -> Result<u64, DbErr>;
+
     async fn find_announce_by_activity_id(
         &self,
         activity_id: &str,
@@ -23,4 +30,14 @@ pub trait NoteAnnouncesRepository: Send + Sync {
     ) -> Result<Option<note_announces::Model>, DbErr>;
 
     async fn list_announces(&self, note_id: i64) -> Result<Vec<note_announces::Model>, DbErr>;
+
+    /// Newest first.
+    async fn list_announces_page(
+        &self,
+        note_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<note_announces::Model>, DbErr>;
+
+    async fn count_announces(&self, note_id: i64) -> Result<u64, DbErr>;
 }
@@ -6,10 +6,17 @@ use sea_orm::DbErr;
 pub trait NoteLikesRepository: Send + Sync {
     async fn add_like(&self, note_id: i64, actor: &str, activity_id: &str) -> Result<(), DbErr>;
 
-    async fn remove_like_by_activity_id(&self, activity_id: &str) -> Result<u64, DbErr>;
+    /// Only removes the record if `actor` made it.
+    async fn remove_like_by_activity_id(
+        &self,
+        activity_id: &str,
+        actor: &str,
+    ) -> Result<u64, DbErr>;
 
     async fn remove_like(&self, note_id: i64, actor: &str) -> Result<u64, DbErr>;
 
+    async fn remove_likes_by_actor(&self, actor: &str) -> Result<u64, DbErr>;
+
     async fn find_like_by_activity_id(
         &self,
         activity_id: &str,
@@ -22,4 +29,14 @@ pub trait NoteLikesRepository: Send + Sync {
     ) -> Result<Option<note_likes::Model>, DbErr>;
 
     async fn list_likes(&self, note_id: i64) -> Result<Vec<note_likes::Model>, DbErr>;
+
+    /// Newest first.
+    async fn list_likes_page(
+        &self,
+        note_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<note_likes::Model>, DbErr>;
+
+    async fn count_likes(&self, note_id: i64) -> Result<u64, DbErr>;
 }
@@ -0,0 +1,24 @@
+use crate::domain::entities::note_mentions;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait NoteMentionsRepository: Send + Sync {
+    async fn add_note_mention(
+        &self,
+        note_id: i64,
+        href: &str,
+        name: &str,
+    ) -> Result<note_mentions::Model, DbErr>;
+
+    async fn remove_note_mentions(&self, note_id: i64) -> Result<(), DbErr>;
+
+    /// In the order they were added.
+    async fn list_note_mentions(&self, note_id: i64) -> Result<Vec<note_mentions::Model>, DbErr>;
+
+    /// In the order they were added.
+    async fn list_note_mentions_of_notes(
+        &self,
+        note_ids: &[i64],
+    ) -> Result<Vec<note_mentions::Model>, DbErr>;
+}
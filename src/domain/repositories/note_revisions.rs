@@ -0,0 +1,9 @@
+use crate::domain::entities::note_revisions;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait NoteRevisionsRepository: Send + Sync {
+    /// Oldest first.
+    async fn list_note_revisions(&self, note_id: i64) -> Result<Vec<note_revisions::Model>, DbErr>;
+}
@@ -0,0 +1,35 @@
+use crate::domain::entities::note_tags;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait NoteTagsRepository: Send + Sync {
+    async fn set_note_tags(&self, note_id: i64, note: &str, names: &[String]) -> Result<(), DbErr>;
+
+    async fn set_remote_note_tags(
+        &self,
+        remote_note_id: i64,
+        note: &str,
+        names: &[String],
+    ) -> Result<(), DbErr>;
+
+    /// In the order they appear in the note.
+    async fn list_note_tags(&self, note_id: i64) -> Result<Vec<note_tags::Model>, DbErr>;
+
+    /// In the order they appear in each note.
+    async fn list_note_tags_of_notes(
+        &self,
+        note_ids: &[i64],
+    ) -> Result<Vec<note_tags::Model>, DbErr>;
+
+    /// Local and remote notes alike.
+    async fn count_tagged_notes(&self, name: &str) -> Result<u64, DbErr>;
+
+    /// Most recently tagged first.
+    async fn list_tagged_notes_page(
+        &self,
+        name: &str,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<note_tags::Model>, DbErr>;
+}
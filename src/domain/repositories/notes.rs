@@ -2,22 +2,64 @@ use crate::domain::entities::notes;
 use async_trait::async_trait;
 use sea_orm::DbErr;
 
+pub struct NoteRelations {
+    /// The actor URI of the author.
+    pub author: String,
+    /// The `href` and `name` of each account the note mentions.
+    pub mentions: Vec<(String, String)>,
+    pub mentioned_users: Vec<i64>,
+    pub tags: Vec<String>,
+    /// Uploads of the author; adding the note fails if any cannot be attached.
+    pub media_ids: Vec<i64>,
+    pub direct_messages: Vec<DirectMessage>,
+}
+
+/// A direct note as filed under a conversation of the local user `user_id`.
+pub struct DirectMessage {
+    pub user_id: i64,
+    /// `None` starts a conversation with the note.
+    pub context: Option<String>,
+    pub participants: Vec<String>,
+}
+
 #[async_trait]
 pub trait NotesRepository: Send + Sync {
+    /// Also finds deleted notes, so that their tombstone can be served.
     async fn find_note_by_id(&self, id: i64) -> Result<Option<notes::Model>, DbErr>;
-    async fn find_note_by_author_id(
+    /// Public and unlisted notes of the author older than `before`, newest first.
+    /// Notes are ordered by `created_at`, then `id`; `None` starts from the newest note.
+    async fn list_notes_by_author_before(
         &self,
         author_id: i64,
+        before: Option<&notes::Model>,
         limit: u64,
-        offset: u64,
     ) -> Result<Vec<notes::Model>, DbErr>;
-    async fn add_note(
+    /// Public and unlisted notes of the author newer than `after`, oldest first.
+    /// `None` starts from the oldest note.
+    async fn list_notes_by_author_after(
         &self,
-        content: &str,
         author_id: i64,
-        to: Vec<String>,
+        after: Option<&notes::Model>,
+        limit: u64,
+    ) -> Result<Vec<notes::Model>, DbErr>;
+    async fn count_notes_by_author(&self, author_id: i64) -> Result<u64, DbErr>;
+    async fn add_note(&self, note: notes::ActiveModel) -> Result<notes::Model, DbErr>;
+    /// Adds a note written here together with what it carries, all or nothing.
+    /// `uri` gives the URI of the note from its id.
+    async fn add_note_with_relations(
+        &self,
+        note: notes::ActiveModel,
+        relations: NoteRelations,
+        uri: &(dyn Fn(i64) -> String + Send + Sync),
     ) -> Result<notes::Model, DbErr>;
+    /// Oldest first.
+    /// Deleted replies are listed too, so that the notes answering them can still be reached.
+    async fn list_replies(&self, in_reply_to: &str) -> Result<Vec<notes::Model>, DbErr>;
     async fn update_note(&self, note: notes::ActiveModel) -> Result<notes::Model, DbErr>;
+    /// Updates the note and keeps the version it replaces as a revision.
+    async fn edit_note(&self, note: notes::ActiveModel) -> Result<notes::Model, DbErr>;
+    /// Clears the content and content warning and marks the note deleted;
+    /// the row stays behind as a tombstone and the last content as a revision.
     async fn delete_note(&self, id: i64) -> Result<(), DbErr>;
     async fn list_note(&self, limit: u64, offset: u64) -> Result<Vec<notes::Model>, DbErr>;
 }
@@ -0,0 +1,24 @@
+use crate::domain::entities::remote_actors;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait RemoteActorsRepository: Send + Sync {
+    async fn find_remote_actor_by_uri(
+        &self,
+        uri: &str,
+    ) -> Result<Option<remote_actors::Model>, DbErr>;
+
+    async fn find_remote_actor_by_public_key_id(
+        &self,
+        key_id: &str,
+    ) -> Result<Option<remote_actors::Model>, DbErr>;
+
+    /// Inserts the actor, or overwrites the cached copy with the same `uri`.
+    async fn upsert_remote_actor(
+        &self,
+        actor: remote_actors::ActiveModel,
+    ) -> Result<remote_actors::Model, DbErr>;
+
+    async fn remove_remote_actor_by_uri(&self, uri: &str) -> Result<u64, DbErr>;
+}
@@ -0,0 +1,34 @@
+use crate::domain::entities::remote_notes;
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait RemoteNotesRepository: Send + Sync {
+    /// Stores a note unless one with the same `ap_id` exists. Returns whether it was new.
+    async fn add_remote_note(&self, note: remote_notes::ActiveModel) -> Result<bool, DbErr>;
+
+    async fn update_remote_note(
+        &self,
+        note: remote_notes::ActiveModel,
+    ) -> Result<remote_notes::Model, DbErr>;
+
+    async fn remove_remote_note(&self, id: i64) -> Result<u64, DbErr>;
+
+    async fn remove_remote_notes_by_actor(&self, actor: &str) -> Result<u64, DbErr>;
+
+    async fn find_remote_note_by_ap_id(
+        &self,
+        ap_id: &str,
+    ) -> Result<Option<remote_notes::Model>, DbErr>;
+
+    async fn list_remote_notes_in_reply_to(
+        &self,
+        in_reply_to: &str,
+    ) -> Result<Vec<remote_notes::Model>, DbErr>;
+
+    async fn list_remote_notes_by_actor(
+        &self,
+        actor: &str,
+        limit: u64,
+    ) -> Result<Vec<remote_notes::Model>, DbErr>;
+}
@@ -10,4 +10,5 @@ pub trait UsersRepository: Send + Sync {
     async fn update_user(&self, user: users::ActiveModel) -> Result<users::Model, DbErr>;
     async fn delete_user(&self, id: i64) -> Result<(), DbErr>;
     async fn list_user(&self, limit: u64, offset: u64) -> Result<Vec<users::Model>, DbErr>;
+    async fn list_users_without_keys(&self) -> Result<Vec<users::Model>, DbErr>;
 }
@@ -0,0 +1,40 @@
+use crate::domain::entities::notes;
+use serde::{Deserialize, Serialize};
+
+/// Who may see a note.
+/// https://docs.joinmastodon.org/spec/activitypub/#to-cc
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    #[default]
+    Public,
+    /// Readable by anyone, but kept out of public listings: `as:Public` is only in `cc`.
+    Unlisted,
+    Followers,
+    Direct,
+}
+
+impl Visibility {
+    /// Unknown values are taken as the most private.
+    pub fn of(note: &notes::Model) -> Self {
+        match note.visibility.as_str() {
+            "public" => Visibility::Public,
+            "unlisted" => Visibility::Unlisted,
+            "followers" => Visibility::Followers,
+            _ => Visibility::Direct,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Unlisted => "unlisted",
+            Visibility::Followers => "followers",
+            Visibility::Direct => "direct",
+        }
+    }
+
+    pub fn is_readable_by_anyone(self) -> bool {
+        matches!(self, Visibility::Public | Visibility::Unlisted)
+    }
+}
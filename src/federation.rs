@@ -0,0 +1,28 @@
+// Server-to-server concerns that are not tied to a single HTTP endpoint.
+// https://www.w3.org/TR/activitypub/#server-to-server-interactions
+
+pub mod actor;
+pub mod http_signature;
+pub mod keys;
+pub mod note;
+pub mod public_key;
+pub mod webfinger;
+
+use std::time::Duration;
+
+/// HTTP client shared by everything that talks to remote servers.
+pub fn build_http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .user_agent(concat!("calmi/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(10))
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
+/// Whether both URIs are served by the same scheme, host and port.
+pub fn same_origin(a: &str, b: &str) -> bool {
+    match (reqwest::Url::parse(a), reqwest::Url::parse(b)) {
+        (Ok(a), Ok(b)) => a.origin() == b.origin(),
+        _ => false,
+    }
+}
@@ -0,0 +1,155 @@
+use crate::domain::entities::remote_actors;
+use crate::domain::repositories::RemoteActorsRepository;
+use calmi_activity_streams::types::enums::{
+    ImageOrLinkOrStringUrl, LinkOrStringUrl, ObjectOrLinkOrStringUrl, SingleOrMultiple,
+};
+use calmi_activity_streams::types::object::person::Person;
+use chrono::Utc;
+use sea_orm::ActiveValue;
+use std::time::Duration;
+
+/// Dereferences remote actors and keeps a copy of each in `remote_actors`.
+#[derive(Clone)]
+pub struct ActorResolver {
+    client: reqwest::Client,
+    ttl: Duration,
+}
+
+impl ActorResolver {
+    /// Cached actors older than `ttl` are fetched again on their next use.
+    pub fn new(client: reqwest::Client, ttl: Duration) -> Self {
+        Self { client, ttl }
+    }
+
+    pub async fn resolve<T: RemoteActorsRepository>(
+        &self,
+        storage: &T,
+        uri: &str,
+    ) -> Result<remote_actors::Model, String> {
+        let cached = storage
+            .find_remote_actor_by_uri(uri)
+            .await
+            .map_err(|e| format!("Failed to load actor {}: {}", uri, e))?;
+        let Some(cached) = cached else {
+            return self.refresh(storage, uri).await;
+        };
+
+        let age = (Utc::now().naive_utc() - cached.fetched_at)
+            .to_std()
+            .unwrap_or_default();
+        if age < self.ttl {
+            return Ok(cached);
+        }
+
+        // A stale copy is still better than nothing while the remote is unreachable.
+        match self.refresh(storage, uri).await {
+            Ok(actor) => Ok(actor),
+            Err(err) => {
+                eprintln!("Using stale copy of {}: {}", uri, err);
+                Ok(cached)
+            }
+        }
+    }
+
+    /// Fetches the actor regardless of the cached copy.
+    pub async fn refresh<T: RemoteActorsRepository>(
+        &self,
+        storage: &T,
+        uri: &str,
+    ) -> Result<remote_actors::Model, String> {
+        let person = fetch_person(&self.client, uri).await?;
+        store_person(storage, uri, &person).await
+    }
+}
+
+pub async fn fetch_person(client: &reqwest::Client, uri: &str) -> Result<Person, String> {
+    let response = client
+        .get(uri)
+        .header(reqwest::header::ACCEPT, "application/activity+json")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", uri, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Fetching {} returned {}", uri, response.status()));
+    }
+    let person = response
+        .json::<Person>()
+        .await
+        .map_err(|e| format!("Failed to parse actor {}: {}", uri, e))?;
+
+    if person.id.as_deref() != Some(uri) {
+        return Err(format!(
+            "Actor id mismatch: requested {}, received {:?}",
+            uri, person.id
+        ));
+    }
+    Ok(person)
+}
+
+/// Caches `person` as the current document of the actor at `uri`.
+pub async fn store_person<T: RemoteActorsRepository>(
+    storage: &T,
+    uri: &str,
+    person: &Person,
+) -> Result<remote_actors::Model, String> {
+    let inbox = person
+        .inbox
+        .as_deref()
+        .and_then(url_of_object)
+        .ok_or_else(|| format!("Actor {} has no inbox", uri))?;
+    let public_key = person.public_key.as_ref().filter(|key| key.owner == uri);
+
+    let actor = remote_actors::ActiveModel {
+        id: ActiveValue::NotSet,
+        uri: ActiveValue::Set(uri.to_string()),
+        inbox: ActiveValue::Set(inbox),
+        shared_inbox: ActiveValue::Set(
+            person
+                .endpoints
+                .as_ref()
+                .and_then(|endpoints| endpoints.shared_inbox.clone()),
+        ),
+        public_key_id: ActiveValue::Set(public_key.map(|key| key.id.clone())),
+        public_key_pem: ActiveValue::Set(public_key.map(|key| key.public_key_pem.clone())),
+        preferred_username: ActiveValue::Set(person.preferred_username.clone()),
+        name: ActiveValue::Set(person.name.clone()),
+        icon_url: ActiveValue::Set(person.icon.as_deref().and_then(icon_url)),
+        followers: ActiveValue::Set(person.followers.as_deref().and_then(url_of_object)),
+        fetched_at: ActiveValue::Set(Utc::now().naive_utc()),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+    };
+    storage
+        .upsert_remote_actor(actor)
+        .await
+        .map_err(|e| format!("Failed to store actor {}: {}", uri, e))
+}
+
+fn url_of_object(value: &ObjectOrLinkOrStringUrl) -> Option<String> {
+    match value {
+        ObjectOrLinkOrStringUrl::Str(url) => Some(url.clone()),
+        ObjectOrLinkOrStringUrl::Link(link) => link.href.clone(),
+        ObjectOrLinkOrStringUrl::Object(_) => None,
+    }
+}
+
+/// The first usable URL among the actor's icons.
+fn icon_url(icon: &SingleOrMultiple<ImageOrLinkOrStringUrl>) -> Option<String> {
+    let icons = match icon {
+        SingleOrMultiple::Single(icon) => std::slice::from_ref(icon),
+        SingleOrMultiple::Multiple(icons) => icons.as_slice(),
+    };
+    icons.iter().find_map(|icon| match icon {
+        ImageOrLinkOrStringUrl::Str(url) => Some(url.clone()),
+        ImageOrLinkOrStringUrl::Link(link) => link.href.clone(),
+        ImageOrLinkOrStringUrl::Image(image) => {
+            let urls = match image.url.as_deref()? {
+                SingleOrMultiple::Single(url) => std::slice::from_ref(url),
+                SingleOrMultiple::Multiple(urls) => urls.as_slice(),
+            };
+            urls.iter().find_map(|url| match url {
+                LinkOrStringUrl::Str(url) => Some(url.clone()),
+                LinkOrStringUrl::Link(link) => link.href.clone(),
+            })
+        }
+    })
+}
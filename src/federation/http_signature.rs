@@ -0,0 +1,281 @@
+// HTTP Signatures as deployed across the fediverse.
+// https://datatracker.ietf.org/doc/html/draft-cavage-http-signatures-12
+// https://docs.joinmastodon.org/spec/security/#http
+
+use crate::domain::repositories::RemoteActorsRepository;
+use crate::federation::public_key::PublicKeyCache;
+use axum::http::{HeaderMap, Method};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Utc};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs1v15::{Signature, SigningKey, VerifyingKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use sha2::{Digest, Sha256};
+
+/// How far the `Date` header may drift from our clock, in either direction.
+pub const MAX_DATE_SKEW_SECONDS: i64 = 60 * 60;
+
+/// Headers covered by signatures we produce.
+const SIGNED_HEADERS_WITH_BODY: [&str; 4] = ["(request-target)", "host", "date", "digest"];
+const SIGNED_HEADERS_WITHOUT_BODY: [&str; 3] = ["(request-target)", "host", "date"];
+
+/// Parameters of a `Signature` header.
+pub struct SignatureParams {
+    pub key_id: String,
+    pub algorithm: Option<String>,
+    pub headers: Vec<String>,
+    pub signature: Vec<u8>,
+}
+
+/// Header values to attach to an outgoing request, alongside `Host`.
+pub struct SignedHeaders {
+    pub date: String,
+    pub digest: Option<String>,
+    pub signature: String,
+}
+
+/// Verifies the signature of an incoming request and returns the owner of the signing key.
+pub async fn verify_request<T: RemoteActorsRepository>(
+    keys: &PublicKeyCache,
+    storage: &T,
+    method: &Method,
+    path_and_query: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<String, String> {
+    let header = header_value(headers, "signature")?
+        .ok_or_else(|| "Missing Signature header".to_string())?;
+    let params = parse_signature_header(&header)?;
+
+    match params.algorithm.as_deref() {
+        None | Some("rsa-sha256") | Some("hs2019") => {}
+        Some(other) => return Err(format!("Unsupported signature algorithm: {}", other)),
+    }
+
+    let mut required = vec!["(request-target)", "host", "date"];
+    if *method == Method::POST {
+        required.push("digest");
+    }
+    for name in required {
+        if !params.headers.iter().any(|h| h == name) {
+            return Err(format!("Signature does not cover {}", name));
+        }
+    }
+
+    verify_date(headers, Utc::now())?;
+    if params.headers.iter().any(|h| h == "digest") {
+        verify_digest(headers, body)?;
+    }
+
+    let signing_string = build_signing_string(method, path_and_query, headers, &params.headers)?;
+
+    let key = keys.get(storage, &params.key_id).await?;
+    if verify_signature(&signing_string, &params.signature, &key.pem).is_ok() {
+        return Ok(key.owner);
+    }
+
+    // The actor may have rotated its key since we cached it.
+    let key = keys.refresh(storage, &params.key_id).await?;
+    verify_signature(&signing_string, &params.signature, &key.pem)?;
+    Ok(key.owner)
+}
+
+/// Signs an outgoing request with the given PKCS#8 private key.
+/// A `Digest` is produced and signed when `body` is present.
+pub fn sign_request(
+    method: &Method,
+    path_and_query: &str,
+    host: &str,
+    body: Option<&[u8]>,
+    key_id: &str,
+    private_key_pem: &str,
+) -> Result<SignedHeaders, String> {
+    let date = format_http_date(Utc::now());
+    let digest = body.map(digest_header);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("host", to_header_value(host)?);
+    headers.insert("date", to_header_value(&date)?);
+    let signed_headers: &[&str] = if let Some(digest) = &digest {
+        headers.insert("digest", to_header_value(digest)?);
+        &SIGNED_HEADERS_WITH_BODY
+    } else {
+        &SIGNED_HEADERS_WITHOUT_BODY
+    };
+    let signed_headers: Vec<String> = signed_headers.iter().map(|h| h.to_string()).collect();
+
+    let signing_string = build_signing_string(method, path_and_query, &headers, &signed_headers)?;
+
+    let private_key = RsaPrivateKey::from_pkcs8_pem(private_key_pem)
+        .map_err(|e| format!("Invalid private key: {}", e))?;
+    let signing_key = SigningKey::<Sha256>::new(private_key);
+    let signature = signing_key
+        .try_sign(signing_string.as_bytes())
+        .map_err(|e| format!("Failed to sign request: {}", e))?;
+
+    let signature = format!(
+        r#"keyId="{}",algorithm="rsa-sha256",headers="{}",signature="{}""#,
+        key_id,
+        signed_headers.join(" "),
+        BASE64.encode(signature.to_bytes())
+    );
+
+    Ok(SignedHeaders {
+        date,
+        digest,
+        signature,
+    })
+}
+
+pub fn parse_signature_header(header: &str) -> Result<SignatureParams, String> {
+    let mut key_id = None;
+    let mut algorithm = None;
+    let mut headers = None;
+    let mut signature = None;
+
+    for part in header.split(',') {
+        let (name, value) = part
+            .trim()
+            .split_once('=')
+            .ok_or_else(|| format!("Malformed signature parameter: {}", part))?;
+        let value = value.trim().trim_matches('"').to_string();
+        match name.trim() {
+            "keyId" => key_id = Some(value),
+            "algorithm" => algorithm = Some(value.to_ascii_lowercase()),
+            "headers" => headers = Some(value),
+            "signature" => signature = Some(value),
+            _ => {}
+        }
+    }
+
+    let signature = BASE64
+        .decode(signature.ok_or_else(|| "Signature parameter missing".to_string())?)
+        .map_err(|e| format!("Signature is not valid base64: {}", e))?;
+
+    Ok(SignatureParams {
+        key_id: key_id.ok_or_else(|| "keyId parameter missing".to_string())?,
+        algorithm,
+        // The specification defaults to `date` when `headers` is omitted.
+        headers: headers
+            .unwrap_or_else(|| "date".to_string())
+            .split_whitespace()
+            .map(|h| h.to_ascii_lowercase())
+            .collect(),
+        signature,
+    })
+}
+
+pub fn build_signing_string(
+    method: &Method,
+    path_and_query: &str,
+    headers: &HeaderMap,
+    signed_headers: &[String],
+) -> Result<String, String> {
+    let mut lines = Vec::with_capacity(signed_headers.len());
+    for name in signed_headers {
+        if name == "(request-target)" {
+            lines.push(format!(
+                "(request-target): {} {}",
+                method.as_str().to_ascii_lowercase(),
+                path_and_query
+            ));
+            continue;
+        }
+
+        let values: Vec<&str> = headers
+            .get_all(name.as_str())
+            .iter()
+            .map(|v| v.to_str().map(str::trim))
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("Header {} is not valid ASCII", name))?;
+        if values.is_empty() {
+            return Err(format!(
+                "Signed header {} is missing from the request",
+                name
+            ));
+        }
+        lines.push(format!("{}: {}", name, values.join(", ")));
+    }
+    Ok(lines.join("\n"))
+}
+
+pub fn digest_header(body: &[u8]) -> String {
+    format!("SHA-256={}", BASE64.encode(Sha256::digest(body)))
+}
+
+pub fn verify_digest(headers: &HeaderMap, body: &[u8]) -> Result<(), String> {
+    let header =
+        header_value(headers, "digest")?.ok_or_else(|| "Missing Digest header".to_string())?;
+    let expected = BASE64.encode(Sha256::digest(body));
+
+    // A Digest header may list several algorithms; SHA-256 is the one we understand.
+    let matches = header.split(',').any(|entry| {
+        entry
+            .trim()
+            .split_once('=')
+            .is_some_and(|(algorithm, value)| {
+                algorithm.eq_ignore_ascii_case("sha-256") && value == expected
+            })
+    });
+
+    if matches {
+        Ok(())
+    } else {
+        Err("Digest does not match request body".to_string())
+    }
+}
+
+pub fn verify_date(headers: &HeaderMap, now: DateTime<Utc>) -> Result<(), String> {
+    let header = header_value(headers, "date")?.ok_or_else(|| "Missing Date header".to_string())?;
+    let date = DateTime::parse_from_rfc2822(&header)
+        .map_err(|e| format!("Invalid Date header {}: {}", header, e))?;
+
+    let skew = (now - date.with_timezone(&Utc)).num_seconds().abs();
+    if skew > MAX_DATE_SKEW_SECONDS {
+        return Err(format!(
+            "Date header {} is outside the allowed window",
+            header
+        ));
+    }
+    Ok(())
+}
+
+pub fn verify_signature(
+    signing_string: &str,
+    signature: &[u8],
+    public_key_pem: &str,
+) -> Result<(), String> {
+    let public_key = RsaPublicKey::from_public_key_pem(public_key_pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(public_key_pem))
+        .map_err(|e| format!("Invalid public key: {}", e))?;
+    let signature =
+        Signature::try_from(signature).map_err(|e| format!("Malformed signature: {}", e))?;
+
+    VerifyingKey::<Sha256>::new(public_key)
+        .verify(signing_string.as_bytes(), &signature)
+        .map_err(|_| "Signature verification failed".to_string())
+}
+
+pub fn format_http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Result<Option<String>, String> {
+    headers
+        .get(name)
+        .map(|value| {
+            value
+                .to_str()
+                .map(|s| s.to_string())
+                .map_err(|_| format!("Header {} is not valid ASCII", name))
+        })
+        .transpose()
+}
+
+fn to_header_value(value: &str) -> Result<axum::http::HeaderValue, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid header value: {}", value))
+}
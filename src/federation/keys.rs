@@ -0,0 +1,30 @@
+use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::{RsaPrivateKey, rand_core::OsRng};
+
+/// Key size used by Mastodon and most other implementations.
+const KEY_BITS: usize = 2048;
+
+/// PEM-encoded RSA keypair: SPKI for the public half, PKCS#8 for the private half.
+pub struct Keypair {
+    pub public_key_pem: String,
+    pub private_key_pem: String,
+}
+
+/// Generates a keypair for signing requests. This is CPU heavy, so call it off the async runtime.
+pub fn generate_keypair() -> Result<Keypair, String> {
+    let private_key = RsaPrivateKey::new(&mut OsRng, KEY_BITS)
+        .map_err(|e| format!("Failed to generate RSA key: {}", e))?;
+    let private_key_pem = private_key
+        .to_pkcs8_pem(LineEnding::LF)
+        .map_err(|e| format!("Failed to encode private key: {}", e))?
+        .to_string();
+    let public_key_pem = private_key
+        .to_public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|e| format!("Failed to encode public key: {}", e))?;
+
+    Ok(Keypair {
+        public_key_pem,
+        private_key_pem,
+    })
+}
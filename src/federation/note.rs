@@ -0,0 +1,129 @@
+use crate::domain::entities::remote_notes;
+use crate::domain::repositories::RemoteNotesRepository;
+use calmi_activity_streams::types::enums::{
+    ObjectBased, ObjectOrLinkOrStringUrl, SingleOrMultiple,
+};
+use calmi_activity_streams::types::object::note::Note;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sea_orm::{ActiveValue, IntoActiveModel};
+
+/// Dereferences a Note, making sure the document is the one we asked for.
+pub async fn fetch_note(client: &reqwest::Client, uri: &str) -> Result<Note, String> {
+    let response = client
+        .get(uri)
+        .header(reqwest::header::ACCEPT, "application/activity+json")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", uri, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Fetching {} returned {}", uri, response.status()));
+    }
+    let note = response
+        .json::<Note>()
+        .await
+        .map_err(|e| format!("Failed to parse note {}: {}", uri, e))?;
+
+    if note.id.as_deref() != Some(uri) {
+        return Err(format!(
+            "Note id mismatch: requested {}, received {:?}",
+            uri, note.id
+        ));
+    }
+    Ok(note)
+}
+
+/// Stores `note` as authored by `actor`. Returns false when it was already known.
+pub async fn store_note<T: RemoteNotesRepository>(
+    storage: &T,
+    actor: &str,
+    note: &Note,
+) -> Result<bool, String> {
+    let ap_id = note
+        .id
+        .clone()
+        .ok_or_else(|| "Note has no id".to_string())?;
+
+    let remote_note = remote_notes::ActiveModel {
+        id: ActiveValue::NotSet,
+        ap_id: ActiveValue::Set(ap_id.clone()),
+        actor: ActiveValue::Set(actor.to_string()),
+        content: ActiveValue::Set(note.content.clone().unwrap_or_default()),
+        summary: ActiveValue::Set(note.summary.clone()),
+        sensitive: ActiveValue::Set(note.sensitive.unwrap_or(false)),
+        to: ActiveValue::Set(note.to.as_deref().map(ids_of).unwrap_or_default()),
+        cc: ActiveValue::Set(note.cc.as_deref().map(ids_of).unwrap_or_default()),
+        in_reply_to: ActiveValue::Set(
+            note.in_reply_to
+                .as_deref()
+                .and_then(|in_reply_to| ids_of(in_reply_to).into_iter().next()),
+        ),
+        published: ActiveValue::Set(note.published.as_deref().and_then(parse_datetime)),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+        updated: ActiveValue::Set(note.updated.as_deref().and_then(parse_datetime)),
+        context: ActiveValue::Set(
+            note.thread_context
+                .as_deref()
+                .and_then(|context| ids_of(context).into_iter().next()),
+        ),
+    };
+    storage
+        .add_remote_note(remote_note)
+        .await
+        .map_err(|e| format!("Failed to store note {}: {}", ap_id, e))
+}
+
+/// Replaces the stored copy of a note with its edited version.
+pub async fn update_note<T: RemoteNotesRepository>(
+    storage: &T,
+    stored: remote_notes::Model,
+    note: &Note,
+) -> Result<remote_notes::Model, String> {
+    let ap_id = stored.ap_id.clone();
+    let mut remote_note = stored.into_active_model();
+    remote_note.content = ActiveValue::Set(note.content.clone().unwrap_or_default());
+    remote_note.summary = ActiveValue::Set(note.summary.clone());
+    remote_note.sensitive = ActiveValue::Set(note.sensitive.unwrap_or(false));
+    remote_note.to = ActiveValue::Set(note.to.as_deref().map(ids_of).unwrap_or_default());
+    remote_note.cc = ActiveValue::Set(note.cc.as_deref().map(ids_of).unwrap_or_default());
+    // Not every server sets `updated` on edits.
+    remote_note.updated = ActiveValue::Set(Some(
+        note.updated
+            .as_deref()
+            .and_then(parse_datetime)
+            .unwrap_or_else(|| Utc::now().naive_utc()),
+    ));
+    storage
+        .update_remote_note(remote_note)
+        .await
+        .map_err(|e| format!("Failed to update note {}: {}", ap_id, e))
+}
+
+/// The ids referenced by an addressing-style property, skipping entries without one.
+pub fn ids_of(values: &SingleOrMultiple<ObjectOrLinkOrStringUrl>) -> Vec<String> {
+    let values = match values {
+        SingleOrMultiple::Single(value) => std::slice::from_ref(value),
+        SingleOrMultiple::Multiple(values) => values.as_slice(),
+    };
+    values.iter().filter_map(id_of).collect()
+}
+
+fn id_of(value: &ObjectOrLinkOrStringUrl) -> Option<String> {
+    match value {
+        ObjectOrLinkOrStringUrl::Str(id) => Some(id.clone()),
+        ObjectOrLinkOrStringUrl::Link(link) => link.href.clone(),
+        ObjectOrLinkOrStringUrl::Object(object) => match object {
+            ObjectBased::Object(object) => object.id.clone(),
+            ObjectBased::Person(person) => person.id.clone(),
+            ObjectBased::Note(note) => note.id.clone(),
+            ObjectBased::Collection(collection) => collection.id.clone(),
+            ObjectBased::OrderedCollection(collection) => collection.id.clone(),
+            _ => None,
+        },
+    }
+}
+
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|value| value.naive_utc())
+}
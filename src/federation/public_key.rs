@@ -0,0 +1,177 @@
+use crate::domain::repositories::RemoteActorsRepository;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+const MAX_CACHED_KEYS: usize = 10_000;
+
+/// A remote actor's key, as used to verify HTTP Signatures.
+#[derive(Clone, Debug)]
+pub struct RemotePublicKey {
+    pub id: String,
+    pub owner: String,
+    pub pem: String,
+}
+
+/// Fetches `publicKey`s by `keyId` and keeps them in memory for `ttl`.
+#[derive(Clone)]
+pub struct PublicKeyCache {
+    client: reqwest::Client,
+    ttl: Duration,
+    keys: Arc<RwLock<HashMap<String, (RemotePublicKey, Instant)>>>,
+}
+
+impl PublicKeyCache {
+    pub fn new(client: reqwest::Client, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            keys: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn get<T: RemoteActorsRepository>(
+        &self,
+        storage: &T,
+        key_id: &str,
+    ) -> Result<RemotePublicKey, String> {
+        if let Some((key, fetched_at)) = self.keys.read().await.get(key_id)
+            && fetched_at.elapsed() < self.ttl
+        {
+            return Ok(key.clone());
+        }
+        self.refresh(storage, key_id).await
+    }
+
+    /// Fetches the key regardless of the cached copy.
+    pub async fn refresh<T: RemoteActorsRepository>(
+        &self,
+        storage: &T,
+        key_id: &str,
+    ) -> Result<RemotePublicKey, String> {
+        let key = match self.fetch(key_id).await {
+            Ok(key) => key,
+            // Deleted actors answer 410 from then on, yet sign their Delete with the old key.
+            Err(err) => return stored_key(storage, key_id).await?.ok_or(err),
+        };
+        let mut keys = self.keys.write().await;
+        if keys.len() >= MAX_CACHED_KEYS {
+            keys.retain(|_, (_, fetched_at)| fetched_at.elapsed() < self.ttl);
+        }
+        if keys.len() >= MAX_CACHED_KEYS
+            && let Some(oldest) = keys
+                .iter()
+                .min_by_key(|(_, (_, fetched_at))| *fetched_at)
+                .map(|(key_id, _)| key_id.clone())
+        {
+            keys.remove(&oldest);
+        }
+        keys.insert(key_id.to_string(), (key.clone(), Instant::now()));
+        Ok(key)
+    }
+
+    async fn fetch(&self, key_id: &str) -> Result<RemotePublicKey, String> {
+        let document = self.fetch_document(key_id).await?;
+
+        // Most servers use a fragment of the actor URI as keyId and serve the actor there.
+        if let Some(key) = document.public_key {
+            let url = key_id.split('#').next().unwrap_or(key_id);
+            if document.id.as_deref() != Some(url) {
+                return Err(format!("Actor document at {} has a different id", url));
+            }
+            return checked_key(key, key_id, url);
+        }
+
+        // Others serve a standalone key document; its owner has to claim the key back.
+        let owner = document
+            .owner
+            .ok_or_else(|| format!("Document at {} is neither an actor nor a key", key_id))?;
+        let actor = self.fetch_document(&owner).await?;
+        if actor.id.as_deref() != Some(owner.as_str()) {
+            return Err(format!("Actor document at {} has a different id", owner));
+        }
+        let key = actor
+            .public_key
+            .ok_or_else(|| format!("Actor {} has no publicKey", owner))?;
+        checked_key(key, key_id, &owner)
+    }
+
+    async fn fetch_document(&self, uri: &str) -> Result<KeyDocument, String> {
+        let url = uri.split('#').next().unwrap_or(uri);
+        let response = self
+            .client
+            .get(url)
+            .header(reqwest::header::ACCEPT, "application/activity+json")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+        if !response.status().is_success() {
+            return Err(format!("Fetching {} returned {}", url, response.status()));
+        }
+        response
+            .json::<KeyDocument>()
+            .await
+            .map_err(|e| format!("Failed to parse document at {}: {}", url, e))
+    }
+}
+
+/// The key kept with the actor when it was last resolved; see `actor::store_person`.
+async fn stored_key<T: RemoteActorsRepository>(
+    storage: &T,
+    key_id: &str,
+) -> Result<Option<RemotePublicKey>, String> {
+    let actor = storage
+        .find_remote_actor_by_public_key_id(key_id)
+        .await
+        .map_err(|e| format!("Failed to load the key {}: {}", key_id, e))?;
+    Ok(actor.and_then(|actor| {
+        Some(RemotePublicKey {
+            id: key_id.to_string(),
+            pem: actor.public_key_pem?,
+            owner: actor.uri,
+        })
+    }))
+}
+
+fn checked_key(
+    key: PublicKeyDocument,
+    key_id: &str,
+    owner: &str,
+) -> Result<RemotePublicKey, String> {
+    if key.id != key_id {
+        return Err(format!(
+            "Key id mismatch: expected {}, found {}",
+            key_id, key.id
+        ));
+    }
+    if key.owner != owner {
+        return Err(format!(
+            "Key owner mismatch: expected {}, found {}",
+            owner, key.owner
+        ));
+    }
+    Ok(RemotePublicKey {
+        id: key.id,
+        owner: key.owner,
+        pem: key.public_key_pem,
+    })
+}
+
+/// Either an actor carrying `publicKey`, or a bare key document.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyDocument {
+    id: Option<String>,
+    owner: Option<String>,
+    public_key: Option<PublicKeyDocument>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PublicKeyDocument {
+    id: String,
+    owner: String,
+    public_key_pem: String,
+}
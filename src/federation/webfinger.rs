@@ -0,0 +1,47 @@
+// https://www.rfc-editor.org/rfc/rfc7033
+// https://docs.joinmastodon.org/spec/webfinger/
+
+use calmi_webfinger::types::WebFingerResponse;
+
+/// Taken from the `self` link of the WebFinger document.
+pub async fn resolve_account(
+    client: &reqwest::Client,
+    scheme: &str,
+    username: &str,
+    domain: &str,
+) -> Result<String, String> {
+    let account = format!("{}@{}", username, domain);
+    let url = format!("{}://{}/.well-known/webfinger", scheme, domain);
+    let response = client
+        .get(&url)
+        .query(&[("resource", format!("acct:{}", account))])
+        .header(reqwest::header::ACCEPT, "application/jrd+json")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to look up {}: {}", account, e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Looking up {} returned {}",
+            account,
+            response.status()
+        ));
+    }
+    let document = response
+        .json::<WebFingerResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse WebFinger document of {}: {}", account, e))?;
+
+    document
+        .links
+        .unwrap_or_default()
+        .into_iter()
+        .find(|link| {
+            link.rel == "self"
+                && link.r#type.as_deref().is_some_and(|media_type| {
+                    media_type == "application/activity+json"
+                        || media_type.starts_with("application/ld+json")
+                })
+        })
+        .and_then(|link| link.href)
+        .ok_or_else(|| format!("{} has no ActivityPub actor", account))
+}
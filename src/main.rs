@@ -4,6 +4,9 @@ use calmi::storage;
 use sea_orm::ConnectionTrait;
 use sea_orm::Database;
 use sea_orm::DbBackend;
+use std::time::Duration;
+
+const JOB_WORKERS: usize = 4;
 
 #[tokio::main]
 async fn main() {
@@ -19,10 +22,31 @@ async fn main() {
         panic!("Unsupported database backend. Only Postgres is supported.");
     };
 
-    let config = config::Config::new(domain);
+    let mut config = config::Config::new(domain);
+    if let Ok(hours) = std::env::var("JOB_RETRY_HORIZON_HOURS") {
+        let hours: u64 = hours
+            .parse()
+            .expect("env JOB_RETRY_HORIZON_HOURS must be a number");
+        config.job_retry_horizon = Duration::from_secs(hours * 60 * 60);
+    }
+    if let Ok(hours) = std::env::var("REMOTE_ACTOR_TTL_HOURS") {
+        let hours: u64 = hours
+            .parse()
+            .expect("env REMOTE_ACTOR_TTL_HOURS must be a number");
+        config.remote_actor_ttl = Duration::from_secs(hours * 60 * 60);
+    }
+    config.api_token = std::env::var("API_TOKEN").ok().filter(|t| !t.is_empty());
+    if let Ok(dir) = std::env::var("MEDIA_DIR") {
+        config.media_dir = dir.into();
+    }
     let storage = storage::postgres::PostgresStorage::new(db);
+    app::keys::backfill_user_keys(&storage)
+        .await
+        .expect("Failed to generate keys for existing users");
     let state = app::state::AppState::new(config, storage);
 
+    app::jobs::spawn_workers(state.clone(), JOB_WORKERS);
+
     let app = app::create_app(state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
@@ -1,9 +1,20 @@
 use sea_orm::DatabaseConnection;
 
+pub mod conversation;
 pub mod follow;
+pub mod following;
+pub mod job;
+pub mod liked;
+pub mod media;
+pub mod mention;
 pub mod note;
 pub mod note_announce;
 pub mod note_like;
+pub mod note_mention;
+pub mod note_revision;
+pub mod note_tag;
+pub mod remote_actor;
+pub mod remote_note;
 pub mod user;
 
 #[derive(Clone)]
@@ -0,0 +1,156 @@
+use crate::domain::entities::{conversation_messages, conversations};
+use crate::domain::repositories::conversations::ConversationsRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
+};
+
+pub(super) async fn insert_conversation_message<C: TransactionTrait>(
+    db: &C,
+    user_id: i64,
+    context: &str,
+    participants: &[String],
+    note: &str,
+    note_id: Option<i64>,
+    remote_note_id: Option<i64>,
+) -> Result<conversations::Model, DbErr> {
+    let now = Utc::now().naive_utc();
+    let txn = db.begin().await?;
+
+    conversations::Entity::insert(conversations::ActiveModel {
+        id: ActiveValue::NotSet,
+        user_id: ActiveValue::Set(user_id),
+        context: ActiveValue::Set(context.to_string()),
+        participants: ActiveValue::Set(Vec::new()),
+        created_at: ActiveValue::Set(now),
+        updated_at: ActiveValue::Set(now),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            conversations::Column::UserId,
+            conversations::Column::Context,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(&txn)
+    .await?;
+    let conversation = conversations::Entity::find()
+        .filter(conversations::Column::UserId.eq(user_id))
+        .filter(conversations::Column::Context.eq(context))
+        .one(&txn)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("conversation {}", context)))?;
+
+    let mut joined = conversation.participants.clone();
+    for participant in participants {
+        if !joined.contains(participant) {
+            joined.push(participant.clone());
+        }
+    }
+    let mut conversation = conversation.into_active_model();
+    conversation.participants = ActiveValue::Set(joined);
+    conversation.updated_at = ActiveValue::Set(now);
+    let conversation = conversation.update(&txn).await?;
+
+    conversation_messages::Entity::insert(conversation_messages::ActiveModel {
+        id: ActiveValue::NotSet,
+        conversation_id: ActiveValue::Set(conversation.id),
+        note: ActiveValue::Set(note.to_string()),
+        note_id: ActiveValue::Set(note_id),
+        remote_note_id: ActiveValue::Set(remote_note_id),
+        created_at: ActiveValue::Set(now),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            conversation_messages::Column::ConversationId,
+            conversation_messages::Column::Note,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(&txn)
+    .await?;
+
+    txn.commit().await?;
+    Ok(conversation)
+}
+
+#[async_trait]
+impl ConversationsRepository for PostgresStorage {
+    async fn add_conversation_message(
+        &self,
+        user_id: i64,
+        context: &str,
+        participants: &[String],
+        note: &str,
+        note_id: Option<i64>,
+        remote_note_id: Option<i64>,
+    ) -> Result<conversations::Model, DbErr> {
+        insert_conversation_message(
+            &self.db,
+            user_id,
+            context,
+            participants,
+            note,
+            note_id,
+            remote_note_id,
+        )
+        .await
+    }
+
+    async fn find_conversation(
+        &self,
+        user_id: i64,
+        id: i64,
+    ) -> Result<Option<conversations::Model>, DbErr> {
+        conversations::Entity::find_by_id(id)
+            .filter(conversations::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_conversation_by_message(
+        &self,
+        user_id: i64,
+        note: &str,
+    ) -> Result<Option<conversations::Model>, DbErr> {
+        conversations::Entity::find()
+            .inner_join(conversation_messages::Entity)
+            .filter(conversations::Column::UserId.eq(user_id))
+            .filter(conversation_messages::Column::Note.eq(note))
+            .one(&self.db)
+            .await
+    }
+
+    async fn list_conversations_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<conversations::Model>, DbErr> {
+        conversations::Entity::find()
+            .filter(conversations::Column::UserId.eq(user_id))
+            .order_by_desc(conversations::Column::UpdatedAt)
+            .order_by_desc(conversations::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_conversation_messages(
+        &self,
+        conversation_id: i64,
+    ) -> Result<Vec<conversation_messages::Model>, DbErr> {
+        conversation_messages::Entity::find()
+            .filter(conversation_messages::Column::ConversationId.eq(conversation_id))
+            .order_by_asc(conversation_messages::Column::Id)
+            .all(&self.db)
+            .await
+    }
+}
@@ -3,18 +3,28 @@ use crate::domain::repositories::follows::FollowsRepository;
 use crate::storage::postgres::PostgresStorage;
 use async_trait::async_trait;
 use chrono::Utc;
-use sea_orm::sea_query::OnConflict;
-use sea_orm::{ActiveValue, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveValue, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
 
 #[async_trait]
 impl FollowsRepository for PostgresStorage {
-    async fn add_follow(&self, user_id: i64, actor: &str, activity_id: &str) -> Result<(), DbErr> {
+    async fn add_follow(
+        &self,
+        user_id: i64,
+        actor: &str,
+        activity_id: &str,
+        pending: bool,
+    ) -> Result<(), DbErr> {
         let model = follows::ActiveModel {
             id: ActiveValue::NotSet,
             user_id: ActiveValue::Set(user_id),
             actor: ActiveValue::Set(actor.to_string()),
             activity_id: ActiveValue::Set(activity_id.to_string()),
             created_at: ActiveValue::Set(Utc::now().naive_utc()),
+            pending: ActiveValue::Set(pending),
         };
 
         follows::Entity::insert(model)
@@ -28,9 +38,28 @@ impl FollowsRepository for PostgresStorage {
             .map(|_| ())
     }
 
-    async fn remove_follow_by_activity_id(&self, activity_id: &str) -> Result<u64, DbErr> {
+    async fn approve_follow(&self, id: i64) -> Result<(), DbErr> {
+        follows::Entity::update_many()
+            .col_expr(follows::Column::Pending, Expr::value(false))
+            .filter(follows::Column::Id.eq(id))
+            .exec(&self.db)
+            .await
+            .map(|_| ())
+    }
+
+    async fn remove_follow_by_id(&self, id: i64) -> Result<u64, DbErr> {
+        let result = follows::Entity::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected)
+    }
+
+    async fn remove_follow_by_activity_id(
+        &self,
+        activity_id: &str,
+        actor: &str,
+    ) -> Result<u64, DbErr> {
         let result = follows::Entity::delete_many()
             .filter(follows::Column::ActivityId.eq(activity_id))
+            .filter(follows::Column::Actor.eq(actor))
             .exec(&self.db)
             .await?;
         Ok(result.rows_affected)
@@ -45,6 +74,14 @@ impl FollowsRepository for PostgresStorage {
         Ok(result.rows_affected)
     }
 
+    async fn remove_follows_by_actor(&self, actor: &str) -> Result<u64, DbErr> {
+        let result = follows::Entity::delete_many()
+            .filter(follows::Column::Actor.eq(actor))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
     async fn find_follow_by_activity_id(
         &self,
         activity_id: &str,
@@ -55,6 +92,10 @@ impl FollowsRepository for PostgresStorage {
             .await
     }
 
+    async fn find_follow_by_id(&self, id: i64) -> Result<Option<follows::Model>, DbErr> {
+        follows::Entity::find_by_id(id).one(&self.db).await
+    }
+
     async fn find_follow(
         &self,
         user_id: i64,
@@ -70,7 +111,42 @@ impl FollowsRepository for PostgresStorage {
     async fn list_followers(&self, user_id: i64) -> Result<Vec<follows::Model>, DbErr> {
         follows::Entity::find()
             .filter(follows::Column::UserId.eq(user_id))
+            .filter(follows::Column::Pending.eq(false))
+            .order_by_desc(follows::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_followers_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<follows::Model>, DbErr> {
+        follows::Entity::find()
+            .filter(follows::Column::UserId.eq(user_id))
+            .filter(follows::Column::Pending.eq(false))
             .order_by_desc(follows::Column::CreatedAt)
+            .order_by_desc(follows::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_followers(&self, user_id: i64) -> Result<u64, DbErr> {
+        follows::Entity::find()
+            .filter(follows::Column::UserId.eq(user_id))
+            .filter(follows::Column::Pending.eq(false))
+            .count(&self.db)
+            .await
+    }
+
+    async fn list_follow_requests(&self, user_id: i64) -> Result<Vec<follows::Model>, DbErr> {
+        follows::Entity::find()
+            .filter(follows::Column::UserId.eq(user_id))
+            .filter(follows::Column::Pending.eq(true))
+            .order_by_asc(follows::Column::CreatedAt)
             .all(&self.db)
             .await
     }
@@ -0,0 +1,103 @@
+use crate::domain::entities::following;
+use crate::domain::repositories::following::FollowingRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+
+#[async_trait]
+impl FollowingRepository for PostgresStorage {
+    async fn add_following(
+        &self,
+        user_id: i64,
+        actor: &str,
+        activity_id: &str,
+    ) -> Result<following::Model, DbErr> {
+        let model = following::ActiveModel {
+            id: ActiveValue::NotSet,
+            user_id: ActiveValue::Set(user_id),
+            actor: ActiveValue::Set(actor.to_string()),
+            activity_id: ActiveValue::Set(activity_id.to_string()),
+            pending: ActiveValue::Set(true),
+            created_at: ActiveValue::Set(Utc::now().naive_utc()),
+        };
+        model.insert(&self.db).await
+    }
+
+    async fn accept_following(&self, id: i64) -> Result<(), DbErr> {
+        following::Entity::update_many()
+            .col_expr(following::Column::Pending, Expr::value(false))
+            .filter(following::Column::Id.eq(id))
+            .exec(&self.db)
+            .await
+            .map(|_| ())
+    }
+
+    async fn remove_following(&self, id: i64) -> Result<u64, DbErr> {
+        let result = following::Entity::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected)
+    }
+
+    async fn find_following_by_id(&self, id: i64) -> Result<Option<following::Model>, DbErr> {
+        following::Entity::find_by_id(id).one(&self.db).await
+    }
+
+    async fn find_following_by_activity_id(
+        &self,
+        activity_id: &str,
+    ) -> Result<Option<following::Model>, DbErr> {
+        following::Entity::find()
+            .filter(following::Column::ActivityId.eq(activity_id))
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_following(
+        &self,
+        user_id: i64,
+        actor: &str,
+    ) -> Result<Option<following::Model>, DbErr> {
+        following::Entity::find()
+            .filter(following::Column::UserId.eq(user_id))
+            .filter(following::Column::Actor.eq(actor))
+            .one(&self.db)
+            .await
+    }
+
+    async fn list_following(&self, user_id: i64) -> Result<Vec<following::Model>, DbErr> {
+        following::Entity::find()
+            .filter(following::Column::UserId.eq(user_id))
+            .order_by_desc(following::Column::CreatedAt)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_following_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<following::Model>, DbErr> {
+        following::Entity::find()
+            .filter(following::Column::UserId.eq(user_id))
+            .filter(following::Column::Pending.eq(false))
+            .order_by_desc(following::Column::CreatedAt)
+            .order_by_desc(following::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_following(&self, user_id: i64) -> Result<u64, DbErr> {
+        following::Entity::find()
+            .filter(following::Column::UserId.eq(user_id))
+            .filter(following::Column::Pending.eq(false))
+            .count(&self.db)
+            .await
+    }
+}
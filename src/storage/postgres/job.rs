@@ -0,0 +1,120 @@
+use crate::domain::entities::jobs;
+use crate::domain::repositories::jobs::JobsRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use sea_orm::sea_query::{Expr, LockBehavior, LockType};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
+};
+
+#[async_trait]
+impl JobsRepository for PostgresStorage {
+    async fn enqueue_job(
+        &self,
+        kind: &str,
+        payload: serde_json::Value,
+    ) -> Result<jobs::Model, DbErr> {
+        let now = Utc::now().naive_utc();
+        let job = jobs::ActiveModel {
+            id: ActiveValue::NotSet,
+            kind: ActiveValue::Set(kind.to_string()),
+            payload: ActiveValue::Set(payload),
+            attempts: ActiveValue::Set(0),
+            run_at: ActiveValue::Set(now),
+            locked_until: ActiveValue::Set(None),
+            failed_at: ActiveValue::Set(None),
+            last_error: ActiveValue::Set(None),
+            created_at: ActiveValue::Set(now),
+        };
+        job.insert(&self.db).await
+    }
+
+    async fn claim_due_jobs(
+        &self,
+        limit: u64,
+        locked_until: NaiveDateTime,
+    ) -> Result<Vec<jobs::Model>, DbErr> {
+        let now = Utc::now().naive_utc();
+        let txn = self.db.begin().await?;
+
+        let due = jobs::Entity::find()
+            .filter(jobs::Column::FailedAt.is_null())
+            .filter(jobs::Column::RunAt.lte(now))
+            .filter(
+                Condition::any()
+                    .add(jobs::Column::LockedUntil.is_null())
+                    .add(jobs::Column::LockedUntil.lte(now)),
+            )
+            .order_by_asc(jobs::Column::RunAt)
+            .limit(limit)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .all(&txn)
+            .await?;
+
+        if !due.is_empty() {
+            jobs::Entity::update_many()
+                .col_expr(jobs::Column::LockedUntil, Expr::value(locked_until))
+                .filter(jobs::Column::Id.is_in(due.iter().map(|job| job.id)))
+                .exec(&txn)
+                .await?;
+        }
+
+        txn.commit().await?;
+        Ok(due)
+    }
+
+    async fn complete_job(&self, id: i64) -> Result<(), DbErr> {
+        jobs::Entity::delete_by_id(id).exec(&self.db).await?;
+        Ok(())
+    }
+
+    async fn retry_job(&self, id: i64, run_at: NaiveDateTime, error: &str) -> Result<(), DbErr> {
+        jobs::Entity::update_many()
+            .col_expr(
+                jobs::Column::Attempts,
+                Expr::col(jobs::Column::Attempts).add(1),
+            )
+            .col_expr(jobs::Column::RunAt, Expr::value(run_at))
+            .col_expr(
+                jobs::Column::LockedUntil,
+                Expr::value(Option::<NaiveDateTime>::None),
+            )
+            .col_expr(jobs::Column::LastError, Expr::value(error))
+            .filter(jobs::Column::Id.eq(id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    async fn fail_job(&self, id: i64, error: &str) -> Result<(), DbErr> {
+        jobs::Entity::update_many()
+            .col_expr(
+                jobs::Column::Attempts,
+                Expr::col(jobs::Column::Attempts).add(1),
+            )
+            .col_expr(jobs::Column::FailedAt, Expr::value(Utc::now().naive_utc()))
+            .col_expr(
+                jobs::Column::LockedUntil,
+                Expr::value(Option::<NaiveDateTime>::None),
+            )
+            .col_expr(jobs::Column::LastError, Expr::value(error))
+            .filter(jobs::Column::Id.eq(id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    async fn find_job_by_id(&self, id: i64) -> Result<Option<jobs::Model>, DbErr> {
+        jobs::Entity::find_by_id(id).one(&self.db).await
+    }
+
+    async fn list_jobs(&self, kind: &str) -> Result<Vec<jobs::Model>, DbErr> {
+        jobs::Entity::find()
+            .filter(jobs::Column::Kind.eq(kind))
+            .order_by_asc(jobs::Column::Id)
+            .all(&self.db)
+            .await
+    }
+}
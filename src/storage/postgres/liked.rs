@@ -0,0 +1,70 @@
+use crate::domain::entities::liked;
+use crate::domain::repositories::liked::LikedRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+
+#[async_trait]
+impl LikedRepository for PostgresStorage {
+    async fn add_liked(
+        &self,
+        user_id: i64,
+        object: &str,
+        actor: &str,
+        activity_id: &str,
+    ) -> Result<liked::Model, DbErr> {
+        let model = liked::ActiveModel {
+            id: ActiveValue::NotSet,
+            user_id: ActiveValue::Set(user_id),
+            object: ActiveValue::Set(object.to_string()),
+            actor: ActiveValue::Set(actor.to_string()),
+            activity_id: ActiveValue::Set(activity_id.to_string()),
+            created_at: ActiveValue::Set(Utc::now().naive_utc()),
+        };
+        model.insert(&self.db).await
+    }
+
+    async fn remove_liked(&self, id: i64) -> Result<u64, DbErr> {
+        let result = liked::Entity::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected)
+    }
+
+    async fn find_liked_by_id(&self, id: i64) -> Result<Option<liked::Model>, DbErr> {
+        liked::Entity::find_by_id(id).one(&self.db).await
+    }
+
+    async fn find_liked(&self, user_id: i64, object: &str) -> Result<Option<liked::Model>, DbErr> {
+        liked::Entity::find()
+            .filter(liked::Column::UserId.eq(user_id))
+            .filter(liked::Column::Object.eq(object))
+            .one(&self.db)
+            .await
+    }
+
+    async fn list_liked_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<liked::Model>, DbErr> {
+        liked::Entity::find()
+            .filter(liked::Column::UserId.eq(user_id))
+            .order_by_desc(liked::Column::CreatedAt)
+            .order_by_desc(liked::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_liked(&self, user_id: i64) -> Result<u64, DbErr> {
+        liked::Entity::find()
+            .filter(liked::Column::UserId.eq(user_id))
+            .count(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,100 @@
+use crate::domain::entities::media;
+use crate::domain::repositories::media::MediaRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
+
+/// Attaches uploads of the user that are not attached yet to the local note `note_id`.
+/// Returns how many were attached.
+pub(super) async fn attach<C: ConnectionTrait>(
+    db: &C,
+    user_id: i64,
+    note_id: i64,
+    ids: &[i64],
+) -> Result<u64, DbErr> {
+    let result = media::Entity::update_many()
+        .col_expr(media::Column::NoteId, note_id.into())
+        .filter(media::Column::Id.is_in(ids.iter().copied()))
+        .filter(media::Column::UserId.eq(user_id))
+        .filter(media::Column::NoteId.is_null())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+#[async_trait]
+impl MediaRepository for PostgresStorage {
+    async fn add_media(&self, media: media::ActiveModel) -> Result<media::Model, DbErr> {
+        media.insert(&self.db).await
+    }
+
+    async fn update_media(&self, media: media::ActiveModel) -> Result<media::Model, DbErr> {
+        media.update(&self.db).await
+    }
+
+    async fn find_media_by_id(&self, id: i64) -> Result<Option<media::Model>, DbErr> {
+        media::Entity::find_by_id(id).one(&self.db).await
+    }
+
+    async fn find_media_by_file_name(
+        &self,
+        file_name: &str,
+    ) -> Result<Option<media::Model>, DbErr> {
+        media::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(media::Column::FileName.eq(file_name))
+                    .add(media::Column::ThumbnailFileName.eq(file_name)),
+            )
+            .one(&self.db)
+            .await
+    }
+
+    async fn list_note_media(&self, note_id: i64) -> Result<Vec<media::Model>, DbErr> {
+        media::Entity::find()
+            .filter(media::Column::NoteId.eq(note_id))
+            .order_by_asc(media::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_note_media_of_notes(&self, note_ids: &[i64]) -> Result<Vec<media::Model>, DbErr> {
+        media::Entity::find()
+            .filter(media::Column::NoteId.is_in(note_ids.iter().copied()))
+            .order_by_asc(media::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_remote_note_media(
+        &self,
+        remote_note_id: i64,
+    ) -> Result<Vec<media::Model>, DbErr> {
+        media::Entity::find()
+            .filter(media::Column::RemoteNoteId.eq(remote_note_id))
+            .order_by_asc(media::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn set_remote_note_media(
+        &self,
+        remote_note_id: i64,
+        media: Vec<media::ActiveModel>,
+    ) -> Result<(), DbErr> {
+        let txn = self.db.begin().await?;
+        media::Entity::delete_many()
+            .filter(media::Column::RemoteNoteId.eq(remote_note_id))
+            .exec(&txn)
+            .await?;
+        if !media.is_empty() {
+            media::Entity::insert_many(media)
+                .exec_without_returning(&txn)
+                .await?;
+        }
+        txn.commit().await
+    }
+}
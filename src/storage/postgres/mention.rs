@@ -0,0 +1,57 @@
+use crate::domain::entities::mentions;
+use crate::domain::repositories::mentions::MentionsRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+
+pub(super) async fn insert_mention<C: ConnectionTrait>(
+    db: &C,
+    user_id: i64,
+    note: &str,
+    actor: &str,
+) -> Result<bool, DbErr> {
+    let model = mentions::ActiveModel {
+        id: ActiveValue::NotSet,
+        user_id: ActiveValue::Set(user_id),
+        note: ActiveValue::Set(note.to_string()),
+        actor: ActiveValue::Set(actor.to_string()),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+    };
+    let rows = mentions::Entity::insert(model)
+        .on_conflict(
+            OnConflict::columns([mentions::Column::UserId, mentions::Column::Note])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    Ok(rows > 0)
+}
+
+#[async_trait]
+impl MentionsRepository for PostgresStorage {
+    async fn add_mention(&self, user_id: i64, note: &str, actor: &str) -> Result<bool, DbErr> {
+        insert_mention(&self.db, user_id, note, actor).await
+    }
+
+    async fn list_mentions_page(
+        &self,
+        user_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<mentions::Model>, DbErr> {
+        mentions::Entity::find()
+            .filter(mentions::Column::UserId.eq(user_id))
+            .order_by_desc(mentions::Column::CreatedAt)
+            .order_by_desc(mentions::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await
+    }
+}
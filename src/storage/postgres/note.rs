@@ -1,11 +1,18 @@
-use crate::domain::entities::notes;
-use crate::domain::repositories::notes::NotesRepository;
+use crate::domain::entities::{note_revisions, note_tags, notes};
+use crate::domain::repositories::notes::{NoteRelations, NotesRepository};
+use crate::domain::visibility::Visibility;
 use crate::storage::postgres::PostgresStorage;
+use crate::storage::postgres::conversation::insert_conversation_message;
+use crate::storage::postgres::media::attach;
+use crate::storage::postgres::mention::insert_mention;
+use crate::storage::postgres::note_mention::insert_note_mention;
+use crate::storage::postgres::note_tag::{note_tag, replace_note_tags};
 use async_trait::async_trait;
 use chrono::Utc;
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
-    QuerySelect,
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
 };
 
 #[async_trait]
@@ -14,48 +21,185 @@ impl NotesRepository for PostgresStorage {
         notes::Entity::find_by_id(id).one(&self.db).await
     }
 
-    async fn find_note_by_author_id(
+    async fn list_notes_by_author_before(
         &self,
         author_id: i64,
+        before: Option<&notes::Model>,
         limit: u64,
-        offset: u64,
     ) -> Result<Vec<notes::Model>, DbErr> {
-        notes::Entity::find()
+        let mut query = notes::Entity::find()
             .filter(notes::Column::AuthorId.eq(author_id))
+            .filter(notes::Column::DeletedAt.is_null())
+            .filter(readable_by_anyone());
+        if let Some(before) = before {
+            query = query.filter(
+                Condition::any()
+                    .add(notes::Column::CreatedAt.lt(before.created_at))
+                    .add(
+                        Condition::all()
+                            .add(notes::Column::CreatedAt.eq(before.created_at))
+                            .add(notes::Column::Id.lt(before.id)),
+                    ),
+            );
+        }
+        query
             .order_by_desc(notes::Column::CreatedAt)
+            .order_by_desc(notes::Column::Id)
             .limit(limit)
-            .offset(offset)
             .all(&self.db)
             .await
     }
 
-    async fn add_note(
+    async fn list_notes_by_author_after(
         &self,
-        content: &str,
         author_id: i64,
-        to: Vec<String>,
-    ) -> Result<notes::Model, DbErr> {
-        let note = notes::ActiveModel {
-            id: ActiveValue::NotSet,
-            content: ActiveValue::Set(content.to_string()),
-            author_id: ActiveValue::Set(author_id),
-            created_at: ActiveValue::Set(Utc::now().naive_utc()),
-            to: ActiveValue::Set(to),
-        };
+        after: Option<&notes::Model>,
+        limit: u64,
+    ) -> Result<Vec<notes::Model>, DbErr> {
+        let mut query = notes::Entity::find()
+            .filter(notes::Column::AuthorId.eq(author_id))
+            .filter(notes::Column::DeletedAt.is_null())
+            .filter(readable_by_anyone());
+        if let Some(after) = after {
+            query = query.filter(
+                Condition::any()
+                    .add(notes::Column::CreatedAt.gt(after.created_at))
+                    .add(
+                        Condition::all()
+                            .add(notes::Column::CreatedAt.eq(after.created_at))
+                            .add(notes::Column::Id.gt(after.id)),
+                    ),
+            );
+        }
+        query
+            .order_by_asc(notes::Column::CreatedAt)
+            .order_by_asc(notes::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_notes_by_author(&self, author_id: i64) -> Result<u64, DbErr> {
+        notes::Entity::find()
+            .filter(notes::Column::AuthorId.eq(author_id))
+            .filter(notes::Column::DeletedAt.is_null())
+            .filter(readable_by_anyone())
+            .count(&self.db)
+            .await
+    }
+
+    async fn add_note(&self, note: notes::ActiveModel) -> Result<notes::Model, DbErr> {
         note.insert(&self.db).await
     }
 
+    async fn add_note_with_relations(
+        &self,
+        note: notes::ActiveModel,
+        relations: NoteRelations,
+        uri: &(dyn Fn(i64) -> String + Send + Sync),
+    ) -> Result<notes::Model, DbErr> {
+        let txn = self.db.begin().await?;
+        let note = note.insert(&txn).await?;
+        let uri = uri(note.id);
+
+        for (href, name) in &relations.mentions {
+            insert_note_mention(&txn, note.id, href, name).await?;
+        }
+        for user_id in relations.mentioned_users {
+            insert_mention(&txn, user_id, &uri, &relations.author).await?;
+        }
+        replace_note_tags(
+            &txn,
+            note_tags::Column::NoteId.eq(note.id),
+            relations
+                .tags
+                .iter()
+                .map(|name| note_tag(&uri, name, Some(note.id), None))
+                .collect(),
+        )
+        .await?;
+        if !relations.media_ids.is_empty() {
+            let attached = attach(&txn, note.author_id, note.id, &relations.media_ids).await?;
+            if attached != relations.media_ids.len() as u64 {
+                return Err(DbErr::Custom(format!(
+                    "Cannot attach all of {:?} to note {}",
+                    relations.media_ids, note.id
+                )));
+            }
+        }
+        for message in relations.direct_messages {
+            insert_conversation_message(
+                &txn,
+                message.user_id,
+                message.context.as_deref().unwrap_or(&uri),
+                &message.participants,
+                &uri,
+                Some(note.id),
+                None,
+            )
+            .await?;
+        }
+
+        txn.commit().await?;
+        Ok(note)
+    }
+
+    async fn list_replies(&self, in_reply_to: &str) -> Result<Vec<notes::Model>, DbErr> {
+        notes::Entity::find()
+            .filter(notes::Column::InReplyTo.eq(in_reply_to))
+            .order_by_asc(notes::Column::CreatedAt)
+            .order_by_asc(notes::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
     async fn update_note(&self, note: notes::ActiveModel) -> Result<notes::Model, DbErr> {
         note.update(&self.db).await
     }
 
+    async fn edit_note(&self, note: notes::ActiveModel) -> Result<notes::Model, DbErr> {
+        let txn = self.db.begin().await?;
+        let id = *note
+            .id
+            .try_as_ref()
+            .ok_or_else(|| DbErr::Custom("Note to edit has no id".to_string()))?;
+        let previous = notes::Entity::find_by_id(id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Note {} not found", id)))?;
+        revision_of(previous).insert(&txn).await?;
+        let note = note.update(&txn).await?;
+        txn.commit().await?;
+        Ok(note)
+    }
+
     async fn delete_note(&self, id: i64) -> Result<(), DbErr> {
-        notes::Entity::delete_by_id(id).exec(&self.db).await?;
+        let txn = self.db.begin().await?;
+        let Some(note) = notes::Entity::find_by_id(id)
+            .filter(notes::Column::DeletedAt.is_null())
+            .one(&txn)
+            .await?
+        else {
+            return Ok(());
+        };
+        revision_of(note).insert(&txn).await?;
+        notes::Entity::update_many()
+            .col_expr(notes::Column::Content, Expr::value(""))
+            .col_expr(notes::Column::Summary, Expr::value(Option::<String>::None))
+            .col_expr(
+                notes::Column::DeletedAt,
+                Expr::value(Utc::now().naive_utc()),
+            )
+            .filter(notes::Column::Id.eq(id))
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
         Ok(())
     }
 
     async fn list_note(&self, limit: u64, offset: u64) -> Result<Vec<notes::Model>, DbErr> {
         notes::Entity::find()
+            .filter(notes::Column::DeletedAt.is_null())
             .order_by_desc(notes::Column::CreatedAt)
             .limit(limit)
             .offset(offset)
@@ -63,3 +207,19 @@ impl NotesRepository for PostgresStorage {
             .await
     }
 }
+
+fn revision_of(note: notes::Model) -> note_revisions::ActiveModel {
+    note_revisions::ActiveModel {
+        id: ActiveValue::NotSet,
+        note_id: ActiveValue::Set(note.id),
+        content: ActiveValue::Set(note.content),
+        created_at: ActiveValue::Set(note.updated_at.unwrap_or(note.created_at)),
+        summary: ActiveValue::Set(note.summary),
+    }
+}
+
+fn readable_by_anyone() -> Condition {
+    Condition::any()
+        .add(notes::Column::Visibility.eq(Visibility::Public.as_str()))
+        .add(notes::Column::Visibility.eq(Visibility::Unlisted.as_str()))
+}
@@ -4,7 +4,10 @@ use crate::storage::postgres::PostgresStorage;
 use async_trait::async_trait;
 use chrono::Utc;
 use sea_orm::sea_query::OnConflict;
-use sea_orm::{ActiveValue, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{
+    ActiveValue, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
 
 #[async_trait]
 impl NoteAnnouncesRepository for PostgresStorage {
@@ -36,9 +39,14 @@ impl NoteAnnouncesRepository for PostgresStorage {
             .map(|_| ())
     }
 
-    async fn remove_announce_by_activity_id(&self, activity_id: &str) -> Result<u64, DbErr> {
+    async fn remove_announce_by_activity_id(
+        &self,
+        activity_id: &str,
+        actor: &str,
+    ) -> Result<u64, DbErr> {
         let result = note_announces::Entity::delete_many()
             .filter(note_announces::Column::ActivityId.eq(activity_id))
+            .filter(note_announces::Column::Actor.eq(actor))
             .exec(&self.db)
             .await?;
         Ok(result.rows_affected)
@@ -53,6 +61,14 @@ impl NoteAnnouncesRepository for PostgresStorage {
         Ok(result.rows_affected)
     }
 
+    async fn remove_announces_by_actor(&self, actor: &str) -> Result<u64, DbErr> {
+        let result = note_announces::Entity::delete_many()
+            .filter(note_announces::Column::Actor.eq(actor))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
     async fn find_announce_by_activity_id(
         &self,
         activity_id: &str,
@@ -82,4 +98,27 @@ impl NoteAnnouncesRepository for PostgresStorage {
             .all(&self.db)
             .await
     }
+
+    async fn list_announces_page(
+        &self,
+        note_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<note_announces::Model>, DbErr> {
+        note_announces::Entity::find()
+            .filter(note_announces::Column::NoteId.eq(note_id))
+            .order_by_desc(note_announces::Column::CreatedAt)
+            .order_by_desc(note_announces::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_announces(&self, note_id: i64) -> Result<u64, DbErr> {
+        note_announces::Entity::find()
+            .filter(note_announces::Column::NoteId.eq(note_id))
+            .count(&self.db)
+            .await
+    }
 }
@@ -4,7 +4,10 @@ use crate::storage::postgres::PostgresStorage;
 use async_trait::async_trait;
 use chrono::Utc;
 use sea_orm::sea_query::OnConflict;
-use sea_orm::{ActiveValue, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{
+    ActiveValue, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
 
 #[async_trait]
 impl NoteLikesRepository for PostgresStorage {
@@ -28,9 +31,14 @@ impl NoteLikesRepository for PostgresStorage {
             .map(|_| ())
     }
 
-    async fn remove_like_by_activity_id(&self, activity_id: &str) -> Result<u64, DbErr> {
+    async fn remove_like_by_activity_id(
+        &self,
+        activity_id: &str,
+        actor: &str,
+    ) -> Result<u64, DbErr> {
         let result = note_likes::Entity::delete_many()
             .filter(note_likes::Column::ActivityId.eq(activity_id))
+            .filter(note_likes::Column::Actor.eq(actor))
             .exec(&self.db)
             .await?;
         Ok(result.rows_affected)
@@ -45,6 +53,14 @@ impl NoteLikesRepository for PostgresStorage {
         Ok(result.rows_affected)
     }
 
+    async fn remove_likes_by_actor(&self, actor: &str) -> Result<u64, DbErr> {
+        let result = note_likes::Entity::delete_many()
+            .filter(note_likes::Column::Actor.eq(actor))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
     async fn find_like_by_activity_id(
         &self,
         activity_id: &str,
@@ -74,4 +90,27 @@ impl NoteLikesRepository for PostgresStorage {
             .all(&self.db)
             .await
     }
+
+    async fn list_likes_page(
+        &self,
+        note_id: i64,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<note_likes::Model>, DbErr> {
+        note_likes::Entity::find()
+            .filter(note_likes::Column::NoteId.eq(note_id))
+            .order_by_desc(note_likes::Column::CreatedAt)
+            .order_by_desc(note_likes::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_likes(&self, note_id: i64) -> Result<u64, DbErr> {
+        note_likes::Entity::find()
+            .filter(note_likes::Column::NoteId.eq(note_id))
+            .count(&self.db)
+            .await
+    }
 }
@@ -0,0 +1,62 @@
+use crate::domain::entities::note_mentions;
+use crate::domain::repositories::note_mentions::NoteMentionsRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+
+pub(super) async fn insert_note_mention<C: ConnectionTrait>(
+    db: &C,
+    note_id: i64,
+    href: &str,
+    name: &str,
+) -> Result<note_mentions::Model, DbErr> {
+    let model = note_mentions::ActiveModel {
+        id: ActiveValue::NotSet,
+        note_id: ActiveValue::Set(note_id),
+        href: ActiveValue::Set(href.to_string()),
+        name: ActiveValue::Set(name.to_string()),
+    };
+    model.insert(db).await
+}
+
+#[async_trait]
+impl NoteMentionsRepository for PostgresStorage {
+    async fn add_note_mention(
+        &self,
+        note_id: i64,
+        href: &str,
+        name: &str,
+    ) -> Result<note_mentions::Model, DbErr> {
+        insert_note_mention(&self.db, note_id, href, name).await
+    }
+
+    async fn remove_note_mentions(&self, note_id: i64) -> Result<(), DbErr> {
+        note_mentions::Entity::delete_many()
+            .filter(note_mentions::Column::NoteId.eq(note_id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_note_mentions(&self, note_id: i64) -> Result<Vec<note_mentions::Model>, DbErr> {
+        note_mentions::Entity::find()
+            .filter(note_mentions::Column::NoteId.eq(note_id))
+            .order_by_asc(note_mentions::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_note_mentions_of_notes(
+        &self,
+        note_ids: &[i64],
+    ) -> Result<Vec<note_mentions::Model>, DbErr> {
+        note_mentions::Entity::find()
+            .filter(note_mentions::Column::NoteId.is_in(note_ids.iter().copied()))
+            .order_by_asc(note_mentions::Column::Id)
+            .all(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,17 @@
+use crate::domain::entities::note_revisions;
+use crate::domain::repositories::note_revisions::NoteRevisionsRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+#[async_trait]
+impl NoteRevisionsRepository for PostgresStorage {
+    async fn list_note_revisions(&self, note_id: i64) -> Result<Vec<note_revisions::Model>, DbErr> {
+        note_revisions::Entity::find()
+            .filter(note_revisions::Column::NoteId.eq(note_id))
+            .order_by_asc(note_revisions::Column::CreatedAt)
+            .order_by_asc(note_revisions::Column::Id)
+            .all(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,141 @@
+use crate::domain::entities::{note_tags, notes};
+use crate::domain::repositories::note_tags::NoteTagsRepository;
+use crate::domain::visibility::Visibility;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::{OnConflict, Query};
+use sea_orm::{
+    ActiveValue, ColumnTrait, Condition, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
+};
+
+pub(super) async fn replace_note_tags<C: TransactionTrait>(
+    db: &C,
+    owner: sea_orm::sea_query::SimpleExpr,
+    tags: Vec<note_tags::ActiveModel>,
+) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    note_tags::Entity::delete_many()
+        .filter(owner)
+        .exec(&txn)
+        .await?;
+    if !tags.is_empty() {
+        note_tags::Entity::insert_many(tags)
+            .on_conflict(
+                OnConflict::columns([note_tags::Column::Name, note_tags::Column::Note])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&txn)
+            .await?;
+    }
+    txn.commit().await
+}
+
+pub(super) fn note_tag(
+    note: &str,
+    name: &str,
+    note_id: Option<i64>,
+    remote_note_id: Option<i64>,
+) -> note_tags::ActiveModel {
+    note_tags::ActiveModel {
+        id: ActiveValue::NotSet,
+        name: ActiveValue::Set(name.to_string()),
+        note: ActiveValue::Set(note.to_string()),
+        note_id: ActiveValue::Set(note_id),
+        remote_note_id: ActiveValue::Set(remote_note_id),
+        created_at: ActiveValue::Set(Utc::now().naive_utc()),
+    }
+}
+
+#[async_trait]
+impl NoteTagsRepository for PostgresStorage {
+    async fn set_note_tags(&self, note_id: i64, note: &str, names: &[String]) -> Result<(), DbErr> {
+        replace_note_tags(
+            &self.db,
+            note_tags::Column::NoteId.eq(note_id),
+            names
+                .iter()
+                .map(|name| note_tag(note, name, Some(note_id), None))
+                .collect(),
+        )
+        .await
+    }
+
+    async fn set_remote_note_tags(
+        &self,
+        remote_note_id: i64,
+        note: &str,
+        names: &[String],
+    ) -> Result<(), DbErr> {
+        replace_note_tags(
+            &self.db,
+            note_tags::Column::RemoteNoteId.eq(remote_note_id),
+            names
+                .iter()
+                .map(|name| note_tag(note, name, None, Some(remote_note_id)))
+                .collect(),
+        )
+        .await
+    }
+
+    async fn list_note_tags(&self, note_id: i64) -> Result<Vec<note_tags::Model>, DbErr> {
+        note_tags::Entity::find()
+            .filter(note_tags::Column::NoteId.eq(note_id))
+            .order_by_asc(note_tags::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_note_tags_of_notes(
+        &self,
+        note_ids: &[i64],
+    ) -> Result<Vec<note_tags::Model>, DbErr> {
+        note_tags::Entity::find()
+            .filter(note_tags::Column::NoteId.is_in(note_ids.iter().copied()))
+            .order_by_asc(note_tags::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn count_tagged_notes(&self, name: &str) -> Result<u64, DbErr> {
+        note_tags::Entity::find()
+            .filter(note_tags::Column::Name.eq(name))
+            .filter(listed())
+            .count(&self.db)
+            .await
+    }
+
+    async fn list_tagged_notes_page(
+        &self,
+        name: &str,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<note_tags::Model>, DbErr> {
+        note_tags::Entity::find()
+            .filter(note_tags::Column::Name.eq(name))
+            .filter(listed())
+            .order_by_desc(note_tags::Column::CreatedAt)
+            .order_by_desc(note_tags::Column::Id)
+            .limit(limit)
+            .offset(offset)
+            .all(&self.db)
+            .await
+    }
+}
+
+/// Remote notes, which are only indexed when public, and public local notes.
+fn listed() -> Condition {
+    Condition::any()
+        .add(note_tags::Column::NoteId.is_null())
+        .add(
+            note_tags::Column::NoteId.in_subquery(
+                Query::select()
+                    .column(notes::Column::Id)
+                    .from(notes::Entity)
+                    .and_where(notes::Column::Visibility.eq(Visibility::Public.as_str()))
+                    .to_owned(),
+            ),
+        )
+}
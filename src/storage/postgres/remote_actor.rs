@@ -0,0 +1,61 @@
+use crate::domain::entities::remote_actors;
+use crate::domain::repositories::remote_actors::RemoteActorsRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+
+#[async_trait]
+impl RemoteActorsRepository for PostgresStorage {
+    async fn find_remote_actor_by_uri(
+        &self,
+        uri: &str,
+    ) -> Result<Option<remote_actors::Model>, DbErr> {
+        remote_actors::Entity::find()
+            .filter(remote_actors::Column::Uri.eq(uri))
+            .one(&self.db)
+            .await
+    }
+
+    async fn find_remote_actor_by_public_key_id(
+        &self,
+        key_id: &str,
+    ) -> Result<Option<remote_actors::Model>, DbErr> {
+        remote_actors::Entity::find()
+            .filter(remote_actors::Column::PublicKeyId.eq(key_id))
+            .one(&self.db)
+            .await
+    }
+
+    async fn upsert_remote_actor(
+        &self,
+        actor: remote_actors::ActiveModel,
+    ) -> Result<remote_actors::Model, DbErr> {
+        remote_actors::Entity::insert(actor)
+            .on_conflict(
+                OnConflict::column(remote_actors::Column::Uri)
+                    .update_columns([
+                        remote_actors::Column::Inbox,
+                        remote_actors::Column::SharedInbox,
+                        remote_actors::Column::PublicKeyId,
+                        remote_actors::Column::PublicKeyPem,
+                        remote_actors::Column::PreferredUsername,
+                        remote_actors::Column::Name,
+                        remote_actors::Column::IconUrl,
+                        remote_actors::Column::Followers,
+                        remote_actors::Column::FetchedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_with_returning(&self.db)
+            .await
+    }
+
+    async fn remove_remote_actor_by_uri(&self, uri: &str) -> Result<u64, DbErr> {
+        let result = remote_actors::Entity::delete_many()
+            .filter(remote_actors::Column::Uri.eq(uri))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::domain::entities::remote_notes;
+use crate::domain::repositories::remote_notes::RemoteNotesRepository;
+use crate::storage::postgres::PostgresStorage;
+use async_trait::async_trait;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+
+#[async_trait]
+impl RemoteNotesRepository for PostgresStorage {
+    async fn add_remote_note(&self, note: remote_notes::ActiveModel) -> Result<bool, DbErr> {
+        let rows = remote_notes::Entity::insert(note)
+            .on_conflict(
+                OnConflict::column(remote_notes::Column::ApId)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+        Ok(rows > 0)
+    }
+
+    async fn update_remote_note(
+        &self,
+        note: remote_notes::ActiveModel,
+    ) -> Result<remote_notes::Model, DbErr> {
+        note.update(&self.db).await
+    }
+
+    async fn remove_remote_note(&self, id: i64) -> Result<u64, DbErr> {
+        let result = remote_notes::Entity::delete_by_id(id)
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    async fn remove_remote_notes_by_actor(&self, actor: &str) -> Result<u64, DbErr> {
+        let result = remote_notes::Entity::delete_many()
+            .filter(remote_notes::Column::Actor.eq(actor))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    async fn find_remote_note_by_ap_id(
+        &self,
+        ap_id: &str,
+    ) -> Result<Option<remote_notes::Model>, DbErr> {
+        remote_notes::Entity::find()
+            .filter(remote_notes::Column::ApId.eq(ap_id))
+            .one(&self.db)
+            .await
+    }
+
+    async fn list_remote_notes_in_reply_to(
+        &self,
+        in_reply_to: &str,
+    ) -> Result<Vec<remote_notes::Model>, DbErr> {
+        remote_notes::Entity::find()
+            .filter(remote_notes::Column::InReplyTo.eq(in_reply_to))
+            .order_by_asc(remote_notes::Column::Published)
+            .order_by_asc(remote_notes::Column::Id)
+            .all(&self.db)
+            .await
+    }
+
+    async fn list_remote_notes_by_actor(
+        &self,
+        actor: &str,
+        limit: u64,
+    ) -> Result<Vec<remote_notes::Model>, DbErr> {
+        remote_notes::Entity::find()
+            .filter(remote_notes::Column::Actor.eq(actor))
+            .order_by_desc(remote_notes::Column::Published)
+            .order_by_desc(remote_notes::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+    }
+}
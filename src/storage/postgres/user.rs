@@ -1,9 +1,11 @@
 use crate::domain::entities::users;
 use crate::domain::repositories::users::UsersRepository;
+use crate::federation::keys;
 use crate::storage::postgres::PostgresStorage;
 use async_trait::async_trait;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, DbErr, EntityTrait, QueryFilter, QuerySelect,
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter,
+    QuerySelect,
 };
 
 #[async_trait]
@@ -20,10 +22,19 @@ impl UsersRepository for PostgresStorage {
     }
 
     async fn add_user(&self, username: &str, display_name: &str) -> Result<users::Model, DbErr> {
+        let keypair = tokio::task::spawn_blocking(keys::generate_keypair)
+            .await
+            .map_err(|e| DbErr::Custom(e.to_string()))?
+            .map_err(DbErr::Custom)?;
+
         let user = users::ActiveModel {
             id: ActiveValue::NotSet,
             username: ActiveValue::Set(username.to_string()),
             display_name: ActiveValue::Set(display_name.to_string()),
+            public_key_pem: ActiveValue::Set(Some(keypair.public_key_pem)),
+            private_key_pem: ActiveValue::Set(Some(keypair.private_key_pem)),
+            manually_approves_followers: ActiveValue::Set(false),
+            hide_followers: ActiveValue::Set(false),
         };
         user.insert(&self.db).await
     }
@@ -44,4 +55,15 @@ impl UsersRepository for PostgresStorage {
             .all(&self.db)
             .await
     }
+
+    async fn list_users_without_keys(&self) -> Result<Vec<users::Model>, DbErr> {
+        users::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(users::Column::PublicKeyPem.is_null())
+                    .add(users::Column::PrivateKeyPem.is_null()),
+            )
+            .all(&self.db)
+            .await
+    }
 }
@@ -0,0 +1,179 @@
+mod helper;
+
+use axum::http::StatusCode;
+use calmi::domain::repositories::NotesRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{create_test_server, insert_note, insert_user, setup_db};
+use serde_json::Value;
+
+const BROWSER_ACCEPT: &str =
+    "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,*/*;q=0.8";
+
+#[tokio::test]
+async fn person_is_served_as_activity_json_when_asked_for() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    for accept in [
+        "application/activity+json",
+        r#"application/ld+json; profile="https://www.w3.org/ns/activitystreams""#,
+    ] {
+        let response = server
+            .get("/users/alice")
+            .add_header("accept", accept)
+            .await;
+
+        response.assert_status_ok();
+        assert_eq!(response.header("content-type"), "application/activity+json");
+        assert_eq!(response.header("vary"), "Accept");
+        let json: Value = response.json();
+        assert_eq!(json["type"], "Person");
+        assert_eq!(json["url"], "https://example.com/users/alice");
+    }
+}
+
+#[tokio::test]
+async fn person_is_served_as_a_profile_page_to_browsers() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice <3").await;
+    let note_id = insert_note(&db, "<p>Hello world</p>", author_id, vec![]).await;
+    let server = create_test_server(db);
+
+    let response = server
+        .get("/users/alice")
+        .add_header("accept", BROWSER_ACCEPT)
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "text/html; charset=utf-8");
+    let page = response.text();
+    assert!(page.contains("<h1>Alice &lt;3</h1>"));
+    assert!(page.contains("@alice@example.com"));
+    assert!(page.contains("<p>Hello world</p>"));
+    assert!(page.contains(&format!(
+        r#"href="https://example.com/users/alice/notes/{}""#,
+        note_id
+    )));
+    assert!(page.contains(
+        r#"<link rel="alternate" type="application/activity+json" href="https://example.com/users/alice">"#
+    ));
+}
+
+#[tokio::test]
+async fn scripts_in_notes_do_not_reach_the_pages() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(helper::TEST_API_TOKEN)
+        .json(&serde_json::json!({
+            "content": "<p>Hi <script>alert(1)</script><img src=x onerror=alert(2)> #rust</p>"
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let note: Value = response.json();
+
+    for path in [
+        format!("/users/alice/notes/{}", note["id"]),
+        "/users/alice".to_string(),
+    ] {
+        let page = server
+            .get(&path)
+            .add_header("accept", BROWSER_ACCEPT)
+            .await
+            .text();
+        assert!(!page.contains("<script"), "{}", path);
+        assert!(!page.contains("onerror"), "{}", path);
+        assert!(page.contains("<p>Hi "), "{}", path);
+        assert!(page.contains(r#"class="mention hashtag""#), "{}", path);
+    }
+}
+
+#[tokio::test]
+async fn refused_activity_json_gets_the_page() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let response = server
+        .get("/users/alice")
+        .add_header("accept", "application/activity+json;q=0, text/html")
+        .await;
+
+    assert_eq!(response.header("content-type"), "text/html; charset=utf-8");
+}
+
+#[tokio::test]
+async fn note_links_and_serves_its_page() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "<p>Hello world</p>", author_id, vec![]).await;
+    let server = create_test_server(db);
+    let path = format!("/users/alice/notes/{}", note_id);
+
+    let json: Value = server
+        .get(&path)
+        .add_header("accept", "application/activity+json")
+        .await
+        .json();
+    assert_eq!(json["url"], json["id"]);
+
+    let response = server.get(&path).add_header("accept", BROWSER_ACCEPT).await;
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "text/html; charset=utf-8");
+    let page = response.text();
+    assert!(page.contains("<title>Alice: Hello world</title>"));
+    assert!(page.contains("<p>Hello world</p>"));
+    assert!(page.contains(r#"<a href="https://example.com/users/alice">Alice</a>"#));
+
+    let activity = server
+        .get(&format!("{}/activity", path))
+        .add_header("accept", BROWSER_ACCEPT)
+        .await;
+    assert_eq!(activity.header("content-type"), "text/html; charset=utf-8");
+}
+
+#[tokio::test]
+async fn deleted_note_page_is_gone() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "<p>Hello world</p>", author_id, vec![]).await;
+    PostgresStorage::new(db.clone())
+        .delete_note(note_id)
+        .await
+        .unwrap();
+    let server = create_test_server(db);
+
+    let response = server
+        .get(&format!("/users/alice/notes/{}", note_id))
+        .add_header("accept", BROWSER_ACCEPT)
+        .await;
+
+    response.assert_status(StatusCode::GONE);
+    assert!(response.text().contains("This note has been deleted."));
+}
+
+#[tokio::test]
+async fn collections_are_served_as_activity_json() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    for path in [
+        "/users/alice/outbox",
+        "/users/alice/followers",
+        "/users/alice/following",
+        "/users/alice/liked",
+    ] {
+        let response = server.get(path).await;
+        assert_eq!(
+            response.header("content-type"),
+            "application/activity+json",
+            "{}",
+            path
+        );
+    }
+}
@@ -0,0 +1,160 @@
+mod helper;
+
+use axum::http::StatusCode;
+use calmi::domain::repositories::FollowsRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{TEST_API_TOKEN, create_test_server, insert_user, setup_db};
+use serde_json::{Value, json};
+
+async fn add_followers(storage: &PostgresStorage, user_id: i64, count: usize) {
+    for i in 0..count {
+        let actor = format!("https://remote.example/users/follower{}", i);
+        storage
+            .add_follow(user_id, &actor, &format!("{}/follows/1", actor), false)
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn collection_reports_total_and_links_first_page() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let storage = PostgresStorage::new(db.clone());
+    add_followers(&storage, user_id, 3).await;
+    storage
+        .add_follow(
+            user_id,
+            "https://remote.example/users/pending",
+            "https://remote.example/users/pending/follows/1",
+            true,
+        )
+        .await
+        .unwrap();
+    let server = create_test_server(db);
+
+    let response = server.get("/users/alice/followers").await;
+
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "application/activity+json");
+    let json: Value = response.json();
+    assert_eq!(json["id"], "https://example.com/users/alice/followers");
+    assert_eq!(json["type"], "OrderedCollection");
+    assert_eq!(json["totalItems"], 3);
+    assert_eq!(
+        json["first"],
+        "https://example.com/users/alice/followers?page=1"
+    );
+    assert!(json.get("orderedItems").is_none());
+}
+
+#[tokio::test]
+async fn pages_list_accepted_followers() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let storage = PostgresStorage::new(db.clone());
+    add_followers(&storage, user_id, 25).await;
+    let server = create_test_server(db);
+
+    let first: Value = server.get("/users/alice/followers?page=1").await.json();
+    assert_eq!(first["type"], "OrderedCollectionPage");
+    assert_eq!(first["partOf"], "https://example.com/users/alice/followers");
+    assert_eq!(first["orderedItems"].as_array().unwrap().len(), 20);
+    assert_eq!(
+        first["next"],
+        "https://example.com/users/alice/followers?page=2"
+    );
+    assert!(first.get("prev").is_none());
+
+    let second: Value = server.get("/users/alice/followers?page=2").await.json();
+    let items = second["orderedItems"].as_array().unwrap();
+    assert_eq!(items.len(), 5);
+    assert!(items.iter().all(Value::is_string));
+    assert!(second.get("next").is_none());
+    assert_eq!(
+        second["prev"],
+        "https://example.com/users/alice/followers?page=1"
+    );
+}
+
+#[tokio::test]
+async fn pages_out_of_range_are_refused() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let storage = PostgresStorage::new(db.clone());
+    add_followers(&storage, user_id, 1).await;
+    let note_id = helper::insert_note(&db, "Hello #rust", user_id, vec![]).await;
+    let server = create_test_server(db);
+
+    for collection in [
+        "/users/alice/followers".to_string(),
+        "/users/alice/following".to_string(),
+        "/users/alice/liked".to_string(),
+        format!("/users/alice/notes/{}/likes", note_id),
+        format!("/users/alice/notes/{}/shares", note_id),
+        format!("/users/alice/notes/{}/replies", note_id),
+        "/tags/rust".to_string(),
+    ] {
+        server
+            .get(&format!("{}?page={}", collection, u64::MAX))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        // The last page any query can reach still answers.
+        let page = i64::MAX as u64 / 20 + 1;
+        let response = server.get(&format!("{}?page={}", collection, page)).await;
+        response.assert_status_ok();
+        let json: Value = response.json();
+        assert_eq!(json["orderedItems"], json!([]), "{}", collection);
+        assert!(json.get("next").is_none());
+    }
+}
+
+#[tokio::test]
+async fn hidden_followers_only_expose_the_count() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let storage = PostgresStorage::new(db.clone());
+    add_followers(&storage, user_id, 2).await;
+    let server = create_test_server(db);
+
+    server
+        .patch("/api/users/alice")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "hide_followers": true }))
+        .await
+        .assert_status_ok();
+
+    let json: Value = server.get("/users/alice/followers").await.json();
+    assert_eq!(json["totalItems"], 2);
+    assert!(json.get("first").is_none());
+
+    server
+        .get("/users/alice/followers?page=1")
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn returns_not_found_for_unknown_user() {
+    let db = setup_db().await;
+    let server = create_test_server(db);
+
+    server
+        .get("/users/nobody/followers")
+        .await
+        .assert_status_not_found();
+}
+
+#[tokio::test]
+async fn person_links_followers_collection() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let json: Value = server.get("/users/alice").await.json();
+
+    assert_eq!(
+        json["followers"],
+        "https://example.com/users/alice/followers"
+    );
+}
@@ -0,0 +1,86 @@
+mod helper;
+
+use calmi::domain::repositories::FollowingRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{create_test_server, insert_user, setup_db};
+use serde_json::Value;
+
+async fn follow(storage: &PostgresStorage, user_id: i64, actor: &str, accepted: bool) {
+    let record = storage
+        .add_following(user_id, actor, &format!("{}#follow", actor))
+        .await
+        .unwrap();
+    if accepted {
+        storage.accept_following(record.id).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn collection_counts_accepted_follows_only() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let storage = PostgresStorage::new(db.clone());
+    follow(&storage, user_id, "https://remote.example/users/bob", true).await;
+    follow(
+        &storage,
+        user_id,
+        "https://remote.example/users/carol",
+        false,
+    )
+    .await;
+    let server = create_test_server(db);
+
+    let response = server.get("/users/alice/following").await;
+
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "application/activity+json");
+    let json: Value = response.json();
+    assert_eq!(json["id"], "https://example.com/users/alice/following");
+    assert_eq!(json["type"], "OrderedCollection");
+    assert_eq!(json["totalItems"], 1);
+    assert_eq!(
+        json["first"],
+        "https://example.com/users/alice/following?page=1"
+    );
+}
+
+#[tokio::test]
+async fn page_lists_followed_actors() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let storage = PostgresStorage::new(db.clone());
+    follow(&storage, user_id, "https://remote.example/users/bob", true).await;
+    follow(
+        &storage,
+        user_id,
+        "https://remote.example/users/carol",
+        false,
+    )
+    .await;
+    let server = create_test_server(db);
+
+    let json: Value = server.get("/users/alice/following?page=1").await.json();
+
+    assert_eq!(json["type"], "OrderedCollectionPage");
+    assert_eq!(json["partOf"], "https://example.com/users/alice/following");
+    assert_eq!(
+        json["orderedItems"],
+        serde_json::json!(["https://remote.example/users/bob"])
+    );
+    assert!(json.get("next").is_none());
+}
+
+#[tokio::test]
+async fn person_links_following_and_liked_collections() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let json: Value = server.get("/users/alice").await.json();
+
+    assert_eq!(
+        json["following"],
+        "https://example.com/users/alice/following"
+    );
+    assert_eq!(json["liked"], "https://example.com/users/alice/liked");
+}
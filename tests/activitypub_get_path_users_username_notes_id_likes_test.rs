@@ -0,0 +1,133 @@
+mod helper;
+
+use axum::http::StatusCode;
+use calmi::domain::repositories::{NoteAnnouncesRepository, NoteLikesRepository, NotesRepository};
+use calmi::storage::postgres::PostgresStorage;
+use helper::{create_test_server, insert_note, insert_user, setup_db};
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn note_embeds_likes_and_shares_counts() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Hello world", author_id, vec![]).await;
+    let storage = PostgresStorage::new(db.clone());
+    for actor in ["bob", "carol"] {
+        let actor = format!("https://remote.example/users/{}", actor);
+        storage
+            .add_like(note_id, &actor, &format!("{}/likes/1", actor))
+            .await
+            .unwrap();
+    }
+    storage
+        .add_announce(
+            note_id,
+            "https://remote.example/users/bob",
+            "https://remote.example/users/bob/announces/1",
+        )
+        .await
+        .unwrap();
+    let server = create_test_server(db);
+
+    let json: Value = server
+        .get(&format!("/users/alice/notes/{}", note_id))
+        .await
+        .json();
+
+    let note_uri = format!("https://example.com/users/alice/notes/{}", note_id);
+    assert_eq!(json["likes"]["id"], format!("{}/likes", note_uri));
+    assert_eq!(json["likes"]["type"], "OrderedCollection");
+    assert_eq!(json["likes"]["totalItems"], 2);
+    assert!(json["likes"].get("@context").is_none());
+    assert_eq!(json["shares"]["id"], format!("{}/shares", note_uri));
+    assert_eq!(json["shares"]["totalItems"], 1);
+}
+
+#[tokio::test]
+async fn likes_collection_pages_like_activities() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Hello world", author_id, vec![]).await;
+    let storage = PostgresStorage::new(db.clone());
+    storage
+        .add_like(
+            note_id,
+            "https://remote.example/users/bob",
+            "https://remote.example/users/bob/likes/1",
+        )
+        .await
+        .unwrap();
+    let server = create_test_server(db);
+    let likes_uri = format!("https://example.com/users/alice/notes/{}/likes", note_id);
+
+    let response = server
+        .get(&format!("/users/alice/notes/{}/likes", note_id))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "application/activity+json");
+    let collection: Value = response.json();
+    assert_eq!(collection["id"], likes_uri);
+    assert_eq!(collection["totalItems"], 1);
+    assert_eq!(collection["first"], format!("{}?page=1", likes_uri));
+
+    let page: Value = server
+        .get(&format!("/users/alice/notes/{}/likes?page=1", note_id))
+        .await
+        .json();
+    assert_eq!(page["partOf"], likes_uri);
+    assert_eq!(
+        page["orderedItems"],
+        json!(["https://remote.example/users/bob/likes/1"])
+    );
+}
+
+#[tokio::test]
+async fn shares_collection_pages_announce_activities() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Hello world", author_id, vec![]).await;
+    let storage = PostgresStorage::new(db.clone());
+    storage
+        .add_announce(
+            note_id,
+            "https://remote.example/users/bob",
+            "https://remote.example/users/bob/announces/1",
+        )
+        .await
+        .unwrap();
+    let server = create_test_server(db);
+
+    let collection: Value = server
+        .get(&format!("/users/alice/notes/{}/shares", note_id))
+        .await
+        .json();
+    assert_eq!(collection["totalItems"], 1);
+
+    let page: Value = server
+        .get(&format!("/users/alice/notes/{}/shares?page=1", note_id))
+        .await
+        .json();
+    assert_eq!(
+        page["orderedItems"],
+        json!(["https://remote.example/users/bob/announces/1"])
+    );
+}
+
+#[tokio::test]
+async fn collections_of_deleted_notes_are_gone() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Hello world", author_id, vec![]).await;
+    let storage = PostgresStorage::new(db.clone());
+    storage.delete_note(note_id).await.unwrap();
+    let server = create_test_server(db);
+
+    server
+        .get(&format!("/users/alice/notes/{}/likes", note_id))
+        .await
+        .assert_status(StatusCode::GONE);
+    server
+        .get(&format!("/users/alice/notes/{}/shares", note_id))
+        .await
+        .assert_status(StatusCode::GONE);
+}
@@ -62,10 +62,17 @@ async fn returns_note_as_a_valid_note_object() {
     );
     assert_eq!(json["content"], "Hello world");
     assert!(json["published"].is_string());
-    assert!(json["to"].is_array());
-    let to_array = json["to"].as_array().unwrap();
-    assert_eq!(to_array.len(), 1);
-    assert_eq!(to_array[0], "https://example.com/users/bob");
+    assert_eq!(
+        json["to"],
+        serde_json::json!(["https://www.w3.org/ns/activitystreams#Public"])
+    );
+    assert_eq!(
+        json["cc"],
+        serde_json::json!([
+            "https://example.com/users/alice/followers",
+            "https://example.com/users/bob"
+        ])
+    );
 }
 
 #[tokio::test]
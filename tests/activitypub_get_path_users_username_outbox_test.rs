@@ -0,0 +1,144 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use calmi::domain::repositories::NotesRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{create_test_server, insert_note, insert_user, setup_db};
+use serde_json::Value;
+
+/// Inserts `count` notes and returns their ids, oldest first.
+async fn insert_notes(db: &sea_orm::DatabaseConnection, author_id: i64, count: usize) -> Vec<i64> {
+    let mut ids = Vec::new();
+    for i in 0..count {
+        ids.push(insert_note(db, &format!("Note {}", i), author_id, vec![]).await);
+    }
+    ids
+}
+
+fn note_ids(page: &Value) -> Vec<i64> {
+    page["orderedItems"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|activity| {
+            let id = activity["object"]["id"].as_str().unwrap();
+            id.rsplit('/').next().unwrap().parse().unwrap()
+        })
+        .collect()
+}
+
+async fn get_page(server: &TestServer, uri: &str) -> Value {
+    let path = uri.strip_prefix("https://example.com").unwrap();
+    let response = server.get(path).await;
+    response.assert_status_ok();
+    response.json()
+}
+
+#[tokio::test]
+async fn outbox_reports_total_and_links_first_and_last_pages() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    insert_notes(&db, author_id, 3).await;
+    let server = create_test_server(db);
+
+    let response = server.get("/users/alice/outbox").await;
+
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "application/activity+json");
+    let json: Value = response.json();
+    assert_eq!(json["id"], "https://example.com/users/alice/outbox");
+    assert_eq!(json["type"], "OrderedCollection");
+    assert_eq!(json["totalItems"], 3);
+    assert_eq!(
+        json["first"],
+        "https://example.com/users/alice/outbox?page=true"
+    );
+    assert_eq!(
+        json["last"],
+        "https://example.com/users/alice/outbox?page=true&min_id=0"
+    );
+    assert!(json.get("orderedItems").is_none());
+}
+
+#[tokio::test]
+async fn pages_walk_notes_newest_first() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let mut ids = insert_notes(&db, author_id, 25).await;
+    ids.reverse();
+    let server = create_test_server(db);
+
+    let first = get_page(&server, "https://example.com/users/alice/outbox?page=true").await;
+    assert_eq!(first["type"], "OrderedCollectionPage");
+    assert_eq!(first["partOf"], "https://example.com/users/alice/outbox");
+    assert_eq!(first["totalItems"], 25);
+    assert_eq!(first["orderedItems"][0]["type"], "Create");
+    assert_eq!(note_ids(&first), ids[..20]);
+    assert!(first.get("prev").is_none());
+
+    let second = get_page(&server, first["next"].as_str().unwrap()).await;
+    assert_eq!(note_ids(&second), ids[20..]);
+    assert!(second.get("next").is_none());
+
+    let back = get_page(&server, second["prev"].as_str().unwrap()).await;
+    assert_eq!(note_ids(&back), ids[..20]);
+    assert!(back.get("prev").is_none());
+}
+
+#[tokio::test]
+async fn last_page_holds_the_oldest_notes() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let mut ids = insert_notes(&db, author_id, 25).await;
+    ids.reverse();
+    let server = create_test_server(db);
+
+    let last = get_page(
+        &server,
+        "https://example.com/users/alice/outbox?page=true&min_id=0",
+    )
+    .await;
+    assert_eq!(note_ids(&last), ids[5..]);
+    assert!(last.get("next").is_none());
+
+    let newer = get_page(&server, last["prev"].as_str().unwrap()).await;
+    assert_eq!(note_ids(&newer), ids[..5]);
+    assert!(newer.get("prev").is_none());
+}
+
+#[tokio::test]
+async fn deleted_notes_are_left_out() {
+    let db = setup_db().await;
+    let author_id = insert_user(&db, "alice", "Alice").await;
+    let ids = insert_notes(&db, author_id, 3).await;
+    let storage = PostgresStorage::new(db.clone());
+    storage.delete_note(ids[1]).await.unwrap();
+    let server = create_test_server(db);
+
+    let page = get_page(&server, "https://example.com/users/alice/outbox?page=true").await;
+
+    assert_eq!(page["totalItems"], 2);
+    assert_eq!(note_ids(&page), vec![ids[2], ids[0]]);
+}
+
+#[tokio::test]
+async fn rejects_cursors_that_are_not_the_authors_notes() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let bob_id = insert_user(&db, "bob", "Bob").await;
+    let bob_note = insert_note(&db, "Hi", bob_id, vec![]).await;
+    let server = create_test_server(db);
+
+    server
+        .get(&format!(
+            "/users/alice/outbox?page=true&max_id={}",
+            bob_note
+        ))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .get("/users/alice/outbox?page=true&max_id=1&min_id=1")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
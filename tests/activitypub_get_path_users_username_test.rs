@@ -1,6 +1,10 @@
 mod helper;
 
+use calmi::app::keys;
+use calmi::domain::repositories::UsersRepository;
+use calmi::storage::postgres::PostgresStorage;
 use helper::{create_test_server, insert_user, setup_db};
+use sea_orm::{ActiveValue, IntoActiveModel};
 use serde_json::Value;
 
 #[tokio::test]
@@ -57,6 +61,52 @@ async fn inbox_and_outbox_returned_with_person_are_strings_not_objects() {
     assert!(json["outbox"].is_string());
 }
 
+#[tokio::test]
+async fn returns_public_key_of_the_actor() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let response = server.get("/users/alice").await;
+
+    response.assert_status_ok();
+
+    let json: Value = response.json();
+
+    let public_key = &json["publicKey"];
+    assert_eq!(public_key["id"], "https://example.com/users/alice#main-key");
+    assert_eq!(public_key["owner"], "https://example.com/users/alice");
+    let pem = public_key["publicKeyPem"].as_str().unwrap();
+    assert!(pem.starts_with("-----BEGIN PUBLIC KEY-----"));
+    assert!(!json.to_string().contains("PRIVATE KEY"));
+}
+
+#[tokio::test]
+async fn users_created_before_keys_existed_are_given_one() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let storage = PostgresStorage::new(db.clone());
+    let mut user = storage
+        .find_user_by_id(user_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .into_active_model();
+    user.public_key_pem = ActiveValue::Set(None);
+    user.private_key_pem = ActiveValue::Set(None);
+    storage.update_user(user).await.unwrap();
+
+    assert_eq!(keys::backfill_user_keys(&storage).await.unwrap(), 1);
+    assert_eq!(keys::backfill_user_keys(&storage).await.unwrap(), 0);
+
+    let server = create_test_server(db);
+    let json: Value = server.get("/users/alice").await.json();
+    let pem = json["publicKey"]["publicKeyPem"].as_str().unwrap();
+    assert!(pem.starts_with("-----BEGIN PUBLIC KEY-----"));
+    let user = storage.find_user_by_id(user_id).await.unwrap().unwrap();
+    assert!(user.private_key_pem.is_some());
+}
+
 #[tokio::test]
 async fn returns_404_for_unknown_user() {
     let db = setup_db().await;
@@ -86,4 +136,8 @@ async fn multiple_users_have_different_actors() {
     assert_eq!(bob["id"], "https://example.com/users/bob");
     assert_eq!(alice["name"], "Alice");
     assert_eq!(bob["name"], "Bob");
+    assert_ne!(
+        alice["publicKey"]["publicKeyPem"],
+        bob["publicKey"]["publicKeyPem"]
+    );
 }
@@ -0,0 +1,180 @@
+mod helper;
+
+use axum::http::StatusCode;
+use calmi::domain::repositories::RemoteNotesRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{create_test_server, insert_user, post_signed, setup_db, spawn_remote_server};
+use serde_json::{Value, json};
+
+fn note(id: &str, author: &str) -> Value {
+    json!({
+        "id": id,
+        "type": "Note",
+        "attributedTo": author,
+        "content": "<p>Hello Alice</p>",
+        "to": ["https://www.w3.org/ns/activitystreams#Public"],
+        "cc": [format!("{}/followers", author), "https://example.com/users/alice"],
+        "inReplyTo": "https://example.com/users/alice/notes/1",
+        "published": "2025-11-24T10:00:00Z"
+    })
+}
+
+fn create(actor: &str, object: Value) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/activities/create-1", actor),
+        "type": "Create",
+        "actor": actor,
+        "object": object
+    })
+}
+
+#[tokio::test]
+async fn stores_embedded_note() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note_id = format!("{}/notes/1", bob.id);
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(&bob.id, note(&note_id, &bob.id)),
+    )
+    .await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let storage = PostgresStorage::new(db);
+    let stored = storage
+        .find_remote_note_by_ap_id(&note_id)
+        .await
+        .unwrap()
+        .expect("Note should be stored");
+    assert_eq!(stored.actor, bob.id);
+    assert_eq!(stored.content, "<p>Hello Alice</p>");
+    assert_eq!(
+        stored.to,
+        vec!["https://www.w3.org/ns/activitystreams#Public".to_string()]
+    );
+    assert_eq!(
+        stored.cc,
+        vec![
+            format!("{}/followers", bob.id),
+            "https://example.com/users/alice".to_string()
+        ]
+    );
+    assert_eq!(
+        stored.in_reply_to.as_deref(),
+        Some("https://example.com/users/alice/notes/1")
+    );
+    assert_eq!(
+        stored.published.unwrap().to_string(),
+        "2025-11-24 10:00:00".to_string()
+    );
+}
+
+#[tokio::test]
+async fn ignores_duplicate_note() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note_id = format!("{}/notes/1", bob.id);
+    let activity = create(&bob.id, note(&note_id, &bob.id));
+
+    post_signed(&server, "/users/alice/inbox", &bob, &activity)
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+    post_signed(&server, "/users/alice/inbox", &bob, &activity)
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+
+    let storage = PostgresStorage::new(db);
+    let notes = storage
+        .list_remote_notes_by_actor(&bob.id, 10)
+        .await
+        .unwrap();
+    assert_eq!(notes.len(), 1);
+}
+
+#[tokio::test]
+async fn fetches_note_referenced_by_id() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note_id = format!("{}/objects/note-1", remote.base_url);
+    remote.set_object("note-1", note(&note_id, &bob.id)).await;
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(&bob.id, json!(note_id)),
+    )
+    .await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let storage = PostgresStorage::new(db);
+    let stored = storage
+        .find_remote_note_by_ap_id(&note_id)
+        .await
+        .unwrap()
+        .expect("Fetched note should be stored");
+    assert_eq!(stored.actor, bob.id);
+    assert_eq!(stored.content, "<p>Hello Alice</p>");
+}
+
+#[tokio::test]
+async fn rejects_note_attributed_to_another_actor() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let carol = remote.add_actor("carol").await;
+    let note_id = format!("{}/notes/1", carol.id);
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(&bob.id, note(&note_id, &carol.id)),
+    )
+    .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    let storage = PostgresStorage::new(db);
+    assert!(
+        storage
+            .find_remote_note_by_ap_id(&note_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn rejects_note_from_another_origin() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note_id = "https://elsewhere.example/notes/1";
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(&bob.id, note(note_id, &bob.id)),
+    )
+    .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+}
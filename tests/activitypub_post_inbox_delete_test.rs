@@ -0,0 +1,222 @@
+mod helper;
+
+use axum::http::StatusCode;
+use calmi::domain::repositories::{
+    FollowsRepository, NoteAnnouncesRepository, NoteLikesRepository, RemoteNotesRepository,
+};
+use calmi::federation::{self, actor::ActorResolver};
+use calmi::storage::postgres::PostgresStorage;
+use helper::{
+    create_test_server, insert_note, insert_user, post_signed, setup_db, spawn_remote_server,
+};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+fn create_note(actor: &str, note_id: &str) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/activity", note_id),
+        "type": "Create",
+        "actor": actor,
+        "object": {
+            "id": note_id,
+            "type": "Note",
+            "attributedTo": actor,
+            "content": "Soon to be gone"
+        }
+    })
+}
+
+fn delete(actor: &str, object: Value) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}#delete", actor),
+        "type": "Delete",
+        "actor": actor,
+        "object": object
+    })
+}
+
+#[tokio::test]
+async fn deletes_remote_note_replaced_by_tombstone() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note_id = format!("{}/notes/1", bob.id);
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create_note(&bob.id, &note_id),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &delete(&bob.id, json!({"id": note_id, "type": "Tombstone"})),
+    )
+    .await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let storage = PostgresStorage::new(db);
+    assert!(
+        storage
+            .find_remote_note_by_ap_id(&note_id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn refuses_to_delete_note_of_another_actor() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let mallory = remote.add_actor("mallory").await;
+    let note_id = format!("{}/notes/1", bob.id);
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create_note(&bob.id, &note_id),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &mallory,
+        &delete(&mallory.id, json!(note_id)),
+    )
+    .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    let storage = PostgresStorage::new(db);
+    assert!(
+        storage
+            .find_remote_note_by_ap_id(&note_id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn ignores_delete_of_unknown_object() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &delete(&bob.id, json!(format!("{}/notes/404", bob.id))),
+    )
+    .await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+}
+
+#[tokio::test]
+async fn deleting_actor_drops_their_follows_likes_and_announces() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Hello", alice_id, vec![]).await;
+    let server = create_test_server(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let carol = remote.add_actor("carol").await;
+
+    for actor in [&bob.id, &carol.id] {
+        storage
+            .add_follow(alice_id, actor, &format!("{}/follows/1", actor), false)
+            .await
+            .unwrap();
+        storage
+            .add_like(note_id, actor, &format!("{}/likes/1", actor))
+            .await
+            .unwrap();
+        storage
+            .add_announce(note_id, actor, &format!("{}/announces/1", actor))
+            .await
+            .unwrap();
+    }
+    let bob_note = format!("{}/notes/1", bob.id);
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create_note(&bob.id, &bob_note),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &delete(&bob.id, json!(bob.id)),
+    )
+    .await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let followers = storage.list_followers(alice_id).await.unwrap();
+    assert_eq!(followers.len(), 1);
+    assert_eq!(followers[0].actor, carol.id);
+    let likes = storage.list_likes(note_id).await.unwrap();
+    assert_eq!(likes.len(), 1);
+    assert_eq!(likes[0].actor, carol.id);
+    let announces = storage.list_announces(note_id).await.unwrap();
+    assert_eq!(announces.len(), 1);
+    assert_eq!(announces[0].actor, carol.id);
+    assert!(
+        storage
+            .find_remote_note_by_ap_id(&bob_note)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn deleting_actor_is_verified_with_the_stored_key_once_they_are_gone() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    storage
+        .add_follow(alice_id, &bob.id, &format!("{}/follows/1", bob.id), false)
+        .await
+        .unwrap();
+    ActorResolver::new(federation::build_http_client(), Duration::from_secs(3600))
+        .resolve(&storage, &bob.id)
+        .await
+        .unwrap();
+
+    remote.remove_actor("bob").await;
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &delete(&bob.id, json!(bob.id)),
+    )
+    .await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+    assert!(storage.list_followers(alice_id).await.unwrap().is_empty());
+}
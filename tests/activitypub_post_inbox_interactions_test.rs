@@ -1,9 +1,14 @@
 mod helper;
 
 use axum::http::StatusCode;
-use calmi::domain::repositories::{FollowsRepository, NoteAnnouncesRepository, NoteLikesRepository};
+use calmi::domain::repositories::{
+    FollowsRepository, NoteAnnouncesRepository, NoteLikesRepository,
+};
 use calmi::storage::postgres::PostgresStorage;
-use helper::{create_test_server, insert_note, insert_user, setup_db};
+use helper::{
+    create_test_server, create_test_state, insert_note, insert_user, post_signed, setup_db,
+    spawn_remote_server,
+};
 use serde_json::json;
 
 #[tokio::test]
@@ -11,19 +16,18 @@ async fn test_inbox_follow_is_persisted_and_undo_removes_it() {
     let db = setup_db().await;
     let user_id = insert_user(&db, "alice", "Alice").await;
     let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
 
     let follow_activity = json!({
         "@context": "https://www.w3.org/ns/activitystreams",
         "id": "https://remote.example/follow/123",
         "type": "Follow",
-        "actor": "https://remote.example/users/bob",
+        "actor": bob.id,
         "object": "https://example.com/users/alice"
     });
 
-    let follow_response = server
-        .post("/users/alice/inbox")
-        .json(&follow_activity)
-        .await;
+    let follow_response = post_signed(&server, "/users/alice/inbox", &bob, &follow_activity).await;
 
     follow_response.assert_status(StatusCode::ACCEPTED);
 
@@ -34,7 +38,7 @@ async fn test_inbox_follow_is_persisted_and_undo_removes_it() {
         .expect("Failed to list followers");
 
     assert_eq!(followers.len(), 1);
-    assert_eq!(followers[0].actor, "https://remote.example/users/bob");
+    assert_eq!(followers[0].actor, bob.id);
     assert_eq!(
         followers[0].activity_id,
         "https://remote.example/follow/123"
@@ -44,16 +48,16 @@ async fn test_inbox_follow_is_persisted_and_undo_removes_it() {
         "@context": "https://www.w3.org/ns/activitystreams",
         "id": "https://remote.example/undo/123",
         "type": "Undo",
-        "actor": "https://remote.example/users/bob",
+        "actor": bob.id,
         "object": {
             "id": "https://remote.example/follow/123",
             "type": "Follow",
-            "actor": "https://remote.example/users/bob",
+            "actor": bob.id,
             "object": "https://example.com/users/alice"
         }
     });
 
-    let undo_response = server.post("/users/alice/inbox").json(&undo_activity).await;
+    let undo_response = post_signed(&server, "/users/alice/inbox", &bob, &undo_activity).await;
     undo_response.assert_status(StatusCode::ACCEPTED);
 
     let followers_after = FollowsRepository::list_followers(&storage, user_id)
@@ -63,12 +67,56 @@ async fn test_inbox_follow_is_persisted_and_undo_removes_it() {
     assert!(followers_after.is_empty());
 }
 
+#[tokio::test]
+async fn test_inbox_follow_sends_accept_to_follower() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let follow_activity = json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": "https://remote.example/follow/456",
+        "type": "Follow",
+        "actor": bob.id,
+        "object": "https://example.com/users/alice"
+    });
+
+    post_signed(&server, "/users/alice/inbox", &bob, &follow_activity)
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+
+    let delivered = calmi::app::jobs::run_due_jobs(&state)
+        .await
+        .expect("Failed to run jobs");
+    assert_eq!(delivered, 1);
+
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].path, "/users/bob/inbox");
+
+    let accept = &received[0].body;
+    assert_eq!(accept["type"], "Accept");
+    assert_eq!(accept["actor"], "https://example.com/users/alice");
+    assert_eq!(accept["object"]["type"], "Follow");
+    assert_eq!(accept["object"]["id"], "https://remote.example/follow/456");
+    assert_eq!(accept["object"]["actor"], bob.id);
+    assert_eq!(
+        accept["object"]["object"],
+        "https://example.com/users/alice"
+    );
+}
+
 #[tokio::test]
 async fn test_inbox_like_and_undo_remove_reaction() {
     let db = setup_db().await;
     let user_id = insert_user(&db, "alice", "Alice").await;
     let note_id = insert_note(&db, "hello", user_id, vec![]).await;
     let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
 
     let note_url = format!("https://example.com/users/alice/notes/{}", note_id);
 
@@ -76,11 +124,11 @@ async fn test_inbox_like_and_undo_remove_reaction() {
         "@context": "https://www.w3.org/ns/activitystreams",
         "id": "https://remote.example/like/1",
         "type": "Like",
-        "actor": "https://remote.example/users/bob",
+        "actor": bob.id,
         "object": note_url
     });
 
-    let like_response = server.post("/users/alice/inbox").json(&like_activity).await;
+    let like_response = post_signed(&server, "/users/alice/inbox", &bob, &like_activity).await;
     like_response.assert_status(StatusCode::ACCEPTED);
 
     let storage = PostgresStorage::new(db.clone());
@@ -89,23 +137,23 @@ async fn test_inbox_like_and_undo_remove_reaction() {
         .await
         .expect("Failed to list likes");
     assert_eq!(likes.len(), 1);
-    assert_eq!(likes[0].actor, "https://remote.example/users/bob");
+    assert_eq!(likes[0].actor, bob.id);
     assert_eq!(likes[0].activity_id, "https://remote.example/like/1");
 
     let undo_like = json!({
         "@context": "https://www.w3.org/ns/activitystreams",
         "id": "https://remote.example/undo/like/1",
         "type": "Undo",
-        "actor": "https://remote.example/users/bob",
+        "actor": bob.id,
         "object": {
             "id": "https://remote.example/like/1",
             "type": "Like",
-            "actor": "https://remote.example/users/bob",
+            "actor": bob.id,
             "object": format!("https://example.com/users/alice/notes/{}", note_id)
         }
     });
 
-    let undo_like_response = server.post("/users/alice/inbox").json(&undo_like).await;
+    let undo_like_response = post_signed(&server, "/users/alice/inbox", &bob, &undo_like).await;
     undo_like_response.assert_status(StatusCode::ACCEPTED);
 
     let likes_after = NoteLikesRepository::list_likes(&storage, note_id)
@@ -120,20 +168,20 @@ async fn test_inbox_announce_is_persisted() {
     let user_id = insert_user(&db, "alice", "Alice").await;
     let note_id = insert_note(&db, "boost me", user_id, vec![]).await;
     let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
 
     let note_url = format!("https://example.com/users/alice/notes/{}", note_id);
     let announce_activity = json!({
         "@context": "https://www.w3.org/ns/activitystreams",
         "id": "https://remote.example/announce/1",
         "type": "Announce",
-        "actor": "https://remote.example/users/bob",
+        "actor": bob.id,
         "object": note_url
     });
 
-    let announce_response = server
-        .post("/users/alice/inbox")
-        .json(&announce_activity)
-        .await;
+    let announce_response =
+        post_signed(&server, "/users/alice/inbox", &bob, &announce_activity).await;
     announce_response.assert_status(StatusCode::ACCEPTED);
 
     let storage = PostgresStorage::new(db.clone());
@@ -143,7 +191,7 @@ async fn test_inbox_announce_is_persisted() {
         .expect("Failed to list announces");
 
     assert_eq!(announces.len(), 1);
-    assert_eq!(announces[0].actor, "https://remote.example/users/bob");
+    assert_eq!(announces[0].actor, bob.id);
     assert_eq!(
         announces[0].activity_id,
         "https://remote.example/announce/1"
@@ -156,6 +204,8 @@ async fn test_inbox_undo_activity_id_only_removes_like() {
     let user_id = insert_user(&db, "alice", "Alice").await;
     let note_id = insert_note(&db, "hello", user_id, vec![]).await;
     let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let charlie = remote.add_actor("charlie").await;
 
     let note_url = format!("https://example.com/users/alice/notes/{}", note_id);
 
@@ -163,13 +213,11 @@ async fn test_inbox_undo_activity_id_only_removes_like() {
         "@context": "https://www.w3.org/ns/activitystreams",
         "id": "https://remote.example/like/2",
         "type": "Like",
-        "actor": "https://remote.example/users/charlie",
+        "actor": charlie.id,
         "object": note_url
     });
 
-    server
-        .post("/users/alice/inbox")
-        .json(&like_activity)
+    post_signed(&server, "/users/alice/inbox", &charlie, &like_activity)
         .await
         .assert_status(StatusCode::ACCEPTED);
 
@@ -177,13 +225,11 @@ async fn test_inbox_undo_activity_id_only_removes_like() {
         "@context": "https://www.w3.org/ns/activitystreams",
         "id": "https://remote.example/undo/like/2",
         "type": "Undo",
-        "actor": "https://remote.example/users/charlie",
+        "actor": charlie.id,
         "object": "https://remote.example/like/2"
     });
 
-    server
-        .post("/users/alice/inbox")
-        .json(&undo_activity)
+    post_signed(&server, "/users/alice/inbox", &charlie, &undo_activity)
         .await
         .assert_status(StatusCode::ACCEPTED);
 
@@ -194,3 +240,43 @@ async fn test_inbox_undo_activity_id_only_removes_like() {
         .expect("Failed to list likes");
     assert!(likes.is_empty());
 }
+
+#[tokio::test]
+async fn test_inbox_undo_of_someone_elses_like_is_ignored() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "hello", user_id, vec![]).await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let mallory = remote.add_actor("mallory").await;
+
+    let like_activity = json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": "https://remote.example/like/3",
+        "type": "Like",
+        "actor": bob.id,
+        "object": format!("https://example.com/users/alice/notes/{}", note_id)
+    });
+    post_signed(&server, "/users/alice/inbox", &bob, &like_activity)
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+
+    let undo_activity = json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": "https://remote.example/undo/like/3",
+        "type": "Undo",
+        "actor": mallory.id,
+        "object": "https://remote.example/like/3"
+    });
+    post_signed(&server, "/users/alice/inbox", &mallory, &undo_activity)
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+
+    let storage = PostgresStorage::new(db.clone());
+    let likes = NoteLikesRepository::list_likes(&storage, note_id)
+        .await
+        .expect("Failed to list likes");
+    assert_eq!(likes.len(), 1);
+    assert_eq!(likes[0].actor, bob.id);
+}
@@ -0,0 +1,308 @@
+mod helper;
+
+use axum::http::{HeaderMap, Method, StatusCode};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use calmi::domain::repositories::FollowsRepository;
+use calmi::federation::http_signature::{build_signing_string, digest_header, format_http_date};
+use calmi::storage::postgres::PostgresStorage;
+use helper::{
+    RemoteActor, create_test_server, insert_user, post_signed, setup_db, spawn_remote_server,
+};
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::signature::{SignatureEncoding, Signer};
+use serde_json::{Value, json};
+
+fn follow_from(actor: &str) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/follows/1", actor),
+        "type": "Follow",
+        "actor": actor,
+        "object": "https://example.com/users/alice"
+    })
+}
+
+/// Builds a Signature header by hand, so tests can sign with arbitrary dates.
+fn signature_for(actor: &RemoteActor, date: &str, digest: &str) -> String {
+    signature_covering(
+        actor,
+        date,
+        digest,
+        &["(request-target)", "host", "date", "digest"],
+    )
+}
+
+fn signature_covering(
+    actor: &RemoteActor,
+    date: &str,
+    digest: &str,
+    signed_headers: &[&str],
+) -> String {
+    let mut headers = HeaderMap::new();
+    headers.insert("host", "example.com".parse().unwrap());
+    headers.insert("date", date.parse().unwrap());
+    headers.insert("digest", digest.parse().unwrap());
+    let signed_headers: Vec<String> = signed_headers.iter().map(|h| h.to_string()).collect();
+    let signing_string = build_signing_string(
+        &Method::POST,
+        "/users/alice/inbox",
+        &headers,
+        &signed_headers,
+    )
+    .unwrap();
+
+    let private_key = rsa::RsaPrivateKey::from_pkcs8_pem(&actor.private_key_pem).unwrap();
+    let signing_key = rsa::pkcs1v15::SigningKey::<sha2::Sha256>::new(private_key);
+    let signature = signing_key.sign(signing_string.as_bytes());
+
+    format!(
+        r#"keyId="{}",algorithm="rsa-sha256",headers="{}",signature="{}""#,
+        actor.key_id,
+        signed_headers.join(" "),
+        BASE64.encode(signature.to_bytes())
+    )
+}
+
+#[tokio::test]
+async fn accepts_request_signed_by_the_activity_actor() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let response = post_signed(&server, "/users/alice/inbox", &bob, &follow_from(&bob.id)).await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let storage = PostgresStorage::new(db);
+    let followers = FollowsRepository::list_followers(&storage, user_id)
+        .await
+        .unwrap();
+    assert_eq!(followers.len(), 1);
+}
+
+#[tokio::test]
+async fn rejects_unsigned_request() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+
+    let response = server
+        .post("/users/alice/inbox")
+        .json(&follow_from("https://remote.example/users/mallory"))
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    let storage = PostgresStorage::new(db);
+    let followers = FollowsRepository::list_followers(&storage, user_id)
+        .await
+        .unwrap();
+    assert!(followers.is_empty());
+}
+
+#[tokio::test]
+async fn rejects_request_whose_key_owner_is_not_the_actor() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let carol = remote.add_actor("carol").await;
+
+    let response = post_signed(&server, "/users/alice/inbox", &bob, &follow_from(&carol.id)).await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    let storage = PostgresStorage::new(db);
+    let followers = FollowsRepository::list_followers(&storage, user_id)
+        .await
+        .unwrap();
+    assert!(followers.is_empty());
+}
+
+#[tokio::test]
+async fn rejects_body_that_does_not_match_digest() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let signed_body = serde_json::to_vec(&follow_from(&bob.id)).unwrap();
+    let digest = digest_header(&signed_body);
+    let date = format_http_date(chrono::Utc::now());
+    let tampered_body = serde_json::to_vec(&json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/follows/2", bob.id),
+        "type": "Follow",
+        "actor": bob.id,
+        "object": "https://example.com/users/alice"
+    }))
+    .unwrap();
+
+    let response = server
+        .post("/users/alice/inbox")
+        .add_header("host", "example.com")
+        .add_header("date", date.clone())
+        .add_header("digest", digest.clone())
+        .add_header("signature", signature_for(&bob, &date, &digest))
+        .content_type("application/activity+json")
+        .bytes(tampered_body.into())
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rejects_request_with_stale_date() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let body = serde_json::to_vec(&follow_from(&bob.id)).unwrap();
+    let digest = digest_header(&body);
+    let date = format_http_date(chrono::Utc::now() - chrono::Duration::hours(3));
+
+    let response = server
+        .post("/users/alice/inbox")
+        .add_header("host", "example.com")
+        .add_header("date", date.clone())
+        .add_header("digest", digest.clone())
+        .add_header("signature", signature_for(&bob, &date, &digest))
+        .content_type("application/activity+json")
+        .bytes(body.into())
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rejects_request_signed_with_a_foreign_key() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let mallory = remote.add_actor("mallory").await;
+
+    // Mallory signs with their own key but presents it as Bob's.
+    let forged = RemoteActor {
+        id: bob.id.clone(),
+        key_id: bob.key_id.clone(),
+        private_key_pem: mallory.private_key_pem.clone(),
+    };
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &forged,
+        &follow_from(&bob.id),
+    )
+    .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn rejects_key_document_claiming_an_actor_on_another_host() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let evil = spawn_remote_server().await;
+
+    // Mallory serves their own key under Bob's id.
+    let keypair = calmi::federation::keys::generate_keypair().unwrap();
+    let key_id = format!("{}/objects/k#main-key", evil.base_url);
+    evil.set_object(
+        "k",
+        json!({
+            "@context": ["https://www.w3.org/ns/activitystreams", "https://w3id.org/security/v1"],
+            "id": bob.id,
+            "type": "Person",
+            "inbox": format!("{}/inbox", bob.id),
+            "publicKey": {
+                "id": key_id,
+                "owner": bob.id,
+                "publicKeyPem": keypair.public_key_pem
+            }
+        }),
+    )
+    .await;
+    let forged = RemoteActor {
+        id: bob.id.clone(),
+        key_id,
+        private_key_pem: keypair.private_key_pem,
+    };
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &forged,
+        &follow_from(&bob.id),
+    )
+    .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    let storage = PostgresStorage::new(db);
+    let followers = FollowsRepository::list_followers(&storage, user_id)
+        .await
+        .unwrap();
+    assert!(followers.is_empty());
+}
+
+#[tokio::test]
+async fn rejects_request_whose_signature_does_not_cover_host() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let body = serde_json::to_vec(&follow_from(&bob.id)).unwrap();
+    let digest = digest_header(&body);
+    let date = format_http_date(chrono::Utc::now());
+
+    let response = server
+        .post("/users/alice/inbox")
+        .add_header("host", "example.com")
+        .add_header("date", date.clone())
+        .add_header("digest", digest.clone())
+        .add_header(
+            "signature",
+            signature_covering(
+                &bob,
+                &date,
+                &digest,
+                &["(request-target)", "date", "digest"],
+            ),
+        )
+        .content_type("application/activity+json")
+        .bytes(body.into())
+        .await;
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn accepts_request_signed_with_a_rotated_key() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    insert_user(&db, "carol", "Carol").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    post_signed(&server, "/users/alice/inbox", &bob, &follow_from(&bob.id))
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+
+    let rotated = remote.add_actor("bob").await;
+    assert_eq!(rotated.key_id, bob.key_id);
+    let mut follow = follow_from(&bob.id);
+    follow["id"] = json!(format!("{}/follows/2", bob.id));
+    follow["object"] = json!("https://example.com/users/carol");
+    post_signed(&server, "/users/carol/inbox", &rotated, &follow)
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+}
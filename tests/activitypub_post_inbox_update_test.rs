@@ -0,0 +1,187 @@
+mod helper;
+
+use axum::http::StatusCode;
+use calmi::domain::repositories::{RemoteActorsRepository, RemoteNotesRepository};
+use calmi::storage::postgres::PostgresStorage;
+use helper::{create_test_server, insert_user, post_signed, setup_db, spawn_remote_server};
+use serde_json::{Value, json};
+
+fn activity(kind: &str, actor: &str, object: Value) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}#{}", actor, kind.to_lowercase()),
+        "type": kind,
+        "actor": actor,
+        "object": object
+    })
+}
+
+fn note(id: &str, author: &str, content: &str) -> Value {
+    json!({
+        "id": id,
+        "type": "Note",
+        "attributedTo": author,
+        "content": content,
+        "to": ["https://www.w3.org/ns/activitystreams#Public"]
+    })
+}
+
+#[tokio::test]
+async fn update_replaces_note_content() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note_id = format!("{}/notes/1", bob.id);
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity("Create", &bob.id, note(&note_id, &bob.id, "Helo")),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let mut edited = note(&note_id, &bob.id, "Hello");
+    edited["updated"] = json!("2025-11-26T09:30:00Z");
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity("Update", &bob.id, edited),
+    )
+    .await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let storage = PostgresStorage::new(db);
+    let stored = storage
+        .find_remote_note_by_ap_id(&note_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.content, "Hello");
+    assert_eq!(
+        stored.updated.unwrap().to_string(),
+        "2025-11-26 09:30:00".to_string()
+    );
+}
+
+#[tokio::test]
+async fn refuses_update_of_note_by_another_actor() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let mallory = remote.add_actor("mallory").await;
+    let note_id = format!("{}/notes/1", bob.id);
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity("Create", &bob.id, note(&note_id, &bob.id, "Original")),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &mallory,
+        &activity(
+            "Update",
+            &mallory.id,
+            note(&note_id, &mallory.id, "Hijacked"),
+        ),
+    )
+    .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    let storage = PostgresStorage::new(db);
+    let stored = storage
+        .find_remote_note_by_ap_id(&note_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.content, "Original");
+    assert!(stored.updated.is_none());
+}
+
+#[tokio::test]
+async fn update_refreshes_cached_actor() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity(
+            "Update",
+            &bob.id,
+            json!({
+                "id": bob.id,
+                "type": "Person",
+                "preferredUsername": "bob",
+                "name": "Robert",
+                "inbox": format!("{}/inbox", bob.id),
+                "endpoints": {"sharedInbox": format!("{}/inbox", remote.base_url)}
+            }),
+        ),
+    )
+    .await;
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let storage = PostgresStorage::new(db);
+    let cached = storage
+        .find_remote_actor_by_uri(&bob.id)
+        .await
+        .unwrap()
+        .expect("Actor should be cached");
+    assert_eq!(cached.name.as_deref(), Some("Robert"));
+    assert_eq!(
+        cached.shared_inbox,
+        Some(format!("{}/inbox", remote.base_url))
+    );
+}
+
+#[tokio::test]
+async fn refuses_update_of_another_actor() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let mallory = remote.add_actor("mallory").await;
+
+    let response = post_signed(
+        &server,
+        "/users/alice/inbox",
+        &mallory,
+        &activity(
+            "Update",
+            &mallory.id,
+            json!({
+                "id": bob.id,
+                "type": "Person",
+                "name": "Not Bob",
+                "inbox": format!("{}/inbox", mallory.id)
+            }),
+        ),
+    )
+    .await;
+
+    response.assert_status(StatusCode::FORBIDDEN);
+    let storage = PostgresStorage::new(db);
+    assert!(
+        storage
+            .find_remote_actor_by_uri(&bob.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
@@ -0,0 +1,271 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use calmi::app::jobs;
+use helper::{
+    RemoteActor, TEST_API_TOKEN, create_test_server, create_test_state, insert_user, post_signed,
+    setup_db, spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+const ALICE: &str = "https://example.com/users/alice";
+
+fn create(actor: &RemoteActor, object: Value) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/activity", object["id"].as_str().unwrap()),
+        "type": "Create",
+        "actor": actor.id,
+        "object": object
+    })
+}
+
+async fn conversations_of(server: &TestServer, username: &str) -> Value {
+    let response = server
+        .get(&format!("/api/users/{}/conversations", username))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await;
+    response.assert_status_ok();
+    response.json()
+}
+
+#[tokio::test]
+async fn direct_messages_are_grouped_into_a_conversation() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    insert_user(&db, "carol", "Carol").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let message = format!("{}/notes/1", bob.id);
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(
+            &bob,
+            json!({
+                "id": message,
+                "type": "Note",
+                "attributedTo": bob.id,
+                "content": "Psst, Alice",
+                "to": [ALICE]
+            }),
+        ),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({
+            "content": "Hi Bob",
+            "visibility": "direct",
+            "in_reply_to": message
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let reply: Value = response.json();
+    let reply_uri = format!("{}/notes/{}", ALICE, reply["id"]);
+
+    let conversations = conversations_of(&server, "alice").await;
+    assert_eq!(conversations.as_array().unwrap().len(), 1);
+    assert_eq!(conversations[0]["participants"], json!([bob.id, ALICE]));
+
+    let response = server
+        .get(&format!(
+            "/api/users/alice/conversations/{}",
+            conversations[0]["id"]
+        ))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await;
+    response.assert_status_ok();
+    let conversation: Value = response.json();
+    let messages = conversation["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0]["id"], message);
+    assert_eq!(messages[0]["actor"], bob.id);
+    assert_eq!(messages[1]["id"], reply_uri);
+    assert_eq!(messages[1]["in_reply_to"], message);
+
+    // The reply goes to Bob alone and stays out of public listings.
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].path, "/users/bob/inbox");
+    let outbox: Value = server.get("/users/alice/outbox").await.json();
+    assert_eq!(outbox["totalItems"], 0);
+
+    // Others see neither the conversation nor its messages.
+    assert_eq!(conversations_of(&server, "carol").await, json!([]));
+    server
+        .get(&format!(
+            "/api/users/carol/conversations/{}",
+            conversations[0]["id"]
+        ))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn conversations_named_by_another_server_are_not_joined() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let other_remote = spawn_remote_server().await;
+    let mallory = other_remote.add_actor("mallory").await;
+
+    let context = format!("{}/contexts/1", remote.base_url);
+    for (actor, n) in [(&bob, 1), (&mallory, 2)] {
+        post_signed(
+            &server,
+            "/users/alice/inbox",
+            actor,
+            &create(
+                actor,
+                json!({
+                    "id": format!("{}/notes/{}", actor.id, n),
+                    "type": "Note",
+                    "attributedTo": actor.id,
+                    "content": "Psst, Alice",
+                    "to": [ALICE],
+                    "context": context
+                }),
+            ),
+        )
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+    }
+
+    let conversations = conversations_of(&server, "alice").await;
+    let conversations = conversations.as_array().unwrap();
+    assert_eq!(conversations.len(), 2);
+    assert!(
+        conversations
+            .iter()
+            .all(|conversation| conversation["participants"].as_array().unwrap().len() == 2)
+    );
+}
+
+#[tokio::test]
+async fn notes_for_followers_or_everyone_are_not_conversations() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    for (n, to) in [
+        json!([format!("{}/followers", bob.id)]),
+        json!(["https://www.w3.org/ns/activitystreams#Public"]),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        post_signed(
+            &server,
+            "/users/alice/inbox",
+            &bob,
+            &create(
+                &bob,
+                json!({
+                    "id": format!("{}/notes/{}", bob.id, n),
+                    "type": "Note",
+                    "attributedTo": bob.id,
+                    "content": "Hello @alice",
+                    "to": to,
+                    "cc": [ALICE]
+                }),
+            ),
+        )
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+    }
+
+    assert_eq!(conversations_of(&server, "alice").await, json!([]));
+}
+
+#[tokio::test]
+async fn notes_from_actors_without_known_followers_are_not_conversations() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let mut document = remote.actor_document("bob").await;
+    document.as_object_mut().unwrap().remove("followers");
+    remote.set_actor_document("bob", document).await;
+
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(
+            &bob,
+            json!({
+                "id": format!("{}/notes/1", bob.id),
+                "type": "Note",
+                "attributedTo": bob.id,
+                "content": "Hello @alice",
+                "to": [format!("{}/fans", bob.id)],
+                "cc": [ALICE]
+            }),
+        ),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    assert_eq!(conversations_of(&server, "alice").await, json!([]));
+}
+
+#[tokio::test]
+async fn direct_messages_stay_out_of_tag_collections() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(
+            &bob,
+            json!({
+                "id": format!("{}/notes/1", bob.id),
+                "type": "Note",
+                "attributedTo": bob.id,
+                "content": "Just between us #secret",
+                "to": [ALICE],
+                "tag": { "type": "Hashtag", "href": "https://remote.example/tags/secret", "name": "#secret" }
+            }),
+        ),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+    server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Me too #secret", "visibility": "direct", "to": [bob.id] }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let collection: Value = server.get("/tags/secret").await.json();
+    assert_eq!(collection["totalItems"], 0);
+    assert_eq!(
+        conversations_of(&server, "alice")
+            .await
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+}
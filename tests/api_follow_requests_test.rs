@@ -0,0 +1,203 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use calmi::app::jobs;
+use calmi::domain::repositories::FollowsRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{
+    RemoteActor, TEST_API_TOKEN, create_test_server, create_test_state, insert_user, post_signed,
+    setup_db, spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+fn follow_from(actor: &RemoteActor) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/follows/1", actor.id),
+        "type": "Follow",
+        "actor": actor.id,
+        "object": "https://example.com/users/alice"
+    })
+}
+
+async fn lock_account(server: &TestServer, username: &str) {
+    let response = server
+        .patch(&format!("/api/users/{}", username))
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "manually_approves_followers": true }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(
+        response.json::<Value>()["manually_approves_followers"],
+        true
+    );
+}
+
+#[tokio::test]
+async fn locked_account_is_advertised_on_the_person() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let person: Value = server.get("/users/alice").await.json();
+    assert_eq!(person["manuallyApprovesFollowers"], false);
+    assert_eq!(
+        person["@context"][2]["manuallyApprovesFollowers"],
+        "as:manuallyApprovesFollowers"
+    );
+
+    lock_account(&server, "alice").await;
+
+    let person: Value = server.get("/users/alice").await.json();
+    assert_eq!(person["manuallyApprovesFollowers"], true);
+}
+
+#[tokio::test]
+async fn follow_of_locked_account_waits_for_approval() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    lock_account(&server, "alice").await;
+
+    post_signed(&server, "/users/alice/inbox", &bob, &follow_from(&bob))
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+
+    assert!(storage.list_followers(user_id).await.unwrap().is_empty());
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 0);
+
+    let requests: Value = server
+        .get("/api/users/alice/follow_requests")
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .json();
+    let requests = requests.as_array().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["actor"], bob.id);
+    assert_eq!(requests[0]["activity_id"], format!("{}/follows/1", bob.id));
+    let request_id = requests[0]["id"].as_i64().unwrap();
+
+    server
+        .post(&format!(
+            "/api/users/alice/follow_requests/{}/accept",
+            request_id
+        ))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    let followers = storage.list_followers(user_id).await.unwrap();
+    assert_eq!(followers.len(), 1);
+    assert_eq!(followers[0].actor, bob.id);
+    assert!(
+        storage
+            .list_follow_requests(user_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    let accept = &received[0].body;
+    assert_eq!(accept["type"], "Accept");
+    assert_eq!(accept["actor"], "https://example.com/users/alice");
+    assert_eq!(accept["object"]["type"], "Follow");
+    assert_eq!(accept["object"]["id"], format!("{}/follows/1", bob.id));
+}
+
+#[tokio::test]
+async fn rejected_follow_request_is_dropped_and_reported() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    lock_account(&server, "alice").await;
+
+    post_signed(&server, "/users/alice/inbox", &bob, &follow_from(&bob))
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+    let request_id = storage.list_follow_requests(user_id).await.unwrap()[0].id;
+
+    server
+        .post(&format!(
+            "/api/users/alice/follow_requests/{}/reject",
+            request_id
+        ))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    assert!(
+        storage
+            .list_follow_requests(user_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(storage.list_followers(user_id).await.unwrap().is_empty());
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    let reject = &received[0].body;
+    assert_eq!(reject["type"], "Reject");
+    assert_eq!(reject["actor"], "https://example.com/users/alice");
+    assert_eq!(reject["object"]["id"], format!("{}/follows/1", bob.id));
+}
+
+#[tokio::test]
+async fn accepted_follow_cannot_be_accepted_again() {
+    let db = setup_db().await;
+    let user_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    post_signed(&server, "/users/alice/inbox", &bob, &follow_from(&bob))
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+    let follow_id = storage.list_followers(user_id).await.unwrap()[0].id;
+
+    server
+        .post(&format!(
+            "/api/users/alice/follow_requests/{}/reject",
+            follow_id
+        ))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    assert_eq!(storage.list_followers(user_id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn follow_requests_require_the_api_token() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    server
+        .get("/api/users/alice/follow_requests")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .get("/api/users/alice/follow_requests")
+        .authorization_bearer("wrong-token")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .patch("/api/users/alice")
+        .json(&json!({ "manually_approves_followers": true }))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
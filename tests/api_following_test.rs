@@ -0,0 +1,194 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use calmi::app::jobs;
+use helper::{
+    RemoteActor, TEST_API_TOKEN, create_test_server, create_test_state, insert_user, post_signed,
+    setup_db, spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+async fn follow(server: &TestServer, actor: &RemoteActor) -> Value {
+    let response = server
+        .post("/api/users/alice/following")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "actor": actor.id }))
+        .await;
+    response.assert_status(StatusCode::ACCEPTED);
+    response.json()
+}
+
+async fn list_following(server: &TestServer) -> Vec<Value> {
+    let following: Value = server
+        .get("/api/users/alice/following")
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .json();
+    following.as_array().unwrap().clone()
+}
+
+fn response_from(actor: &RemoteActor, kind: &str, follow_id: &str) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/responses/1", actor.id),
+        "type": kind,
+        "actor": actor.id,
+        "object": {
+            "id": follow_id,
+            "type": "Follow",
+            "actor": "https://example.com/users/alice",
+            "object": actor.id
+        }
+    })
+}
+
+#[tokio::test]
+async fn follow_is_sent_and_pending() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let record = follow(&server, &bob).await;
+    assert_eq!(record["actor"], bob.id);
+    assert_eq!(record["pending"], true);
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].path, "/users/bob/inbox");
+    let activity = &received[0].body;
+    assert_eq!(activity["type"], "Follow");
+    assert_eq!(activity["id"], record["activity_id"]);
+    assert_eq!(activity["actor"], "https://example.com/users/alice");
+    assert_eq!(activity["object"], bob.id);
+
+    // Following again does not send another Follow.
+    server
+        .post("/api/users/alice/following")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "actor": bob.id }))
+        .await
+        .assert_status_ok();
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn accept_matching_the_follow_id_completes_the_follow() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let record = follow(&server, &bob).await;
+    let follow_id = record["activity_id"].as_str().unwrap();
+
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &response_from(&bob, "Accept", follow_id),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let following = list_following(&server).await;
+    assert_eq!(following.len(), 1);
+    assert_eq!(following[0]["pending"], false);
+}
+
+#[tokio::test]
+async fn accept_by_another_actor_is_refused() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let carol = remote.add_actor("carol").await;
+
+    let record = follow(&server, &bob).await;
+    let follow_id = record["activity_id"].as_str().unwrap();
+
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &carol,
+        &response_from(&carol, "Accept", follow_id),
+    )
+    .await
+    .assert_status(StatusCode::FORBIDDEN);
+
+    assert_eq!(list_following(&server).await[0]["pending"], true);
+}
+
+#[tokio::test]
+async fn reject_drops_the_follow() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let record = follow(&server, &bob).await;
+    let follow_id = record["activity_id"].as_str().unwrap();
+
+    let reject = json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/responses/1", bob.id),
+        "type": "Reject",
+        "actor": bob.id,
+        "object": follow_id
+    });
+    post_signed(&server, "/users/alice/inbox", &bob, &reject)
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+
+    assert!(list_following(&server).await.is_empty());
+}
+
+#[tokio::test]
+async fn unfollow_sends_undo() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let record = follow(&server, &bob).await;
+    let follow_id = record["activity_id"].as_str().unwrap();
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &response_from(&bob, "Accept", follow_id),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    server
+        .delete(&format!(
+            "/api/users/alice/following/{}",
+            record["id"].as_i64().unwrap()
+        ))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    assert!(list_following(&server).await.is_empty());
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 2);
+    let received = remote.received().await;
+    let undo = received
+        .iter()
+        .map(|request| &request.body)
+        .find(|body| body["type"] == "Undo")
+        .expect("Undo should be delivered");
+    assert_eq!(undo["actor"], "https://example.com/users/alice");
+    assert_eq!(undo["object"]["type"], "Follow");
+    assert_eq!(undo["object"]["id"], follow_id);
+    assert_eq!(undo["object"]["object"], bob.id);
+}
@@ -0,0 +1,119 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use calmi::app::jobs;
+use helper::{
+    RemoteServer, TEST_API_TOKEN, create_test_server, create_test_state, insert_user, setup_db,
+    spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+async fn remote_note(remote: &RemoteServer, author: &str) -> String {
+    let id = format!("{}/objects/note-1", remote.base_url);
+    remote
+        .set_object(
+            "note-1",
+            json!({
+                "@context": "https://www.w3.org/ns/activitystreams",
+                "id": id,
+                "type": "Note",
+                "attributedTo": author,
+                "content": "Hello"
+            }),
+        )
+        .await
+}
+
+async fn like(server: &TestServer, object: &str) -> Value {
+    let response = server
+        .post("/api/users/alice/liked")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "object": object }))
+        .await;
+    response.assert_status(StatusCode::ACCEPTED);
+    response.json()
+}
+
+#[tokio::test]
+async fn like_is_sent_to_the_author_and_listed_in_liked() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note = remote_note(&remote, &bob.id).await;
+
+    let record = like(&server, &note).await;
+    assert_eq!(record["object"], note);
+    assert_eq!(record["actor"], bob.id);
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].path, "/users/bob/inbox");
+    let activity = &received[0].body;
+    assert_eq!(activity["type"], "Like");
+    assert_eq!(activity["id"], record["activity_id"]);
+    assert_eq!(activity["actor"], "https://example.com/users/alice");
+    assert_eq!(activity["object"], note);
+
+    // Liking again does not send another Like.
+    server
+        .post("/api/users/alice/liked")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "object": note }))
+        .await
+        .assert_status_ok();
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 0);
+
+    let collection: Value = server.get("/users/alice/liked").await.json();
+    assert_eq!(collection["totalItems"], 1);
+    let page: Value = server.get("/users/alice/liked?page=1").await.json();
+    assert_eq!(page["orderedItems"], json!([note]));
+}
+
+#[tokio::test]
+async fn unlike_sends_undo_and_empties_liked() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note = remote_note(&remote, &bob.id).await;
+    let record = like(&server, &note).await;
+    jobs::run_due_jobs(&state).await.unwrap();
+
+    server
+        .delete(&format!("/api/users/alice/liked/{}", record["id"]))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    let undo = &received.last().unwrap().body;
+    assert_eq!(undo["type"], "Undo");
+    assert_eq!(undo["object"]["type"], "Like");
+    assert_eq!(undo["object"]["id"], record["activity_id"]);
+
+    let collection: Value = server.get("/users/alice/liked").await.json();
+    assert_eq!(collection["totalItems"], 0);
+}
+
+#[tokio::test]
+async fn liking_an_unreachable_object_fails() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+
+    server
+        .post("/api/users/alice/liked")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "object": format!("{}/objects/missing", remote.base_url) }))
+        .await
+        .assert_status(StatusCode::BAD_GATEWAY);
+}
@@ -0,0 +1,477 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use axum_test::multipart::{MultipartForm, Part};
+use calmi::app::jobs;
+use calmi::domain::entities::notes;
+use calmi::domain::repositories::notes::NoteRelations;
+use calmi::domain::repositories::{
+    MediaRepository, NoteTagsRepository, NotesRepository, RemoteNotesRepository,
+};
+use calmi::storage::postgres::PostgresStorage;
+use helper::{
+    TEST_API_TOKEN, create_test_server, create_test_state, insert_user, post_signed, setup_db,
+    spawn_remote_server,
+};
+use sea_orm::ActiveValue;
+use serde_json::{Value, json};
+use std::io::Cursor;
+
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    image::RgbImage::new(width, height)
+        .write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .unwrap();
+    bytes
+}
+
+async fn upload(server: &TestServer, username: &str, form: MultipartForm) -> (StatusCode, Value) {
+    let response = server
+        .post(&format!("/api/users/{}/media", username))
+        .authorization_bearer(TEST_API_TOKEN)
+        .multipart(form)
+        .await;
+    let status = response.status_code();
+    let body = if status.is_success() {
+        response.json()
+    } else {
+        Value::Null
+    };
+    (status, body)
+}
+
+/// A JPEG `width` by `height` pixels with EXIF saying to turn it a quarter clockwise,
+/// and where it was taken.
+fn jpeg_with_exif(width: u32, height: u32) -> Vec<u8> {
+    let mut jpeg = Vec::new();
+    image::RgbImage::new(width, height)
+        .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+        .unwrap();
+
+    let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08".to_vec();
+    // One IFD entry: Orientation (0x0112), SHORT, 1 value: 6.
+    exif.extend_from_slice(&[0, 1, 0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 6, 0, 0]);
+    exif.extend_from_slice(&[0, 0, 0, 0]);
+    exif.extend_from_slice(b"GPS 35.6812N 139.7671E");
+    let mut app1 = vec![0xFF, 0xE1];
+    app1.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+    app1.extend_from_slice(&exif);
+
+    // Right after the start of image marker.
+    jpeg.splice(2..2, app1);
+    jpeg
+}
+
+/// A GIF of `frames` frames with XMP saying where it was taken, and a comment.
+fn gif_with_xmp(frames: usize) -> Vec<u8> {
+    let mut gif = Vec::new();
+    {
+        let mut encoder = image::codecs::gif::GifEncoder::new(&mut gif);
+        encoder
+            .set_repeat(image::codecs::gif::Repeat::Infinite)
+            .unwrap();
+        for _ in 0..frames {
+            encoder
+                .encode_frame(image::Frame::new(image::RgbaImage::new(2, 2)))
+                .unwrap();
+        }
+    }
+
+    let mut metadata = vec![0x21, 0xFF, 11];
+    metadata.extend_from_slice(b"XMP DataXMP");
+    let xmp = b"<x:xmpmeta><exif:GPSLatitude>35,40.87N</exif:GPSLatitude></x:xmpmeta>";
+    metadata.push(xmp.len() as u8);
+    metadata.extend_from_slice(xmp);
+    metadata.push(0);
+    metadata.extend_from_slice(&[0x21, 0xFE, 9]);
+    metadata.extend_from_slice(b"By Alice.");
+    metadata.push(0);
+
+    // Right before the trailer.
+    let trailer = gif.len() - 1;
+    gif.splice(trailer..trailer, metadata);
+    gif
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+fn file(bytes: Vec<u8>, media_type: &str) -> MultipartForm {
+    MultipartForm::new().add_part("file", Part::bytes(bytes).mime_type(media_type))
+}
+
+#[tokio::test]
+async fn uploaded_image_is_measured_served_and_attached() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+
+    let bytes = png(3, 2);
+    let (status, media) = upload(
+        &server,
+        "alice",
+        file(bytes.clone(), "image/png").add_text("description", "A tiny picture"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(media["media_type"], "image/png");
+    assert_eq!(media["size"], bytes.len());
+    assert_eq!(media["width"], 3);
+    assert_eq!(media["height"], 2);
+    assert_eq!(media["description"], "A tiny picture");
+    let url = media["url"].as_str().unwrap();
+    let path = url.strip_prefix("https://example.com").unwrap();
+    assert!(path.starts_with("/media/") && path.ends_with(".png"));
+
+    // Images are served, and can be attached, once processed.
+    server.get(path).await.assert_status(StatusCode::NOT_FOUND);
+    server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Too soon", "media_ids": [media["id"]] }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let served = server.get(path).await;
+    served.assert_status_ok();
+    assert_eq!(served.header("content-type"), "image/png");
+    let image = image::load_from_memory(served.as_bytes()).unwrap();
+    assert_eq!((image.width(), image.height()), (3, 2));
+    let thumbnail = server.get(&path.replace(".png", "-small.jpg")).await;
+    thumbnail.assert_status_ok();
+    assert_eq!(thumbnail.header("content-type"), "image/jpeg");
+
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Look", "media_ids": [media["id"]] }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let note: Value = response.json();
+    let served: Value = server
+        .get(&format!("/users/alice/notes/{}", note["id"]))
+        .await
+        .json();
+    let blurhash = served["attachment"][0]["blurhash"].as_str().unwrap();
+    assert!(!blurhash.is_empty());
+    assert_eq!(
+        served["attachment"],
+        json!([{
+            "type": "Image",
+            "mediaType": "image/png",
+            "url": url,
+            "name": "A tiny picture",
+            "width": 3,
+            "height": 2,
+            "blurhash": blurhash
+        }])
+    );
+    assert_eq!(
+        served["@context"],
+        json!([
+            "https://www.w3.org/ns/activitystreams",
+            { "toot": "http://joinmastodon.org/ns#", "blurhash": "toot:blurhash" }
+        ])
+    );
+
+    // An upload goes with one note only.
+    server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Again", "media_ids": [media["id"]] }))
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn processing_strips_exif_and_turns_the_image_upright() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+
+    let bytes = jpeg_with_exif(4, 2);
+    assert!(contains(&bytes, b"GPS"));
+    let (status, media) = upload(
+        &server,
+        "alice",
+        file(bytes, "image/jpeg").add_text("focus", "0.5,-0.25"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(media["focal_point"], json!([0.5, -0.25]));
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+
+    let path = media["url"]
+        .as_str()
+        .unwrap()
+        .strip_prefix("https://example.com")
+        .unwrap()
+        .to_string();
+    let served = server.get(&path).await;
+    served.assert_status_ok();
+    let served = served.as_bytes();
+    assert!(!contains(served, b"Exif"));
+    assert!(!contains(served, b"GPS"));
+    let image = image::load_from_memory(served).unwrap();
+    assert_eq!((image.width(), image.height()), (2, 4));
+
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Upright", "media_ids": [media["id"]] }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let note: Value = response.json();
+    let served: Value = server
+        .get(&format!("/users/alice/notes/{}", note["id"]))
+        .await
+        .json();
+    let attachment = &served["attachment"][0];
+    assert_eq!(attachment["width"], 2);
+    assert_eq!(attachment["height"], 4);
+    assert_eq!(attachment["focalPoint"], json!([0.5, -0.25]));
+    assert_eq!(
+        served["@context"][1]["focalPoint"],
+        json!({ "@container": "@list", "@id": "toot:focalPoint" })
+    );
+}
+
+#[tokio::test]
+async fn processing_strips_gif_metadata_and_keeps_the_animation() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+
+    let bytes = gif_with_xmp(2);
+    assert!(image::load_from_memory(&bytes).is_ok());
+    let (status, media) = upload(&server, "alice", file(bytes, "image/gif")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+
+    let path = media["url"]
+        .as_str()
+        .unwrap()
+        .strip_prefix("https://example.com")
+        .unwrap()
+        .to_string();
+    let served = server.get(&path).await;
+    served.assert_status_ok();
+    let served = served.as_bytes();
+    assert!(!contains(served, b"XMP"));
+    assert!(!contains(served, b"GPSLatitude"));
+    assert!(!contains(served, b"By Alice."));
+    assert!(contains(served, b"NETSCAPE2.0"));
+    let decoder = image::codecs::gif::GifDecoder::new(Cursor::new(served.to_vec())).unwrap();
+    let frames = image::AnimationDecoder::into_frames(decoder)
+        .collect_frames()
+        .unwrap();
+    assert_eq!(frames.len(), 2);
+}
+
+#[tokio::test]
+async fn uploads_that_cannot_be_used_are_refused() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    insert_user(&db, "bob", "Bob").await;
+    let server = create_test_server(db);
+
+    let (status, _) = upload(&server, "alice", file(b"hello".to_vec(), "text/plain")).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    let (status, _) = upload(&server, "alice", file(b"not a png".to_vec(), "image/png")).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let (status, _) = upload(
+        &server,
+        "alice",
+        MultipartForm::new().add_text("description", "Nothing"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let (status, _) = upload(
+        &server,
+        "alice",
+        file(png(1, 1), "image/png").add_text("focus", "2,0"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    server
+        .get("/media/missing.png")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    // Nobody may attach the uploads of someone else.
+    let (status, media) = upload(&server, "bob", file(png(1, 1), "image/png")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Mine now", "media_ids": [media["id"]] }))
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let outbox: Value = server.get("/users/alice/outbox").await.json();
+    assert_eq!(outbox["totalItems"], 0);
+}
+
+#[tokio::test]
+async fn a_note_is_not_kept_when_its_media_cannot_be_attached() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    insert_user(&db, "bob", "Bob").await;
+    let server = create_test_server(db.clone());
+    let (_, media) = upload(&server, "bob", file(png(1, 1), "image/png")).await;
+    let storage = PostgresStorage::new(db);
+
+    // As when the upload is attached elsewhere between checking it and adding the note.
+    let note = notes::ActiveModel {
+        id: ActiveValue::NotSet,
+        content: ActiveValue::Set("Mine now #art".to_string()),
+        author_id: ActiveValue::Set(alice_id),
+        created_at: ActiveValue::Set(chrono::Utc::now().naive_utc()),
+        to: ActiveValue::Set(vec![PUBLIC.to_string()]),
+        deleted_at: ActiveValue::Set(None),
+        updated_at: ActiveValue::Set(None),
+        in_reply_to: ActiveValue::Set(None),
+        context: ActiveValue::Set(None),
+        visibility: ActiveValue::Set("public".to_string()),
+        summary: ActiveValue::Set(None),
+        sensitive: ActiveValue::Set(false),
+    };
+    let relations = NoteRelations {
+        author: "https://example.com/users/alice".to_string(),
+        mentions: Vec::new(),
+        mentioned_users: Vec::new(),
+        tags: vec!["art".to_string()],
+        media_ids: vec![media["id"].as_i64().unwrap()],
+        direct_messages: Vec::new(),
+    };
+    let added = storage
+        .add_note_with_relations(note, relations, &|id| {
+            format!("https://example.com/users/alice/notes/{}", id)
+        })
+        .await;
+
+    assert!(added.is_err());
+    assert_eq!(storage.count_notes_by_author(alice_id).await.unwrap(), 0);
+    assert_eq!(storage.count_tagged_notes("art").await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn attachments_of_received_notes_are_recorded() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let note_id = format!("{}/notes/1", bob.id);
+    let note = |attachment: Value| {
+        json!({
+            "id": note_id,
+            "type": "Note",
+            "attributedTo": bob.id,
+            "to": [PUBLIC],
+            "content": "Holiday pictures",
+            "attachment": attachment
+        })
+    };
+    let activity = |kind: &str, object: Value| {
+        json!({
+            "@context": "https://www.w3.org/ns/activitystreams",
+            "id": format!("{}/{}", note_id, kind),
+            "type": kind,
+            "actor": bob.id,
+            "object": object
+        })
+    };
+
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity(
+            "Create",
+            note(json!([
+                {
+                    "type": "Document",
+                    "mediaType": "image/jpeg",
+                    "url": "https://remote.example/files/beach.jpg",
+                    "name": "A beach",
+                    "width": 1200,
+                    "height": 800,
+                    "blurhash": "UBL_:rOpGG-oBUNG,qRj2so|=eE1w^n4S5NH",
+                    "focalPoint": [0.0, 0.5]
+                },
+                {
+                    "type": "Video",
+                    "url": { "type": "Link", "href": "https://remote.example/files/waves.mp4", "mediaType": "video/mp4" }
+                }
+            ])),
+        ),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let stored = storage
+        .find_remote_note_by_ap_id(&note_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let media = storage.list_remote_note_media(stored.id).await.unwrap();
+    let recorded: Vec<_> = media
+        .iter()
+        .map(|media| {
+            (
+                media.remote_url.as_deref(),
+                media.media_type.as_str(),
+                media.description.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        recorded,
+        [
+            (
+                Some("https://remote.example/files/beach.jpg"),
+                "image/jpeg",
+                Some("A beach")
+            ),
+            (
+                Some("https://remote.example/files/waves.mp4"),
+                "video/mp4",
+                None
+            ),
+        ]
+    );
+    assert_eq!((media[0].width, media[0].height), (Some(1200), Some(800)));
+    assert_eq!(
+        media[0].blurhash.as_deref(),
+        Some("UBL_:rOpGG-oBUNG,qRj2so|=eE1w^n4S5NH")
+    );
+    assert_eq!((media[0].focal_x, media[0].focal_y), (Some(0.0), Some(0.5)));
+
+    // Edits replace the attachments.
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity("Update", note(json!([]))),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+    assert!(
+        storage
+            .list_remote_note_media(stored.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
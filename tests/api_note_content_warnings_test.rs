@@ -0,0 +1,208 @@
+mod helper;
+
+use axum::http::StatusCode;
+use calmi::domain::repositories::RemoteNotesRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{
+    TEST_API_TOKEN, create_test_server, insert_user, post_signed, setup_db, spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+#[tokio::test]
+async fn content_warning_is_emitted_as_summary_and_sensitive() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(
+            &json!({ "summary": "Film spoilers", "content": "The butler did it", "to": [PUBLIC] }),
+        )
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let note: Value = response.json();
+    assert_eq!(note["summary"], "Film spoilers");
+    // A content warning always hides the note.
+    assert_eq!(note["sensitive"], true);
+
+    let path = format!("/users/alice/notes/{}", note["id"]);
+    let served: Value = server.get(&path).await.json();
+    assert_eq!(served["summary"], "Film spoilers");
+    assert_eq!(served["sensitive"], true);
+    assert_eq!(
+        served["@context"],
+        json!([
+            "https://www.w3.org/ns/activitystreams",
+            { "sensitive": "as:sensitive" }
+        ])
+    );
+
+    let page = server
+        .get(&path)
+        .add_header("accept", "text/html")
+        .await
+        .text();
+    assert!(page.contains("<details><summary>Film spoilers</summary>The butler did it</details>"));
+    assert!(page.contains("<title>Alice: Film spoilers"));
+}
+
+#[tokio::test]
+async fn notes_can_be_sensitive_without_a_content_warning() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    for (body, sensitive) in [
+        (json!({ "content": "Hello", "sensitive": true }), Some(true)),
+        (json!({ "content": "Hello", "summary": "  " }), None),
+        (json!({ "content": "Hello" }), None),
+    ] {
+        let response = server
+            .post("/api/users/alice/notes")
+            .authorization_bearer(TEST_API_TOKEN)
+            .json(&body)
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let note: Value = response.json();
+        assert_eq!(note["summary"], Value::Null);
+        let served: Value = server
+            .get(&format!("/users/alice/notes/{}", note["id"]))
+            .await
+            .json();
+        assert!(served.get("summary").is_none());
+        assert_eq!(served["sensitive"].as_bool(), sensitive);
+    }
+}
+
+#[tokio::test]
+async fn content_warnings_of_received_notes_are_kept() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let note_id = format!("{}/notes/1", bob.id);
+    let activity = |kind: &str, object: Value| {
+        json!({
+            "@context": [
+                "https://www.w3.org/ns/activitystreams",
+                { "sensitive": "as:sensitive" }
+            ],
+            "id": format!("{}/{}", note_id, kind),
+            "type": kind,
+            "actor": bob.id,
+            "object": object
+        })
+    };
+
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity(
+            "Create",
+            json!({
+                "id": note_id,
+                "type": "Note",
+                "attributedTo": bob.id,
+                "to": [PUBLIC],
+                "summary": "Food",
+                "content": "Pictures of my lunch",
+                "sensitive": true
+            }),
+        ),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+    let stored = storage
+        .find_remote_note_by_ap_id(&note_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.summary.as_deref(), Some("Food"));
+    assert!(stored.sensitive);
+
+    // Edits may lift the warning.
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity(
+            "Update",
+            json!({
+                "id": note_id,
+                "type": "Note",
+                "attributedTo": bob.id,
+                "to": [PUBLIC],
+                "content": "Pictures of my lunch"
+            }),
+        ),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+    let stored = storage
+        .find_remote_note_by_ap_id(&note_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.summary, None);
+    assert!(!stored.sensitive);
+}
+
+#[tokio::test]
+async fn content_warnings_can_be_edited_and_are_kept_in_revisions() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "summary": "Spoilers", "content": "The butler did it" }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let note: Value = response.json();
+    let path = format!("/api/users/alice/notes/{}", note["id"]);
+
+    let response = server
+        .patch(&path)
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "summary": "Film spoilers", "content": "The butler did it" }))
+        .await;
+    response.assert_status_ok();
+    let edited: Value = response.json();
+    assert_eq!(edited["summary"], "Film spoilers");
+    assert_eq!(edited["sensitive"], true);
+
+    let response = server
+        .patch(&path)
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "The butler did it" }))
+        .await;
+    response.assert_status_ok();
+    let edited: Value = response.json();
+    assert_eq!(edited["summary"], Value::Null);
+    assert_eq!(edited["sensitive"], false);
+
+    let revisions: Value = server
+        .get(&format!("{}/revisions", path))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .json();
+    let summaries: Vec<&Value> = revisions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|revision| &revision["summary"])
+        .collect();
+    assert_eq!(
+        summaries,
+        vec![&json!("Spoilers"), &json!("Film spoilers"), &Value::Null]
+    );
+}
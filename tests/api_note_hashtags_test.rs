@@ -0,0 +1,164 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use helper::{
+    TEST_API_TOKEN, create_test_server, insert_user, post_signed, setup_db, spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+async fn post_note(server: &TestServer, content: &str) -> Value {
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": content, "to": [PUBLIC] }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    response.json()
+}
+
+fn activity(kind: &str, actor: &str, object: Value) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/activities/{}", object["id"].as_str().unwrap(), kind),
+        "type": kind,
+        "actor": actor,
+        "object": object
+    })
+}
+
+async fn tagged(server: &TestServer, name: &str) -> Value {
+    let page: Value = server.get(&format!("/tags/{}?page=1", name)).await.json();
+    page["orderedItems"].clone()
+}
+
+#[tokio::test]
+async fn hashtags_are_linked_and_emitted_as_tags() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let note = post_note(&server, "Learning #Rust, #rust and #async_io (#1)").await;
+    assert_eq!(
+        note["content"],
+        concat!(
+            "Learning ",
+            r#"<a href="https://example.com/tags/rust" class="mention hashtag" rel="tag">#Rust</a>, "#,
+            r#"<a href="https://example.com/tags/rust" class="mention hashtag" rel="tag">#rust</a> and "#,
+            r#"<a href="https://example.com/tags/async_io" class="mention hashtag" rel="tag">#async_io</a> (#1)"#
+        )
+    );
+
+    let served: Value = server
+        .get(&format!("/users/alice/notes/{}", note["id"]))
+        .await
+        .json();
+    assert_eq!(
+        served["tag"],
+        json!([
+            { "type": "Hashtag", "href": "https://example.com/tags/rust", "name": "#rust" },
+            { "type": "Hashtag", "href": "https://example.com/tags/async_io", "name": "#async_io" }
+        ])
+    );
+    assert_eq!(
+        served["@context"],
+        json!([
+            "https://www.w3.org/ns/activitystreams",
+            { "Hashtag": "as:Hashtag" }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn tag_collection_lists_local_and_remote_notes_newest_first() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let note = post_note(&server, "Hello #Fediverse").await;
+    let local = format!("https://example.com/users/alice/notes/{}", note["id"]);
+    let remote_note = format!("{}/notes/1", bob.id);
+    let object = json!({
+        "id": remote_note,
+        "type": "Note",
+        "attributedTo": bob.id,
+        "to": [PUBLIC],
+        "content": "Me too #fediverse",
+        "tag": [{ "type": "Hashtag", "href": "https://remote.example/tags/fediverse", "name": "#FediVerse" }]
+    });
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity("Create", &bob.id, object),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let collection: Value = server.get("/tags/Fediverse").await.json();
+    assert_eq!(collection["type"], "OrderedCollection");
+    assert_eq!(collection["id"], "https://example.com/tags/fediverse");
+    assert_eq!(collection["totalItems"], 2);
+    assert_eq!(
+        tagged(&server, "fediverse").await,
+        json!([remote_note, local])
+    );
+    assert_eq!(tagged(&server, "unused").await, json!([]));
+
+    // Edits move the remote note to its new hashtags.
+    let edited = json!({
+        "id": remote_note,
+        "type": "Note",
+        "attributedTo": bob.id,
+        "to": [PUBLIC],
+        "content": "Me too #ActivityPub",
+        "tag": { "type": "Hashtag", "href": "https://remote.example/tags/activitypub", "name": "#ActivityPub" }
+    });
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &activity("Update", &bob.id, edited),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+    assert_eq!(tagged(&server, "fediverse").await, json!([local]));
+    assert_eq!(tagged(&server, "activitypub").await, json!([remote_note]));
+}
+
+#[tokio::test]
+async fn edited_and_deleted_notes_leave_their_tags() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let note = post_note(&server, "#draft").await;
+    let uri = format!("https://example.com/users/alice/notes/{}", note["id"]);
+    assert_eq!(tagged(&server, "draft").await, json!([uri]));
+
+    let response = server
+        .patch(&format!("/api/users/alice/notes/{}", note["id"]))
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "#final" }))
+        .await;
+    response.assert_status_ok();
+    let edited: Value = response.json();
+    assert_eq!(
+        edited["content"],
+        r#"<a href="https://example.com/tags/final" class="mention hashtag" rel="tag">#final</a>"#
+    );
+    assert_eq!(tagged(&server, "draft").await, json!([]));
+    assert_eq!(tagged(&server, "final").await, json!([uri]));
+
+    server
+        .delete(&format!("/api/users/alice/notes/{}", note["id"]))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    let collection: Value = server.get("/tags/final").await.json();
+    assert_eq!(collection["totalItems"], 0);
+}
@@ -0,0 +1,234 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use calmi::app::jobs;
+use helper::{
+    RemoteServer, TEST_API_TOKEN, create_test_server, create_test_state, insert_user, post_signed,
+    setup_db, spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+fn domain_of(remote: &RemoteServer) -> &str {
+    remote.base_url.strip_prefix("http://").unwrap()
+}
+
+async fn post_note(server: &TestServer, content: &str) -> Value {
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": content, "to": [PUBLIC] }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    response.json()
+}
+
+async fn mentions_of(server: &TestServer, username: &str) -> Value {
+    let response = server
+        .get(&format!("/api/users/{}/mentions", username))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await;
+    response.assert_status_ok();
+    response.json()
+}
+
+#[tokio::test]
+async fn mention_is_resolved_linked_tagged_and_delivered() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let name = format!("@bob@{}", domain_of(&remote));
+
+    let note = post_note(&server, &format!("<p>Hi {}!</p>", name)).await;
+    assert_eq!(
+        note["content"],
+        format!(
+            r#"<p>Hi <a href="{}" class="u-url mention">{}</a>!</p>"#,
+            bob.id, name
+        )
+    );
+    assert_eq!(note["to"], json!([PUBLIC, bob.id]));
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].path, "/users/bob/inbox");
+    let mention = json!({ "type": "Mention", "href": bob.id, "name": name });
+    assert_eq!(received[0].body["object"]["tag"], json!([mention]));
+
+    let served: Value = server
+        .get(&format!("/users/alice/notes/{}", note["id"]))
+        .await
+        .json();
+    assert_eq!(served["tag"], json!([mention]));
+    let outbox: Value = server.get("/users/alice/outbox?page=true").await.json();
+    assert_eq!(outbox["orderedItems"][0]["object"]["tag"], json!([mention]));
+}
+
+#[tokio::test]
+async fn unknown_account_stays_plain_text() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let content = format!(
+        "Is @nobody@{} around? mail me at alice@example.com",
+        domain_of(&remote)
+    );
+
+    let note = post_note(&server, &content).await;
+    assert_eq!(note["content"], content);
+    assert_eq!(note["to"], json!([PUBLIC]));
+
+    let served: Value = server
+        .get(&format!("/users/alice/notes/{}", note["id"]))
+        .await
+        .json();
+    assert!(served.get("tag").is_none());
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn mentions_of_local_users_are_recorded_without_delivery() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    insert_user(&db, "carol", "Carol").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+
+    let note = post_note(&server, "Welcome @carol@example.com").await;
+    assert_eq!(
+        note["to"],
+        json!([PUBLIC, "https://example.com/users/carol"])
+    );
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 0);
+
+    let mentions = mentions_of(&server, "carol").await;
+    assert_eq!(mentions.as_array().unwrap().len(), 1);
+    assert_eq!(
+        mentions[0]["note"],
+        format!("https://example.com/users/alice/notes/{}", note["id"])
+    );
+    assert_eq!(mentions[0]["actor"], "https://example.com/users/alice");
+    assert_eq!(mentions_of(&server, "alice").await, json!([]));
+}
+
+#[tokio::test]
+async fn editing_a_note_keeps_and_resolves_its_mentions() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    insert_user(&db, "carol", "Carol").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let bob_name = format!("@bob@{}", domain_of(&remote));
+
+    let original = format!("Hi {}", bob_name);
+    let note = post_note(&server, &original).await;
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let path = format!("/api/users/alice/notes/{}", note["id"]);
+
+    // Sending the same text again changes nothing.
+    let response = server
+        .patch(&path)
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": original }))
+        .await;
+    response.assert_status_ok();
+    let unchanged: Value = response.json();
+    assert_eq!(unchanged["content"], note["content"]);
+    assert!(unchanged["updated_at"].is_null());
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 0);
+
+    let response = server
+        .patch(&path)
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": format!("Hi {} and @carol@example.com", bob_name) }))
+        .await;
+    response.assert_status_ok();
+    let edited: Value = response.json();
+    assert_eq!(
+        edited["content"],
+        format!(
+            r#"Hi <a href="{}" class="u-url mention">{}</a> and <a href="https://example.com/users/carol" class="u-url mention">@carol@example.com</a>"#,
+            bob.id, bob_name
+        )
+    );
+    assert_eq!(
+        edited["to"],
+        json!([PUBLIC, bob.id, "https://example.com/users/carol"])
+    );
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 2);
+    assert_eq!(received[1].body["type"], "Update");
+    assert_eq!(
+        received[1].body["object"]["tag"],
+        json!([
+            { "type": "Mention", "href": bob.id, "name": bob_name },
+            { "type": "Mention", "href": "https://example.com/users/carol", "name": "@carol@example.com" }
+        ])
+    );
+    assert_eq!(
+        mentions_of(&server, "carol")
+            .await
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn incoming_mention_of_a_local_user_is_recorded_once() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let note_id = format!("{}/notes/1", bob.id);
+    let create = json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/activity", note_id),
+        "type": "Create",
+        "actor": bob.id,
+        "object": {
+            "id": note_id,
+            "type": "Note",
+            "attributedTo": bob.id,
+            "content": "<p>@alice hello</p>",
+            "to": [PUBLIC, "https://example.com/users/alice"],
+            "tag": [
+                {
+                    "type": "Mention",
+                    "href": "https://example.com/users/alice",
+                    "name": "@alice@example.com"
+                },
+                {
+                    "type": "Mention",
+                    "href": "https://example.com/users/nobody",
+                    "name": "@nobody@example.com"
+                }
+            ]
+        }
+    });
+
+    for _ in 0..2 {
+        post_signed(&server, "/users/alice/inbox", &bob, &create)
+            .await
+            .assert_status(StatusCode::ACCEPTED);
+    }
+
+    let mentions = mentions_of(&server, "alice").await;
+    assert_eq!(mentions.as_array().unwrap().len(), 1);
+    assert_eq!(mentions[0]["note"], note_id);
+    assert_eq!(mentions[0]["actor"], bob.id);
+}
@@ -0,0 +1,353 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use calmi::app::jobs;
+use calmi::domain::repositories::FollowingRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{
+    TEST_API_TOKEN, create_test_server, create_test_state, insert_note, insert_user, post_signed,
+    setup_db, spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+async fn post_note(server: &TestServer, username: &str, body: Value) -> Value {
+    let response = server
+        .post(&format!("/api/users/{}/notes", username))
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&body)
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    response.json()
+}
+
+fn create(actor: &str, object: Value) -> Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("{}/activities/create-1", object["id"].as_str().unwrap()),
+        "type": "Create",
+        "actor": actor,
+        "object": object
+    })
+}
+
+#[tokio::test]
+async fn reply_to_a_remote_note_joins_its_conversation_and_reaches_its_author() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let parent = remote
+        .set_object(
+            "note-1",
+            json!({
+                "@context": "https://www.w3.org/ns/activitystreams",
+                "id": format!("{}/objects/note-1", remote.base_url),
+                "type": "Note",
+                "attributedTo": bob.id,
+                "context": format!("{}/contexts/1", remote.base_url),
+                "content": "Anyone there?"
+            }),
+        )
+        .await;
+
+    let reply = post_note(
+        &server,
+        "alice",
+        json!({ "content": "Hello Bob", "to": [PUBLIC], "in_reply_to": parent }),
+    )
+    .await;
+    assert_eq!(reply["in_reply_to"], parent);
+    assert_eq!(reply["to"], json!([PUBLIC, bob.id]));
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].path, "/users/bob/inbox");
+    let activity = &received[0].body;
+    assert_eq!(activity["type"], "Create");
+    assert_eq!(activity["object"]["inReplyTo"], parent);
+    assert_eq!(
+        activity["object"]["context"],
+        format!("{}/contexts/1", remote.base_url)
+    );
+
+    let note: Value = server
+        .get(&format!("/users/alice/notes/{}", reply["id"]))
+        .await
+        .json();
+    assert_eq!(note["inReplyTo"], parent);
+    assert_eq!(
+        note["replies"],
+        format!(
+            "https://example.com/users/alice/notes/{}/replies",
+            reply["id"]
+        )
+    );
+}
+
+#[tokio::test]
+async fn replies_collection_lists_local_and_remote_replies_oldest_first() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    insert_user(&db, "carol", "Carol").await;
+    let note_id = insert_note(&db, "Question", alice_id, vec![PUBLIC.to_string()]).await;
+    let note_uri = format!("https://example.com/users/alice/notes/{}", note_id);
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let carol_reply = post_note(
+        &server,
+        "carol",
+        json!({ "content": "Answer", "to": [PUBLIC], "in_reply_to": note_uri }),
+    )
+    .await;
+    let bob_reply = format!("{}/notes/1", bob.id);
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(
+            &bob.id,
+            json!({
+                "id": bob_reply,
+                "type": "Note",
+                "attributedTo": bob.id,
+                "content": "Another answer",
+                "to": [PUBLIC],
+                "inReplyTo": note_uri,
+                "published": "2999-01-01T00:00:00Z"
+            }),
+        ),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+
+    let collection: Value = server
+        .get(&format!("/users/alice/notes/{}/replies", note_id))
+        .await
+        .json();
+    assert_eq!(collection["type"], "OrderedCollection");
+    assert_eq!(collection["totalItems"], 2);
+
+    let page: Value = server
+        .get(&format!("/users/alice/notes/{}/replies?page=1", note_id))
+        .await
+        .json();
+    assert_eq!(
+        page["orderedItems"],
+        json!([
+            format!(
+                "https://example.com/users/carol/notes/{}",
+                carol_reply["id"]
+            ),
+            bob_reply
+        ])
+    );
+
+    // The reply names the conversation after the note it answers.
+    let reply: Value = server
+        .get(&format!("/users/carol/notes/{}", carol_reply["id"]))
+        .await
+        .json();
+    assert_eq!(reply["context"], note_uri);
+}
+
+#[tokio::test]
+async fn thread_gathers_ancestors_and_descendants_across_servers() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let root_id = insert_note(&db, "Root", alice_id, vec![PUBLIC.to_string()]).await;
+    let root_uri = format!("https://example.com/users/alice/notes/{}", root_id);
+    let server = create_test_server(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+
+    let bob_reply = format!("{}/notes/1", bob.id);
+    post_signed(
+        &server,
+        "/users/alice/inbox",
+        &bob,
+        &create(
+            &bob.id,
+            json!({
+                "id": bob_reply,
+                "type": "Note",
+                "attributedTo": bob.id,
+                "content": "Remote reply",
+                "to": [PUBLIC],
+                "inReplyTo": root_uri,
+                "published": "2025-01-01T00:00:00Z"
+            }),
+        ),
+    )
+    .await
+    .assert_status(StatusCode::ACCEPTED);
+    let middle = post_note(
+        &server,
+        "alice",
+        json!({ "content": "Back to Bob", "to": [PUBLIC], "in_reply_to": bob_reply }),
+    )
+    .await;
+    let middle_uri = format!("https://example.com/users/alice/notes/{}", middle["id"]);
+    let leaf = post_note(
+        &server,
+        "alice",
+        json!({ "content": "And more", "to": [PUBLIC], "in_reply_to": middle_uri }),
+    )
+    .await;
+
+    let response = server
+        .get(&format!("/api/users/alice/notes/{}/thread", middle["id"]))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await;
+    response.assert_status_ok();
+    let thread: Value = response.json();
+    let ids = |notes: &Value| -> Vec<Value> {
+        notes
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|note| note["id"].clone())
+            .collect()
+    };
+    assert_eq!(
+        ids(&thread["ancestors"]),
+        vec![json!(root_uri), json!(bob_reply)]
+    );
+    assert_eq!(thread["ancestors"][1]["actor"], bob.id);
+    assert_eq!(thread["note"]["id"], middle_uri);
+    assert_eq!(
+        ids(&thread["descendants"]),
+        vec![json!(format!(
+            "https://example.com/users/alice/notes/{}",
+            leaf["id"]
+        ))]
+    );
+
+    let root: Value = server
+        .get(&format!("/api/users/alice/notes/{}/thread", root_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .json();
+    assert_eq!(root["ancestors"], json!([]));
+    assert_eq!(root["descendants"].as_array().unwrap().len(), 3);
+    assert_eq!(root["descendants"][0]["id"], bob_reply);
+}
+
+#[tokio::test]
+async fn thread_leaves_out_replies_the_user_may_not_read() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    insert_user(&db, "carol", "Carol").await;
+    let root_id = insert_note(&db, "Root", alice_id, vec![PUBLIC.to_string()]).await;
+    let root_uri = format!("https://example.com/users/alice/notes/{}", root_id);
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let following = storage
+        .add_following(alice_id, &bob.id, &format!("{}/follow", bob.id))
+        .await
+        .unwrap();
+    storage.accept_following(following.id).await.unwrap();
+    state.actors.resolve(&state.storage, &bob.id).await.unwrap();
+
+    let bob_followers = format!("{}/followers", bob.id);
+    let dave = format!("{}/users/dave", remote.base_url);
+    let mut readable = Vec::new();
+    for (n, to) in [
+        (1, json!([bob_followers])),
+        (2, json!(["https://example.com/users/alice"])),
+        (3, json!([dave])),
+    ] {
+        let id = format!("{}/notes/{}", bob.id, n);
+        post_signed(
+            &server,
+            "/users/alice/inbox",
+            &bob,
+            &create(
+                &bob.id,
+                json!({
+                    "id": id,
+                    "type": "Note",
+                    "attributedTo": bob.id,
+                    "content": "Reply",
+                    "to": to,
+                    "inReplyTo": root_uri,
+                    "published": format!("2025-01-0{}T00:00:00Z", n)
+                }),
+            ),
+        )
+        .await
+        .assert_status(StatusCode::ACCEPTED);
+        if n != 3 {
+            readable.push(json!(id));
+        }
+    }
+    post_note(
+        &server,
+        "carol",
+        json!({
+            "content": "Followers only",
+            "visibility": "followers",
+            "in_reply_to": format!("{}/notes/1", bob.id)
+        }),
+    )
+    .await;
+    let deleted = post_note(
+        &server,
+        "carol",
+        json!({ "content": "Never mind", "to": [PUBLIC], "in_reply_to": root_uri }),
+    )
+    .await;
+    server
+        .delete(&format!("/api/users/carol/notes/{}", deleted["id"]))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    let thread: Value = server
+        .get(&format!("/api/users/alice/notes/{}/thread", root_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .json();
+    let ids: Vec<Value> = thread["descendants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|note| note["id"].clone())
+        .collect();
+    assert_eq!(ids, readable);
+}
+
+#[tokio::test]
+async fn replying_to_a_deleted_local_note_is_rejected() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Gone soon", alice_id, vec![]).await;
+    let server = create_test_server(db);
+
+    server
+        .delete(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({
+            "content": "Too late",
+            "to": [PUBLIC],
+            "in_reply_to": format!("https://example.com/users/alice/notes/{}", note_id)
+        }))
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,136 @@
+mod helper;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use calmi::app::jobs;
+use calmi::domain::repositories::FollowsRepository;
+use calmi::storage::postgres::PostgresStorage;
+use helper::{
+    TEST_API_TOKEN, create_test_server, create_test_state, get_signed, insert_user, setup_db,
+    spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+const FOLLOWERS: &str = "https://example.com/users/alice/followers";
+
+async fn post_note(server: &TestServer, body: Value) -> Value {
+    let response = server
+        .post("/api/users/alice/notes")
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&body)
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    response.json()
+}
+
+#[tokio::test]
+async fn public_and_unlisted_notes_are_addressed_to_everyone() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db);
+
+    let public = post_note(&server, json!({ "content": "Hello" })).await;
+    assert_eq!(public["visibility"], "public");
+    let note: Value = server
+        .get(&format!("/users/alice/notes/{}", public["id"]))
+        .await
+        .json();
+    assert_eq!(note["to"], json!([PUBLIC]));
+    assert_eq!(note["cc"], json!([FOLLOWERS]));
+
+    let unlisted = post_note(
+        &server,
+        json!({ "content": "Quietly", "visibility": "unlisted" }),
+    )
+    .await;
+    let path = format!("/users/alice/notes/{}", unlisted["id"]);
+    let note: Value = server.get(&path).await.json();
+    assert_eq!(note["to"], json!([FOLLOWERS]));
+    assert_eq!(note["cc"], json!([PUBLIC]));
+    let create: Value = server.get(&format!("{}/activity", path)).await.json();
+    assert_eq!(create["to"], json!([FOLLOWERS]));
+    assert_eq!(create["cc"], json!([PUBLIC]));
+
+    // Both are listed in the outbox.
+    let outbox: Value = server.get("/users/alice/outbox").await.json();
+    assert_eq!(outbox["totalItems"], 2);
+}
+
+#[tokio::test]
+async fn followers_only_note_is_served_to_signed_followers_only() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let carol = remote.add_actor("carol").await;
+    PostgresStorage::new(db)
+        .add_follow(alice_id, &bob.id, "https://remote.example/follow/1", false)
+        .await
+        .unwrap();
+
+    let note = post_note(
+        &server,
+        json!({ "content": "Friends only", "visibility": "followers" }),
+    )
+    .await;
+    let path = format!("/users/alice/notes/{}", note["id"]);
+
+    server.get(&path).await.assert_status(StatusCode::NOT_FOUND);
+    server
+        .get(&path)
+        .add_header("accept", "text/html")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    get_signed(&server, &path, &carol)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    get_signed(&server, &format!("{}/likes", path), &carol)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let response = get_signed(&server, &path, &bob).await;
+    response.assert_status_ok();
+    let served: Value = response.json();
+    assert_eq!(served["to"], json!([FOLLOWERS]));
+    assert!(served.get("cc").is_none());
+
+    let outbox: Value = server.get("/users/alice/outbox").await.json();
+    assert_eq!(outbox["totalItems"], 0);
+}
+
+#[tokio::test]
+async fn direct_note_reaches_only_the_actors_it_names() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db.clone());
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let carol = remote.add_actor("carol").await;
+    PostgresStorage::new(db)
+        .add_follow(alice_id, &bob.id, "https://remote.example/follow/1", false)
+        .await
+        .unwrap();
+
+    let note = post_note(
+        &server,
+        json!({ "content": "Just you", "visibility": "direct", "to": [carol.id] }),
+    )
+    .await;
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].path, "/users/carol/inbox");
+    assert_eq!(received[0].body["to"], json!([carol.id]));
+    assert!(received[0].body.get("cc").is_none());
+    assert_eq!(received[0].body["object"]["to"], json!([carol.id]));
+
+    let path = format!("/users/alice/notes/{}", note["id"]);
+    get_signed(&server, &path, &bob)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    get_signed(&server, &path, &carol).await.assert_status_ok();
+}
@@ -0,0 +1,250 @@
+mod helper;
+
+use axum::http::StatusCode;
+use calmi::app::jobs;
+use calmi::domain::repositories::{FollowsRepository, NoteLikesRepository};
+use calmi::storage::postgres::PostgresStorage;
+use helper::{
+    TEST_API_TOKEN, create_test_server, create_test_state, insert_note, insert_user, setup_db,
+    spawn_remote_server,
+};
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn deleted_note_is_served_as_tombstone() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Regrettable", alice_id, vec![]).await;
+    let server = create_test_server(db);
+
+    server
+        .delete(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    let response = server.get(&format!("/users/alice/notes/{}", note_id)).await;
+    response.assert_status(StatusCode::GONE);
+    assert_eq!(response.header("content-type"), "application/activity+json");
+    let json: Value = response.json();
+    assert_eq!(
+        json["id"],
+        format!("https://example.com/users/alice/notes/{}", note_id)
+    );
+    assert_eq!(json["type"], "Tombstone");
+    assert_eq!(json["formerType"], "Note");
+    assert!(json["deleted"].is_string());
+    assert!(json.get("content").is_none());
+
+    let outbox: Value = server.get("/users/alice/outbox").await.json();
+    assert_eq!(outbox["totalItems"], 0);
+}
+
+#[tokio::test]
+async fn delete_is_sent_to_followers_and_interacting_actors() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Regrettable", alice_id, vec![]).await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let carol = remote.add_actor("carol").await;
+    storage
+        .add_follow(alice_id, &bob.id, &format!("{}/follows/1", bob.id), false)
+        .await
+        .unwrap();
+    storage
+        .add_like(note_id, &carol.id, &format!("{}/likes/1", carol.id))
+        .await
+        .unwrap();
+
+    server
+        .delete(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 2);
+
+    let mut paths: Vec<String> = remote
+        .received()
+        .await
+        .iter()
+        .map(|request| {
+            assert_eq!(request.body["type"], "Delete");
+            assert_eq!(request.body["actor"], "https://example.com/users/alice");
+            assert_eq!(request.body["object"]["type"], "Tombstone");
+            assert_eq!(
+                request.body["object"]["id"],
+                format!("https://example.com/users/alice/notes/{}", note_id)
+            );
+            request.path.clone()
+        })
+        .collect();
+    paths.sort();
+    assert_eq!(paths, vec!["/users/bob/inbox", "/users/carol/inbox"]);
+}
+
+#[tokio::test]
+async fn cannot_delete_note_of_another_user() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let bob_id = insert_user(&db, "bob", "Bob").await;
+    let note_id = insert_note(&db, "Bob's note", bob_id, vec![]).await;
+    let server = create_test_server(db);
+
+    server
+        .delete(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .get(&format!("/users/bob/notes/{}", note_id))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn deleting_a_note_requires_the_api_token() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Keep me", alice_id, vec![]).await;
+    let server = create_test_server(db);
+
+    server
+        .delete(&format!("/api/users/alice/notes/{}", note_id))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .get(&format!("/users/alice/notes/{}", note_id))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn editing_a_note_keeps_its_previous_versions() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Helo", alice_id, vec![]).await;
+    let server = create_test_server(db);
+
+    for content in ["Hello", "Hello, world"] {
+        let response = server
+            .patch(&format!("/api/users/alice/notes/{}", note_id))
+            .authorization_bearer(TEST_API_TOKEN)
+            .json(&json!({ "content": content }))
+            .await;
+        response.assert_status_ok();
+        let note: Value = response.json();
+        assert_eq!(note["content"], content);
+        assert!(note["updated_at"].is_string());
+    }
+
+    let note: Value = server
+        .get(&format!("/users/alice/notes/{}", note_id))
+        .await
+        .json();
+    assert_eq!(note["content"], "Hello, world");
+    assert!(note["updated"].is_string());
+
+    let revisions: Value = server
+        .get(&format!("/api/users/alice/notes/{}/revisions", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .json();
+    let contents: Vec<&str> = revisions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|revision| revision["content"].as_str().unwrap())
+        .collect();
+    assert_eq!(contents, vec!["Helo", "Hello", "Hello, world"]);
+}
+
+#[tokio::test]
+async fn edit_is_sent_to_followers_as_update() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Helo", alice_id, vec![]).await;
+    let server = create_test_server(db.clone());
+    let state = create_test_state(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    storage
+        .add_follow(alice_id, &bob.id, &format!("{}/follows/1", bob.id), false)
+        .await
+        .unwrap();
+
+    server
+        .patch(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Hello" }))
+        .await
+        .assert_status_ok();
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    let activity = &received[0].body;
+    assert_eq!(received[0].path, "/users/bob/inbox");
+    assert_eq!(activity["type"], "Update");
+    assert_eq!(activity["object"]["type"], "Note");
+    assert_eq!(activity["object"]["content"], "Hello");
+    assert!(activity["object"]["updated"].is_string());
+}
+
+#[tokio::test]
+async fn deleted_note_cannot_be_edited() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Gone", alice_id, vec![]).await;
+    let server = create_test_server(db);
+
+    server
+        .delete(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    server
+        .patch(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Back again" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn deleting_an_edited_note_keeps_its_last_version() {
+    let db = setup_db().await;
+    let alice_id = insert_user(&db, "alice", "Alice").await;
+    let note_id = insert_note(&db, "Helo", alice_id, vec![]).await;
+    let server = create_test_server(db);
+
+    server
+        .patch(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .json(&json!({ "content": "Hello" }))
+        .await
+        .assert_status_ok();
+    server
+        .delete(&format!("/api/users/alice/notes/{}", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    let revisions: Value = server
+        .get(&format!("/api/users/alice/notes/{}/revisions", note_id))
+        .authorization_bearer(TEST_API_TOKEN)
+        .await
+        .json();
+    let contents: Vec<&str> = revisions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|revision| revision["content"].as_str().unwrap())
+        .collect();
+    assert_eq!(contents, vec!["Helo", "Hello"]);
+}
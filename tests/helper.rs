@@ -1,6 +1,10 @@
-use axum_test::TestServer;
+use axum_test::{TestResponse, TestServer};
 use migration::{Migrator, MigratorTrait};
 use sea_orm::{ConnectionTrait, Database, DatabaseConnection, Statement};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
 
 pub async fn setup_db() -> DatabaseConnection {
     let base_url =
@@ -34,15 +38,30 @@ pub async fn setup_db() -> DatabaseConnection {
     test_db
 }
 
-pub fn create_test_server(db: DatabaseConnection) -> TestServer {
-    let config = calmi::config::Config::default();
+#[allow(dead_code)]
+pub const TEST_API_TOKEN: &str = "test-api-token";
+
+#[allow(dead_code)]
+pub fn create_test_state(db: DatabaseConnection) -> calmi::app::state::AppState {
+    let config = calmi::config::Config {
+        api_token: Some(TEST_API_TOKEN.to_string()),
+        // The stand-in remote servers only speak plain HTTP.
+        webfinger_scheme: "http".to_string(),
+        media_dir: std::env::temp_dir().join("calmi-test-media"),
+        ..Default::default()
+    };
     let storage = calmi::storage::postgres::PostgresStorage::new(db);
-    let state = calmi::app::state::AppState::new(config, storage);
-    let app = calmi::app::create_app(state);
+    calmi::app::state::AppState::new(config, storage)
+}
+
+#[allow(dead_code)]
+pub fn create_test_server(db: DatabaseConnection) -> TestServer {
+    let app = calmi::app::create_app(create_test_state(db));
 
     TestServer::new(app).unwrap()
 }
 
+#[allow(dead_code)]
 pub async fn insert_user(db: &DatabaseConnection, username: &str, display_name: &str) -> i64 {
     use calmi::domain::repositories::UsersRepository;
     let storage = calmi::storage::postgres::PostgresStorage::new(db.clone());
@@ -60,11 +79,301 @@ pub async fn insert_note(
     author_id: i64,
     to: Vec<String>,
 ) -> i64 {
+    use calmi::domain::entities::notes;
     use calmi::domain::repositories::NotesRepository;
+    use calmi::domain::visibility::Visibility;
+    use sea_orm::ActiveValue;
     let storage = calmi::storage::postgres::PostgresStorage::new(db.clone());
     let note = storage
-        .add_note(content, author_id, to)
+        .add_note(notes::ActiveModel {
+            id: ActiveValue::NotSet,
+            content: ActiveValue::Set(content.to_string()),
+            author_id: ActiveValue::Set(author_id),
+            created_at: ActiveValue::Set(chrono::Utc::now().naive_utc()),
+            to: ActiveValue::Set(to),
+            deleted_at: ActiveValue::Set(None),
+            updated_at: ActiveValue::Set(None),
+            in_reply_to: ActiveValue::Set(None),
+            context: ActiveValue::Set(None),
+            visibility: ActiveValue::Set(Visibility::Public.as_str().to_string()),
+            summary: ActiveValue::Set(None),
+            sensitive: ActiveValue::Set(false),
+        })
         .await
         .expect("Failed to insert note");
     note.id
 }
+
+/// A stand-in for another fediverse server, serving actor documents and inboxes over plain HTTP.
+#[allow(dead_code)]
+pub struct RemoteServer {
+    pub base_url: String,
+    state: RemoteServerState,
+}
+
+#[allow(dead_code)]
+pub struct RemoteActor {
+    pub id: String,
+    pub key_id: String,
+    pub private_key_pem: String,
+}
+
+/// A request received by one of the stand-in inboxes.
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct ReceivedRequest {
+    pub path: String,
+    pub headers: axum::http::HeaderMap,
+    pub raw_body: Vec<u8>,
+    pub body: Value,
+}
+
+#[derive(Clone)]
+struct RemoteServerState {
+    base_url: String,
+    actors: Arc<RwLock<HashMap<String, Value>>>,
+    gone_actors: Arc<RwLock<Vec<String>>>,
+    actor_fetches: Arc<RwLock<usize>>,
+    objects: Arc<RwLock<HashMap<String, Value>>>,
+    received: Arc<RwLock<Vec<ReceivedRequest>>>,
+    inbox_status: Arc<RwLock<axum::http::StatusCode>>,
+}
+
+#[allow(dead_code)]
+pub async fn spawn_remote_server() -> RemoteServer {
+    use axum::extract::{Path, Query, State};
+    use axum::http::{HeaderMap, StatusCode, Uri};
+    use axum::routing::{get, post};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let state = RemoteServerState {
+        base_url: base_url.clone(),
+        actors: Arc::new(RwLock::new(HashMap::new())),
+        gone_actors: Arc::new(RwLock::new(Vec::new())),
+        actor_fetches: Arc::new(RwLock::new(0)),
+        objects: Arc::new(RwLock::new(HashMap::new())),
+        received: Arc::new(RwLock::new(Vec::new())),
+        inbox_status: Arc::new(RwLock::new(StatusCode::ACCEPTED)),
+    };
+    let app = axum::Router::new()
+        .route(
+            "/.well-known/webfinger",
+            get(
+                |Query(query): Query<HashMap<String, String>>,
+                 State(state): State<RemoteServerState>| async move {
+                    let account = query
+                        .get("resource")
+                        .and_then(|resource| resource.strip_prefix("acct:"))
+                        .ok_or(StatusCode::BAD_REQUEST)?;
+                    let (username, _) = account.split_once('@').ok_or(StatusCode::BAD_REQUEST)?;
+                    if !state.actors.read().await.contains_key(username) {
+                        return Err(StatusCode::NOT_FOUND);
+                    }
+                    Ok(axum::Json(json!({
+                        "subject": format!("acct:{}", account),
+                        "links": [{
+                            "rel": "self",
+                            "type": "application/activity+json",
+                            "href": format!("{}/users/{}", state.base_url, username)
+                        }]
+                    })))
+                },
+            ),
+        )
+        .route(
+            "/users/{username}",
+            get(
+                |Path(username): Path<String>, State(state): State<RemoteServerState>| async move {
+                    *state.actor_fetches.write().await += 1;
+                    if state.gone_actors.read().await.contains(&username) {
+                        return Err(StatusCode::GONE);
+                    }
+                    state
+                        .actors
+                        .read()
+                        .await
+                        .get(&username)
+                        .cloned()
+                        .map(axum::Json)
+                        .ok_or(StatusCode::NOT_FOUND)
+                },
+            ),
+        )
+        .route(
+            "/objects/{name}",
+            get(
+                |Path(name): Path<String>, State(state): State<RemoteServerState>| async move {
+                    state
+                        .objects
+                        .read()
+                        .await
+                        .get(&name)
+                        .cloned()
+                        .map(axum::Json)
+                        .ok_or(StatusCode::NOT_FOUND)
+                },
+            ),
+        )
+        .route(
+            "/users/{username}/inbox",
+            post(
+                |State(state): State<RemoteServerState>,
+                 uri: Uri,
+                 headers: HeaderMap,
+                 body: axum::body::Bytes| async move {
+                    state.received.write().await.push(ReceivedRequest {
+                        path: uri.path().to_string(),
+                        headers,
+                        raw_body: body.to_vec(),
+                        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+                    });
+                    *state.inbox_status.read().await
+                },
+            ),
+        )
+        .with_state(state.clone());
+
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    RemoteServer { base_url, state }
+}
+
+#[allow(dead_code)]
+impl RemoteServer {
+    pub async fn add_actor(&self, username: &str) -> RemoteActor {
+        let keypair = calmi::federation::keys::generate_keypair().unwrap();
+
+        let id = format!("{}/users/{}", self.base_url, username);
+        let key_id = format!("{}#main-key", id);
+        self.set_actor_document(
+            username,
+            json!({
+                "@context": ["https://www.w3.org/ns/activitystreams", "https://w3id.org/security/v1"],
+                "id": id,
+                "type": "Person",
+                "preferredUsername": username,
+                "inbox": format!("{}/inbox", id),
+                "followers": format!("{}/followers", id),
+                "publicKey": {
+                    "id": key_id,
+                    "owner": id,
+                    "publicKeyPem": keypair.public_key_pem
+                }
+            }),
+        )
+        .await;
+
+        RemoteActor {
+            id,
+            key_id,
+            private_key_pem: keypair.private_key_pem,
+        }
+    }
+
+    pub async fn set_actor_document(&self, username: &str, document: Value) {
+        self.state
+            .actors
+            .write()
+            .await
+            .insert(username.to_string(), document);
+    }
+
+    /// Answers 410 Gone for the actor from now on, as servers do once an account is deleted.
+    pub async fn remove_actor(&self, username: &str) {
+        self.state.actors.write().await.remove(username);
+        self.state
+            .gone_actors
+            .write()
+            .await
+            .push(username.to_string());
+    }
+
+    pub async fn actor_document(&self, username: &str) -> Value {
+        self.state.actors.read().await[username].clone()
+    }
+
+    /// Serves `document` at `/objects/{name}` and returns its URL.
+    pub async fn set_object(&self, name: &str, document: Value) -> String {
+        self.state
+            .objects
+            .write()
+            .await
+            .insert(name.to_string(), document);
+        format!("{}/objects/{}", self.base_url, name)
+    }
+
+    /// How many times any actor document has been fetched.
+    pub async fn actor_fetches(&self) -> usize {
+        *self.state.actor_fetches.read().await
+    }
+
+    /// Requests POSTed to any inbox on this server, oldest first.
+    pub async fn received(&self) -> Vec<ReceivedRequest> {
+        self.state.received.read().await.clone()
+    }
+
+    /// Makes every inbox on this server answer with `status` from now on.
+    pub async fn set_inbox_status(&self, status: axum::http::StatusCode) {
+        *self.state.inbox_status.write().await = status;
+    }
+}
+
+/// POSTs `activity` to `path`, signed with the key of `actor`.
+#[allow(dead_code)]
+pub async fn post_signed(
+    server: &TestServer,
+    path: &str,
+    actor: &RemoteActor,
+    activity: &Value,
+) -> TestResponse {
+    use axum::http::Method;
+    use calmi::federation::http_signature::sign_request;
+
+    let body = serde_json::to_vec(activity).unwrap();
+    let signed = sign_request(
+        &Method::POST,
+        path,
+        "example.com",
+        Some(&body),
+        &actor.key_id,
+        &actor.private_key_pem,
+    )
+    .expect("Failed to sign request");
+
+    server
+        .post(path)
+        .add_header("host", "example.com")
+        .add_header("date", signed.date)
+        .add_header("digest", signed.digest.unwrap())
+        .add_header("signature", signed.signature)
+        .content_type("application/activity+json")
+        .bytes(body.into())
+        .await
+}
+
+/// GETs `path` as ActivityPub JSON, signed with the key of `actor`.
+#[allow(dead_code)]
+pub async fn get_signed(server: &TestServer, path: &str, actor: &RemoteActor) -> TestResponse {
+    use axum::http::Method;
+    use calmi::federation::http_signature::sign_request;
+
+    let signed = sign_request(
+        &Method::GET,
+        path,
+        "example.com",
+        None,
+        &actor.key_id,
+        &actor.private_key_pem,
+    )
+    .expect("Failed to sign request");
+
+    server
+        .get(path)
+        .add_header("accept", "application/activity+json")
+        .add_header("host", "example.com")
+        .add_header("date", signed.date)
+        .add_header("signature", signed.signature)
+        .await
+}
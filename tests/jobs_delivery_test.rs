@@ -0,0 +1,179 @@
+mod helper;
+
+use axum::http::{Method, StatusCode};
+use calmi::app::jobs::{self, delivery};
+use calmi::domain::repositories::{JobsRepository, UsersRepository};
+use calmi::federation::http_signature::{
+    build_signing_string, parse_signature_header, verify_digest, verify_signature,
+};
+use calmi::storage::postgres::PostgresStorage;
+use helper::{create_test_state, insert_user, setup_db, spawn_remote_server};
+use serde_json::json;
+
+fn note_activity() -> serde_json::Value {
+    json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": "https://example.com/users/alice/notes/1/activity",
+        "type": "Create",
+        "actor": "https://example.com/users/alice",
+        "object": "https://example.com/users/alice/notes/1"
+    })
+}
+
+#[tokio::test]
+async fn delivers_activity_signed_with_the_sender_key() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let state = create_test_state(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    let alice = storage
+        .find_user_by_username("alice")
+        .await
+        .unwrap()
+        .unwrap();
+
+    let inbox = format!("{}/inbox", bob.id);
+    delivery::enqueue(&storage, &alice, &[inbox.clone(), inbox], &note_activity())
+        .await
+        .unwrap();
+
+    let processed = jobs::run_due_jobs(&state).await.unwrap();
+    assert_eq!(processed, 1);
+
+    let received = remote.received().await;
+    assert_eq!(received.len(), 1);
+    let request = &received[0];
+    assert_eq!(request.path, "/users/bob/inbox");
+    assert_eq!(request.body, note_activity());
+
+    let signature =
+        parse_signature_header(request.headers.get("signature").unwrap().to_str().unwrap())
+            .unwrap();
+    assert_eq!(signature.key_id, "https://example.com/users/alice#main-key");
+    let signing_string = build_signing_string(
+        &Method::POST,
+        &request.path,
+        &request.headers,
+        &signature.headers,
+    )
+    .unwrap();
+    verify_signature(
+        &signing_string,
+        &signature.signature,
+        alice.public_key_pem.as_deref().unwrap(),
+    )
+    .expect("Signature should verify with the sender's public key");
+    verify_digest(&request.headers, &request.raw_body).unwrap();
+
+    assert!(storage.list_jobs(delivery::KIND).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn reschedules_delivery_when_remote_is_unavailable() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let state = create_test_state(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    remote
+        .set_inbox_status(StatusCode::SERVICE_UNAVAILABLE)
+        .await;
+    let alice = storage
+        .find_user_by_username("alice")
+        .await
+        .unwrap()
+        .unwrap();
+
+    delivery::enqueue(
+        &storage,
+        &alice,
+        &[format!("{}/inbox", bob.id)],
+        &note_activity(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+
+    let pending = storage.list_jobs(delivery::KIND).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    let job = &pending[0];
+    assert_eq!(job.attempts, 1);
+    assert!(job.failed_at.is_none());
+    assert!(job.run_at > chrono::Utc::now().naive_utc());
+    assert!(job.last_error.as_deref().unwrap().contains("503"));
+
+    // Not due again until the backoff has elapsed.
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 0);
+    assert_eq!(remote.received().await.len(), 1);
+}
+
+#[tokio::test]
+async fn gives_up_once_the_retry_horizon_has_passed() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let mut state = create_test_state(db.clone());
+    state.config.job_retry_horizon = std::time::Duration::ZERO;
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    remote
+        .set_inbox_status(StatusCode::INTERNAL_SERVER_ERROR)
+        .await;
+    let alice = storage
+        .find_user_by_username("alice")
+        .await
+        .unwrap()
+        .unwrap();
+
+    delivery::enqueue(
+        &storage,
+        &alice,
+        &[format!("{}/inbox", bob.id)],
+        &note_activity(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+
+    let failed = storage.list_jobs(delivery::KIND).await.unwrap();
+    assert_eq!(failed.len(), 1);
+    assert!(failed[0].failed_at.is_some());
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn does_not_retry_when_remote_rejects_the_activity() {
+    let db = setup_db().await;
+    insert_user(&db, "alice", "Alice").await;
+    let state = create_test_state(db.clone());
+    let storage = PostgresStorage::new(db);
+    let remote = spawn_remote_server().await;
+    let bob = remote.add_actor("bob").await;
+    remote.set_inbox_status(StatusCode::GONE).await;
+    let alice = storage
+        .find_user_by_username("alice")
+        .await
+        .unwrap()
+        .unwrap();
+
+    delivery::enqueue(
+        &storage,
+        &alice,
+        &[format!("{}/inbox", bob.id)],
+        &note_activity(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(jobs::run_due_jobs(&state).await.unwrap(), 1);
+
+    let failed = storage.list_jobs(delivery::KIND).await.unwrap();
+    assert_eq!(failed.len(), 1);
+    assert!(failed[0].failed_at.is_some());
+    assert!(failed[0].last_error.as_deref().unwrap().contains("410"));
+}
@@ -0,0 +1,154 @@
+mod helper;
+
+use calmi::domain::repositories::RemoteActorsRepository;
+use calmi::federation::{self, actor::ActorResolver};
+use calmi::storage::postgres::PostgresStorage;
+use helper::{RemoteServer, setup_db, spawn_remote_server};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+fn actor_document(remote: &RemoteServer, name: &str) -> Value {
+    let id = format!("{}/users/bob", remote.base_url);
+    json!({
+        "@context": [
+            "https://www.w3.org/ns/activitystreams",
+            "https://w3id.org/security/v1",
+            {"manuallyApprovesFollowers": "as:manuallyApprovesFollowers"}
+        ],
+        "id": id,
+        "type": "Person",
+        "preferredUsername": "bob",
+        "name": name,
+        "inbox": format!("{}/inbox", id),
+        "endpoints": {"sharedInbox": format!("{}/inbox", remote.base_url)},
+        "icon": {
+            "type": "Image",
+            "mediaType": "image/png",
+            "url": format!("{}/avatars/bob.png", remote.base_url)
+        },
+        "publicKey": {
+            "id": format!("{}#main-key", id),
+            "owner": id,
+            "publicKeyPem": "-----BEGIN PUBLIC KEY-----\n...\n-----END PUBLIC KEY-----\n"
+        }
+    })
+}
+
+fn resolver(ttl: Duration) -> ActorResolver {
+    ActorResolver::new(federation::build_http_client(), ttl)
+}
+
+#[tokio::test]
+async fn resolves_and_stores_actor_fields() {
+    let storage = PostgresStorage::new(setup_db().await);
+    let remote = spawn_remote_server().await;
+    remote
+        .set_actor_document("bob", actor_document(&remote, "Bob"))
+        .await;
+    let uri = format!("{}/users/bob", remote.base_url);
+
+    let actor = resolver(Duration::from_secs(3600))
+        .resolve(&storage, &uri)
+        .await
+        .unwrap();
+
+    assert_eq!(actor.uri, uri);
+    assert_eq!(actor.inbox, format!("{}/inbox", uri));
+    assert_eq!(
+        actor.shared_inbox,
+        Some(format!("{}/inbox", remote.base_url))
+    );
+    assert_eq!(actor.public_key_id, Some(format!("{}#main-key", uri)));
+    assert!(actor.public_key_pem.is_some());
+    assert_eq!(actor.preferred_username.as_deref(), Some("bob"));
+    assert_eq!(actor.name.as_deref(), Some("Bob"));
+    assert_eq!(
+        actor.icon_url,
+        Some(format!("{}/avatars/bob.png", remote.base_url))
+    );
+
+    let stored = storage.find_remote_actor_by_uri(&uri).await.unwrap();
+    assert_eq!(stored, Some(actor));
+}
+
+#[tokio::test]
+async fn serves_cached_copy_within_ttl() {
+    let storage = PostgresStorage::new(setup_db().await);
+    let remote = spawn_remote_server().await;
+    remote
+        .set_actor_document("bob", actor_document(&remote, "Bob"))
+        .await;
+    let uri = format!("{}/users/bob", remote.base_url);
+    let resolver = resolver(Duration::from_secs(3600));
+
+    resolver.resolve(&storage, &uri).await.unwrap();
+    remote
+        .set_actor_document("bob", actor_document(&remote, "Robert"))
+        .await;
+    let actor = resolver.resolve(&storage, &uri).await.unwrap();
+
+    assert_eq!(actor.name.as_deref(), Some("Bob"));
+    assert_eq!(remote.actor_fetches().await, 1);
+}
+
+#[tokio::test]
+async fn refetches_once_ttl_has_passed() {
+    let storage = PostgresStorage::new(setup_db().await);
+    let remote = spawn_remote_server().await;
+    remote
+        .set_actor_document("bob", actor_document(&remote, "Bob"))
+        .await;
+    let uri = format!("{}/users/bob", remote.base_url);
+    let resolver = resolver(Duration::ZERO);
+
+    resolver.resolve(&storage, &uri).await.unwrap();
+    remote
+        .set_actor_document("bob", actor_document(&remote, "Robert"))
+        .await;
+    let actor = resolver.resolve(&storage, &uri).await.unwrap();
+
+    assert_eq!(actor.name.as_deref(), Some("Robert"));
+    assert_eq!(remote.actor_fetches().await, 2);
+}
+
+#[tokio::test]
+async fn keeps_stale_copy_when_refresh_fails() {
+    let storage = PostgresStorage::new(setup_db().await);
+    let remote = spawn_remote_server().await;
+    remote
+        .set_actor_document("bob", actor_document(&remote, "Bob"))
+        .await;
+    let uri = format!("{}/users/bob", remote.base_url);
+    let resolver = resolver(Duration::ZERO);
+
+    resolver.resolve(&storage, &uri).await.unwrap();
+    remote
+        .set_actor_document("bob", json!({"type": "Person"}))
+        .await;
+    let actor = resolver.resolve(&storage, &uri).await.unwrap();
+
+    assert_eq!(actor.name.as_deref(), Some("Bob"));
+}
+
+#[tokio::test]
+async fn rejects_document_served_under_another_id() {
+    let storage = PostgresStorage::new(setup_db().await);
+    let remote = spawn_remote_server().await;
+    let mut document = actor_document(&remote, "Bob");
+    document["id"] = json!("https://elsewhere.example/users/bob");
+    remote.set_actor_document("bob", document).await;
+    let uri = format!("{}/users/bob", remote.base_url);
+
+    let result = resolver(Duration::from_secs(3600))
+        .resolve(&storage, &uri)
+        .await;
+
+    assert!(result.is_err());
+    assert!(
+        storage
+            .find_remote_actor_by_uri(&uri)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}